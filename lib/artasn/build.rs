@@ -16,7 +16,9 @@ fn main() {
         &syntax,
         GEN_FILE,
         syn::parse_quote! {
-            use crate::compiler::ast::extra::AstSyntaxTokenLiteral;
+            use crate::compiler::ast::extra::{
+                AstEncodingControlInstructions, AstEncodingInstruction, AstSyntaxTokenLiteral,
+            };
 
             enum_str! {
                 pub enum Keyword {
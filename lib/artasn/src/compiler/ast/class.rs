@@ -71,7 +71,7 @@ fn parse_class_value_field(
                             });
                         }
 
-                        ObjectClassField::Value(ObjectClassFieldValue {
+                        ObjectClassField::Value(Box::new(ObjectClassFieldValue {
                             field_type: ObjectClassFieldValueType::ObjectClassFieldReference(
                                 ocf.clone(),
                             ),
@@ -80,7 +80,7 @@ fn parse_class_value_field(
                             } else {
                                 None
                             },
-                        })
+                        }))
                     }
                     _ => {
                         let target_type = field_type.resolve(parser.context)?;
@@ -137,10 +137,10 @@ fn parse_class_value_field(
                             }
                         };
 
-                        ObjectClassField::Value(ObjectClassFieldValue {
+                        ObjectClassField::Value(Box::new(ObjectClassFieldValue {
                             field_type: ObjectClassFieldValueType::TaggedType(field_type),
                             option,
-                        })
+                        }))
                     }
                 },
             }
@@ -162,7 +162,7 @@ fn parse_class_value_field(
                 }
             };
 
-            ObjectClassField::Value(ObjectClassFieldValue {
+            ObjectClassField::Value(Box::new(ObjectClassFieldValue {
                 field_type: ObjectClassFieldValueType::OpenTypeReference(
                     open_type_ref
                         .element
@@ -172,7 +172,7 @@ fn parse_class_value_field(
                         .clone(),
                 ),
                 option,
-            })
+            }))
         }
     })
 }
@@ -12,6 +12,7 @@ use super::constraint_tree::*;
 enum ConstraintContext {
     Contextless,
     WithinSize,
+    WithinPermittedAlphabet,
 }
 
 fn parse_constraint(
@@ -221,7 +222,7 @@ fn parse_subtype_element(
         }};
     }
 
-    if ctx == ConstraintContext::WithinSize
+    if ctx != ConstraintContext::Contextless
         && !matches!(
             &ast_subtype_element.element,
             AstSubtypeElement::SingleValueConstraint(_)
@@ -229,10 +230,13 @@ fn parse_subtype_element(
         )
     {
         return Err(Error {
-            kind: ErrorKind::Ast(
-                "only single value and value range constraints are allowed in SIZE constraints"
-                    .to_string(),
-            ),
+            kind: ErrorKind::Ast(format!(
+                "only single value and value range constraints are allowed in {} constraints",
+                match ctx {
+                    ConstraintContext::WithinSize => "SIZE",
+                    _ => "permitted alphabet",
+                }
+            )),
             loc: ast_subtype_element.loc,
         });
     }
@@ -264,7 +268,7 @@ fn parse_subtype_element(
             match &constrained_type.ty {
                 BuiltinType::Integer(_) => (),
                 other => {
-                    if ctx != ConstraintContext::WithinSize {
+                    if ctx == ConstraintContext::Contextless {
                         return Err(Error {
                             kind: ErrorKind::Ast(format!(
                                 "value range constraints cannot be applied to type {}",
@@ -354,6 +358,27 @@ fn parse_subtype_element(
                 ConstraintContext::WithinSize,
             )?)
         }
+        AstSubtypeElement::PermittedAlphabet(alphabet) => {
+            match &constrained_type.ty {
+                BuiltinType::CharacterString(_) => (),
+                other => {
+                    return Err(Error {
+                        kind: ErrorKind::Ast(format!(
+                            "permitted alphabet constraints cannot be applied to type {}",
+                            other,
+                        )),
+                        loc: alphabet.loc,
+                    })
+                }
+            }
+            SubtypeElement::PermittedAlphabet(parse_constraint(
+                parser,
+                &alphabet.element.0,
+                constrained_type,
+                parameters,
+                ConstraintContext::WithinPermittedAlphabet,
+            )?)
+        }
         AstSubtypeElement::InnerTypeConstraints(itc) => SubtypeElement::InnerType(
             parse_inner_type_constraints(parser, itc, constrained_type, parameters)?,
         ),
@@ -550,11 +575,13 @@ fn parse_inner_type_constraints(
                         )?),
                         None => None,
                     };
-                    let presence = component_constraint.presence.as_ref().map(|presence| match &presence.element {
+                    let presence = component_constraint.presence.as_ref().map(|presence| {
+                        match &presence.element {
                             AstPresenceConstraint::PresencePresent(_) => Presence::Present,
                             AstPresenceConstraint::PresenceAbsent(_) => Presence::Absent,
                             AstPresenceConstraint::PresenceOptional(_) => Presence::Optional,
-                        });
+                        }
+                    });
                     if !is_choice && presence.is_some() && !is_optional {
                         return Err(Error {
                             kind: ErrorKind::Ast(format!(
@@ -666,7 +693,7 @@ fn parse_constrained_type(
 
                     let components = types::flatten_structure_components(structure);
                     let mut component_constraints = Vec::with_capacity(components.len());
-                    for component in components {
+                    for (component, _) in components {
                         let resolved_component = resolved_components
                             .iter()
                             .find(|resolved_component| {
@@ -720,8 +747,9 @@ fn parse_constrained_type(
                         _ => unreachable!(),
                     };
 
-                    let mut alternative_constraints = Vec::with_capacity(choice.element.0.len());
-                    for alternative in &choice.element.0 {
+                    let alternatives = types::flatten_choice_alternatives(choice);
+                    let mut alternative_constraints = Vec::with_capacity(alternatives.len());
+                    for (alternative, _) in alternatives {
                         let resolved_alternative = resolved_alternatives
                             .iter()
                             .find(|resolved_alternative| {
@@ -787,6 +815,9 @@ pub(crate) fn parse_type_constraint(
     parameters: &[(&String, &Parameter)],
 ) -> Result<PendingConstraint> {
     Ok(match &ty.element {
+        AstType::EncodingPrefixedType(prefixed) => {
+            parse_type_constraint(parser, &prefixed.element.ty, constrained_type, parameters)?
+        }
         AstType::TaggedType(tagged_type) => parse_constrained_type(
             parser,
             &tagged_type.element.ty,
@@ -846,9 +877,10 @@ fn resolve_type_assignment<'a>(
         AstTypeAssignmentSubject::Type(ast_type) => ast_type,
         _ => panic!("parameterized type resolves to a CLASS"),
     };
-    let constrained = match &ast_type.element {
+    let constrained = match &types::strip_encoding_prefixes(ast_type).element {
         AstType::TaggedType(tagged_type) => &tagged_type.element.ty,
         AstType::ConstrainedType(constrained) => constrained,
+        AstType::EncodingPrefixedType(_) => unreachable!(),
     };
     Ok(match &constrained.element {
         AstConstrainedType::Suffixed(suffixed) => match &suffixed.element.ty.element {
@@ -114,3 +114,110 @@ impl TokenLiteralParser for TokenStream {
         })
     }
 }
+
+/// The raw tokens of an encoding instruction inside an encoding prefix (e.g. `[XER:ATTRIBUTE]`),
+/// not including the closing bracket.
+#[derive(Debug, Clone)]
+pub struct AstEncodingInstruction(pub Vec<Token>);
+
+impl Parseable for AstEncodingInstruction {
+    fn parse(context: ParseContext) -> ParseResult<Self> {
+        // encoding instructions always start with an all-uppercase keyword (e.g. ATTRIBUTE or BASE64),
+        // which distinguishes encoding prefixes from tags like `[0]` or `[APPLICATION 0]`
+        let first_token = match context.tokens.try_next(Some(OperatorMode::Single)) {
+            Ok(token) => token,
+            Err(err) => return ParseResult::Fail(err),
+        };
+        match (&first_token.kind, &first_token.data) {
+            (TokenKind::TypeReference, Some(TokenData::Named(name)))
+                if name
+                    .chars()
+                    .all(|ch| ch.is_ascii_uppercase() || ch.is_ascii_digit() || ch == '-') => {}
+            _ => {
+                return ParseResult::Fail(Error {
+                    kind: ErrorKind::ExpectingOther {
+                        expecting: vec![(TokenKind::UppercaseReference, None)],
+                        found: first_token.clone(),
+                    },
+                    loc: first_token.loc,
+                })
+            }
+        }
+
+        let mut tokens = vec![first_token];
+        loop {
+            let cursor = context.tokens.cursor();
+            let next_token = match context.tokens.try_next(Some(OperatorMode::Single)) {
+                Ok(token) => token,
+                Err(err) => return ParseResult::Fail(err),
+            };
+            match (&next_token.kind, &next_token.data) {
+                (TokenKind::Operator, Some(TokenData::Operator(Operator::CloseBracket))) => {
+                    context.tokens.set_cursor(cursor);
+                    break;
+                }
+                (TokenKind::Eoi, _) => {
+                    return ParseResult::Fail(Error {
+                        kind: ErrorKind::ExpectingOther {
+                            expecting: vec![(
+                                TokenKind::Operator,
+                                Some(TokenData::Operator(Operator::CloseBracket)),
+                            )],
+                            found: next_token.clone(),
+                        },
+                        loc: next_token.loc,
+                    })
+                }
+                _ => tokens.push(next_token),
+            }
+        }
+
+        let loc = token_stream_loc(&tokens);
+        ParseResult::Ok(AstElement::new(AstEncodingInstruction(tokens), loc))
+    }
+}
+
+/// The raw tokens of the encoding instructions in an `ENCODING-CONTROL` section,
+/// up to (but not including) the next `ENCODING-CONTROL` or `END` keyword.
+#[derive(Debug, Clone)]
+pub struct AstEncodingControlInstructions(pub Vec<Token>);
+
+impl Parseable for AstEncodingControlInstructions {
+    fn parse(context: ParseContext) -> ParseResult<Self> {
+        let mut tokens = Vec::new();
+        loop {
+            let cursor = context.tokens.cursor();
+            let next_token = match context.tokens.try_next(Some(OperatorMode::Single)) {
+                Ok(token) => token,
+                Err(err) => return ParseResult::Fail(err),
+            };
+            match (&next_token.kind, &next_token.data) {
+                (
+                    TokenKind::Keyword,
+                    Some(TokenData::Keyword(Keyword::End | Keyword::EncodingControl)),
+                )
+                | (TokenKind::Eoi, _) => {
+                    context.tokens.set_cursor(cursor);
+                    break;
+                }
+                _ => tokens.push(next_token),
+            }
+        }
+
+        let loc = if tokens.is_empty() {
+            context.loc()
+        } else {
+            token_stream_loc(&tokens)
+        };
+        ParseResult::Ok(AstElement::new(AstEncodingControlInstructions(tokens), loc))
+    }
+}
+
+fn token_stream_loc(tokens: &[Token]) -> Loc {
+    let first_token = &tokens[0];
+    let last_token = tokens.last().unwrap();
+    Loc::new(
+        first_token.loc.offset,
+        (last_token.loc.offset + last_token.loc.len) - first_token.loc.offset,
+    )
+}
@@ -0,0 +1,506 @@
+use crate::{
+    compiler::{parser::*, Context},
+    module::QualifiedIdentifier,
+    types::*,
+};
+
+use super::AstParser;
+
+/// The encoding reference of the XML Encoding Rules (X.693 clause 12).
+const XER_ENCODING_REFERENCE: &str = "XER";
+
+/// A target of an encoding instruction in an `ENCODING-CONTROL` section.
+#[derive(Debug, Clone)]
+pub(crate) enum EncodingInstructionTarget {
+    /// `ALL`, which targets all types declared in the module.
+    All,
+    /// A type reference, optionally followed by a path of component identifiers (e.g. `Person.name`).
+    Type {
+        name: AstElement<String>,
+        components: Vec<AstElement<String>>,
+    },
+}
+
+/// An encoding instruction from an `ENCODING-CONTROL XER` section, with its targets resolved to type identifiers.
+pub(crate) struct PendingEncodingInstruction {
+    pub ident: QualifiedIdentifier,
+    pub components: Vec<AstElement<String>>,
+    pub instruction: XerEncodingInstruction,
+}
+
+struct InstructionParser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    loc: Loc,
+}
+
+impl<'a> InstructionParser<'a> {
+    fn new(tokens: &'a [Token], loc: Loc) -> InstructionParser<'a> {
+        InstructionParser {
+            tokens,
+            pos: 0,
+            loc,
+        }
+    }
+
+    fn is_eoi(&self) -> bool {
+        self.pos >= self.tokens.len()
+    }
+
+    fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn error_loc(&self) -> Loc {
+        match self.peek() {
+            Some(token) => token.loc,
+            None => Loc::new(self.loc.offset + self.loc.len, 0),
+        }
+    }
+
+    fn error<T>(&self, message: String) -> Result<T> {
+        Err(Error {
+            kind: ErrorKind::Ast(message),
+            loc: self.error_loc(),
+        })
+    }
+
+    /// Returns the name of the token if it is an uppercase word (e.g. `ATTRIBUTE` or `ALL`).
+    fn token_word(token: &Token) -> Option<&str> {
+        match (&token.kind, &token.data) {
+            (TokenKind::TypeReference, Some(TokenData::Named(name)))
+                if name
+                    .chars()
+                    .all(|ch| ch.is_ascii_uppercase() || ch.is_ascii_digit() || ch == '-') =>
+            {
+                Some(name.as_str())
+            }
+            (TokenKind::Keyword, Some(TokenData::Keyword(keyword))) => Some(keyword.name()),
+            _ => None,
+        }
+    }
+
+    fn peek_word(&self, word: &str) -> bool {
+        self.peek()
+            .and_then(Self::token_word)
+            .is_some_and(|name| name == word)
+    }
+
+    fn next_word(&mut self) -> Result<AstElement<&'a str>> {
+        match self.peek() {
+            Some(token) => match Self::token_word(token) {
+                Some(word) => {
+                    self.pos += 1;
+                    Ok(AstElement::new(word, token.loc))
+                }
+                None => self.error(format!(
+                    "expecting encoding instruction keyword, found {}",
+                    token
+                )),
+            },
+            None => self.error("unexpected end of encoding instruction".to_string()),
+        }
+    }
+
+    fn expect_word(&mut self, word: &str) -> Result<()> {
+        if self.peek_word(word) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            self.error(format!("expecting {}", word))
+        }
+    }
+
+    fn next_operator(&mut self, operator: Operator) -> bool {
+        match self.peek() {
+            Some(Token {
+                kind: TokenKind::Operator,
+                data: Some(TokenData::Operator(op)),
+                ..
+            }) if *op == operator => {
+                self.pos += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn expect_string(&mut self) -> Result<String> {
+        match self.peek() {
+            Some(Token {
+                kind: TokenKind::String,
+                data: Some(TokenData::String(_, str)),
+                ..
+            }) => {
+                self.pos += 1;
+                Ok(str.clone())
+            }
+            _ => self.error("expecting string".to_string()),
+        }
+    }
+
+    /// Parses the value of `DEFAULT-FOR-EMPTY AS value`,
+    /// and returns the text of the value that is written in the element content.
+    fn parse_default_for_empty_value(&mut self) -> Result<String> {
+        let negative = self.next_operator(Operator::Negative);
+        let token = match self.peek() {
+            Some(token) => token,
+            None => return self.error("expecting value".to_string()),
+        };
+        let text = match (&token.kind, &token.data) {
+            (TokenKind::String, Some(TokenData::String(_, str))) if !negative => str.clone(),
+            (TokenKind::Number, Some(TokenData::Number(num))) => match negative {
+                true => format!("-{}", num),
+                false => num.to_string(),
+            },
+            (TokenKind::Keyword, Some(TokenData::Keyword(Keyword::True))) if !negative => {
+                "true".to_string()
+            }
+            (TokenKind::Keyword, Some(TokenData::Keyword(Keyword::False))) if !negative => {
+                "false".to_string()
+            }
+            (TokenKind::ValueReference, Some(TokenData::Named(name))) if !negative => name.clone(),
+            _ => return self.error(format!("expecting value, found {}", token)),
+        };
+        self.pos += 1;
+        Ok(text)
+    }
+
+    /// Parses the arguments of the encoding instruction with the specified keyword.
+    /// Returns `None` for instructions that do not apply to types (i.e. `GLOBAL-DEFAULTS`).
+    fn parse_instruction(
+        &mut self,
+        keyword: &AstElement<&str>,
+    ) -> Result<Option<XerEncodingInstruction>> {
+        Ok(Some(match keyword.element {
+            "ANY-ATTRIBUTES" | "ANY-ELEMENT" => {
+                if self.peek_word("FROM") || self.peek_word("EXCEPT") {
+                    return self.error("namespace restrictions are not supported".to_string());
+                }
+                match keyword.element {
+                    "ANY-ATTRIBUTES" => XerEncodingInstruction::AnyAttributes,
+                    _ => XerEncodingInstruction::AnyElement,
+                }
+            }
+            "ATTRIBUTE" => XerEncodingInstruction::Attribute,
+            "BASE64" => XerEncodingInstruction::Base64,
+            "DECIMAL" => XerEncodingInstruction::Decimal,
+            "DEFAULT-FOR-EMPTY" => {
+                self.expect_word("AS")?;
+                XerEncodingInstruction::DefaultForEmpty(self.parse_default_for_empty_value()?)
+            }
+            "ELEMENT" => XerEncodingInstruction::Element,
+            "EMBED-VALUES" => XerEncodingInstruction::EmbedValues,
+            "LIST" => XerEncodingInstruction::List,
+            "NAME" => {
+                self.expect_word("AS")?;
+                XerEncodingInstruction::Name(if self.peek_word("CAPITALIZED") {
+                    self.pos += 1;
+                    XerNameModifier::Capitalized
+                } else if self.peek_word("UNCAPITALIZED") {
+                    self.pos += 1;
+                    XerNameModifier::Uncapitalized
+                } else if self.peek_word("UPPERCASED") {
+                    self.pos += 1;
+                    XerNameModifier::Uppercased
+                } else if self.peek_word("LOWERCASED") {
+                    self.pos += 1;
+                    XerNameModifier::Lowercased
+                } else {
+                    XerNameModifier::Name(self.expect_string()?)
+                })
+            }
+            "NAMESPACE" => XerEncodingInstruction::Namespace(if self.peek_word("AS") {
+                self.pos += 1;
+                let uri = self.expect_string()?;
+                let prefix = if self.peek_word("PREFIX") {
+                    self.pos += 1;
+                    Some(self.expect_string()?)
+                } else {
+                    None
+                };
+                Some(XerNamespace { uri, prefix })
+            } else {
+                None
+            }),
+            "PI-OR-COMMENT" => {
+                self.expect_word("AS")?;
+                let text = self.expect_string()?;
+                let position = self.next_word()?;
+                let position = match position.element {
+                    "BEFORE-TAG" => XerPiOrCommentPosition::BeforeTag,
+                    "BEFORE-VALUE" => XerPiOrCommentPosition::BeforeValue,
+                    "AFTER-VALUE" => XerPiOrCommentPosition::AfterValue,
+                    "AFTER-TAG" => XerPiOrCommentPosition::AfterTag,
+                    other => {
+                        return Err(Error {
+                            kind: ErrorKind::Ast(format!(
+                                "expecting PI-OR-COMMENT position, found {}",
+                                other
+                            )),
+                            loc: position.loc,
+                        })
+                    }
+                };
+                XerEncodingInstruction::PiOrComment { text, position }
+            }
+            "TEXT" => XerEncodingInstruction::Text,
+            "UNTAGGED" => XerEncodingInstruction::Untagged,
+            "USE-NIL" => XerEncodingInstruction::UseNil,
+            "USE-NUMBER" => XerEncodingInstruction::UseNumber,
+            "USE-ORDER" => XerEncodingInstruction::UseOrder,
+            "USE-QNAME" => XerEncodingInstruction::UseQName,
+            "USE-TYPE" => XerEncodingInstruction::UseType,
+            "USE-UNION" => XerEncodingInstruction::UseUnion,
+            "WHITESPACE" => {
+                if self.peek_word("REPLACE") {
+                    self.pos += 1;
+                    XerEncodingInstruction::Whitespace(XerWhitespace::Replace)
+                } else {
+                    self.expect_word("COLLAPSE")?;
+                    XerEncodingInstruction::Whitespace(XerWhitespace::Collapse)
+                }
+            }
+            "GLOBAL-DEFAULTS" => {
+                if self.peek_word("MODIFIED-ENCODINGS") {
+                    // the modified encodings of X.693 clause 18 are not implemented, so the instruction has no effect
+                    self.pos += 1;
+                    return Ok(None);
+                }
+                return self
+                    .error("only GLOBAL-DEFAULTS MODIFIED-ENCODINGS is supported".to_string());
+            }
+            "NOT" => {
+                return Err(Error {
+                    kind: ErrorKind::Ast(
+                        "negated encoding instructions are not supported".to_string(),
+                    ),
+                    loc: keyword.loc,
+                })
+            }
+            other => {
+                return Err(Error {
+                    kind: ErrorKind::Ast(format!("unknown XER encoding instruction {}", other)),
+                    loc: keyword.loc,
+                })
+            }
+        }))
+    }
+
+    fn parse_targets(&mut self) -> Result<Vec<EncodingInstructionTarget>> {
+        let mut targets = Vec::new();
+        loop {
+            let token = match self.peek() {
+                Some(token) => token,
+                None => return self.error("expecting encoding instruction target".to_string()),
+            };
+            let target = match (&token.kind, &token.data) {
+                (TokenKind::Keyword, Some(TokenData::Keyword(Keyword::All))) => {
+                    self.pos += 1;
+                    EncodingInstructionTarget::All
+                }
+                (TokenKind::TypeReference, Some(TokenData::Named(name))) => {
+                    self.pos += 1;
+                    let name = AstElement::new(name.clone(), token.loc);
+                    let mut components = Vec::new();
+                    while self.next_operator(Operator::Period) {
+                        match self.peek() {
+                            Some(Token {
+                                kind: TokenKind::ValueReference,
+                                data: Some(TokenData::Named(component)),
+                                loc,
+                            }) => {
+                                self.pos += 1;
+                                components.push(AstElement::new(component.clone(), *loc));
+                            }
+                            _ => return self.error("expecting component identifier".to_string()),
+                        }
+                    }
+                    EncodingInstructionTarget::Type { name, components }
+                }
+                _ => {
+                    return self.error(format!(
+                        "expecting encoding instruction target, found {}",
+                        token
+                    ))
+                }
+            };
+            targets.push(target);
+
+            if !self.next_operator(Operator::Comma) {
+                return Ok(targets);
+            }
+        }
+    }
+}
+
+/// Returns the encoding reference that applies to an encoding prefix or `ENCODING-CONTROL` section,
+/// falling back to the encoding reference default of the module.
+fn is_xer_encoding_reference(
+    parser: &AstParser<'_>,
+    encoding_reference: Option<&AstElement<AstUppercaseReference>>,
+) -> bool {
+    match encoding_reference {
+        Some(encoding_reference) => encoding_reference.element.0 == XER_ENCODING_REFERENCE,
+        None => match &parser
+            .context
+            .lookup_module(&parser.module)
+            .expect("lookup_module")
+            .encoding_reference
+        {
+            Some(encoding_reference) => encoding_reference == XER_ENCODING_REFERENCE,
+            // XER is the only encoding with encoding instructions
+            None => true,
+        },
+    }
+}
+
+/// Parses the encoding instruction of an encoding prefix (e.g. `[XER:ATTRIBUTE]`).
+/// Returns `None` if the encoding prefix is for an encoding other than XER.
+pub(crate) fn parse_encoding_prefix(
+    parser: &AstParser<'_>,
+    prefix: &AstElement<AstEncodingPrefix>,
+) -> Result<Option<XerEncodingInstruction>> {
+    if !is_xer_encoding_reference(parser, prefix.element.encoding_reference.as_ref()) {
+        return Ok(None);
+    }
+
+    let instruction = &prefix.element.instruction;
+    let mut instruction_parser = InstructionParser::new(&instruction.element.0, instruction.loc);
+    let keyword = instruction_parser.next_word()?;
+    let parsed = match instruction_parser.parse_instruction(&keyword)? {
+        Some(parsed) => parsed,
+        None => {
+            return Err(Error {
+                kind: ErrorKind::Ast(format!(
+                    "{} is only permitted in an ENCODING-CONTROL section",
+                    keyword.element
+                )),
+                loc: keyword.loc,
+            })
+        }
+    };
+    if !instruction_parser.is_eoi() {
+        return instruction_parser.error("unexpected token in encoding instruction".to_string());
+    }
+    Ok(Some(parsed))
+}
+
+/// Parses the encoding instructions of all `ENCODING-CONTROL XER` sections of the module.
+pub(crate) fn parse_encoding_control_sections(
+    parser: &AstParser<'_>,
+) -> Result<Vec<PendingEncodingInstruction>> {
+    let mut pending = Vec::new();
+    for section in &parser.ast_module.element.encoding_control_sections {
+        if !is_xer_encoding_reference(parser, Some(&section.element.encoding_reference)) {
+            continue;
+        }
+
+        let instructions = &section.element.instructions;
+        let mut instruction_parser =
+            InstructionParser::new(&instructions.element.0, instructions.loc);
+        while !instruction_parser.is_eoi() {
+            let keyword = instruction_parser.next_word()?;
+            let targets = match keyword.element {
+                "GLOBAL-DEFAULTS" => Vec::new(),
+                _ => instruction_parser.parse_targets()?,
+            };
+            let instruction = match instruction_parser.parse_instruction(&keyword)? {
+                Some(instruction) => instruction,
+                None => continue,
+            };
+
+            for target in targets {
+                match target {
+                    EncodingInstructionTarget::All => {
+                        for assignment in &parser.ast_module.element.body.element.0 {
+                            if let AstAssignment::TypeAssignment(type_assignment) =
+                                &assignment.element
+                            {
+                                let ident = QualifiedIdentifier::new(
+                                    parser.module.clone(),
+                                    type_assignment.element.name.element.0.clone(),
+                                );
+                                // parameterized types are not registered as types
+                                if parser.context.lookup_type(&ident).is_some() {
+                                    pending.push(PendingEncodingInstruction {
+                                        ident,
+                                        components: Vec::new(),
+                                        instruction: instruction.clone(),
+                                    });
+                                }
+                            }
+                        }
+                    }
+                    EncodingInstructionTarget::Type { name, components } => {
+                        let ident =
+                            QualifiedIdentifier::new(parser.module.clone(), name.element.clone());
+                        if parser.context.lookup_type(&ident).is_none() {
+                            return Err(Error {
+                                kind: ErrorKind::Ast(format!(
+                                    "encoding instruction target '{}' is not a type declared in this module",
+                                    name.element
+                                )),
+                                loc: name.loc,
+                            });
+                        }
+                        pending.push(PendingEncodingInstruction {
+                            ident,
+                            components,
+                            instruction: instruction.clone(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+    Ok(pending)
+}
+
+/// Applies an encoding instruction from an `ENCODING-CONTROL` section to the target type.
+pub(crate) fn apply_pending_encoding_instruction(
+    context: &mut Context,
+    pending: PendingEncodingInstruction,
+) -> Result<()> {
+    let decl = context
+        .lookup_type_mut(&pending.ident)
+        .expect("lookup_type");
+    let mut ty = &mut decl.ty;
+    for component in &pending.components {
+        let component_type = match &mut ty.ty {
+            UntaggedType::BuiltinType(BuiltinType::Structure(structure)) => structure
+                .components
+                .iter_mut()
+                .find(|c| c.name.element == component.element)
+                .map(|c| &mut c.component_type),
+            UntaggedType::BuiltinType(BuiltinType::Choice(choice)) => choice
+                .alternatives
+                .iter_mut()
+                .find(|alternative| alternative.name.element == component.element)
+                .map(|alternative| &mut alternative.alternative_type),
+            _ => {
+                return Err(Error {
+                    kind: ErrorKind::Ast(format!(
+                        "encoding instruction target component '{}' must be in a SEQUENCE, SET, or CHOICE type that is defined in place",
+                        component.element
+                    )),
+                    loc: component.loc,
+                })
+            }
+        };
+        ty = match component_type {
+            Some(component_type) => component_type,
+            None => {
+                return Err(Error {
+                    kind: ErrorKind::Ast(format!(
+                        "encoding instruction target component '{}' is not defined",
+                        component.element
+                    )),
+                    loc: component.loc,
+                })
+            }
+        };
+    }
+    ty.xer_instructions.push(pending.instruction);
+    Ok(())
+}
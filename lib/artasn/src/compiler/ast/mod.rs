@@ -2,6 +2,7 @@ pub(crate) mod class;
 mod constraint_tree;
 pub(crate) mod constraints;
 pub(crate) mod extra;
+pub(crate) mod instructions;
 pub(crate) mod object_id;
 pub(crate) mod types;
 pub(crate) mod util;
@@ -233,6 +234,11 @@ pub fn register_all_modules(
             None => TagDefault::Explicit,
         };
         let extensibility_implied = header.element.extensibility_implied.is_some();
+        let encoding_reference = header
+            .element
+            .encoding_reference
+            .as_ref()
+            .map(|encoding_reference| encoding_reference.element.0.clone());
         let exports = match header
             .element
             .exports
@@ -289,6 +295,7 @@ pub fn register_all_modules(
             ident: parser.module.clone(),
             tag_default,
             extensibility_implied,
+            encoding_reference,
             exports,
             imports: imports_from_module,
             declarations,
@@ -593,7 +600,33 @@ pub fn register_all_constraints(
     }
 }
 
-/// Stage 10: register all declared values that do not contain references
+/// Stage 10: register the encoding instructions of all `ENCODING-CONTROL` sections.
+pub fn register_all_encoding_controls(
+    context: &mut Context,
+    compiler: &Compiler,
+    program: &AstElement<AstProgram>,
+) -> Vec<Error> {
+    match run_parser(context, compiler, program, |parser| {
+        match instructions::parse_encoding_control_sections(&parser) {
+            Ok(pending) => pending.into_iter().map(Ok).collect(),
+            Err(err) => vec![Err(err)],
+        }
+    }) {
+        Ok(pending_instructions) => {
+            let mut errors = Vec::new();
+            for pending in pending_instructions {
+                if let Err(err) = instructions::apply_pending_encoding_instruction(context, pending)
+                {
+                    errors.push(err);
+                }
+            }
+            errors
+        }
+        Err(errors) => errors,
+    }
+}
+
+/// Stage 11: register all declared values that do not contain references
 /// to information object class fields.
 pub fn register_all_normal_values(
     context: &mut Context,
@@ -630,7 +663,7 @@ pub fn register_all_normal_values(
     }
 }
 
-/// Stage 11: register all values that contain references to information object class fields.
+/// Stage 12: register all values that contain references to information object class fields.
 pub fn register_all_class_reference_values(
     context: &mut Context,
     compiler: &Compiler,
@@ -669,7 +702,29 @@ pub fn register_all_class_reference_values(
     }
 }
 
-/// Stage 12: verify all types.
+/// Stage 13: parse the DEFAULT values of the components of all declared types.
+pub fn register_all_default_values(
+    context: &mut Context,
+    compiler: &Compiler,
+    program: &AstElement<AstProgram>,
+) -> Vec<Error> {
+    match run_parser(context, compiler, program, |parser| {
+        let mut results = Vec::new();
+        for (ident, declared_type) in parser.context.list_types() {
+            if ident.module != parser.module {
+                continue;
+            }
+
+            results.push(types::parse_default_values(&parser, &declared_type.ty));
+        }
+        results
+    }) {
+        Ok(_) => Vec::new(),
+        Err(errors) => errors,
+    }
+}
+
+/// Stage 14: verify all types.
 pub fn verify_all_types(
     context: &mut Context,
     compiler: &Compiler,
@@ -693,7 +748,7 @@ pub fn verify_all_types(
     })
 }
 
-/// Stage 13: verify all declared values.
+/// Stage 15: verify all declared values.
 pub fn verify_all_values(
     context: &Context,
     compiler: &Compiler,
@@ -1,5 +1,5 @@
 use super::{
-    class,
+    class, instructions,
     util::LazyParse,
     values::{self, ParseValueAssignmentStage},
     AstParser,
@@ -50,21 +50,57 @@ fn default_value_lazy_parser(
     )
 }
 
+/// Parses the DEFAULT values of the components of the type and of its nested types,
+/// so that they can be used when there is no parser, such as when resolving decoded values.
+pub fn parse_default_values(parser: &AstParser<'_>, ty: &TaggedType) -> Result<()> {
+    match &ty.ty {
+        UntaggedType::BuiltinType(BuiltinType::Structure(structure)) => {
+            for component in &structure.components {
+                // the types of information object class fields depend on the value
+                if matches!(
+                    component.component_type.ty,
+                    UntaggedType::ObjectClassField(_)
+                ) {
+                    continue;
+                }
+                if let Some(default_value) = &component.default_value {
+                    default_value
+                        .parse(parser, &component.component_type.resolve(parser.context)?)?;
+                }
+                parse_default_values(parser, &component.component_type)?;
+            }
+        }
+        UntaggedType::BuiltinType(BuiltinType::StructureOf(structure_of)) => {
+            parse_default_values(parser, &structure_of.component_type)?;
+        }
+        UntaggedType::BuiltinType(BuiltinType::Choice(choice)) => {
+            for alternative in &choice.alternatives {
+                parse_default_values(parser, &alternative.alternative_type)?;
+            }
+        }
+        _ => (),
+    }
+    Ok(())
+}
+
 fn parse_structure_components(
     parser: &AstParser<'_>,
-    components: &[&AstElement<AstStructureComponent>],
+    components: &[(
+        &AstElement<AstStructureComponent>,
+        Option<ExtensionAddition>,
+    )],
     parameters: &[(&String, &Parameter)],
 ) -> Result<Vec<StructureComponent>> {
-    let has_tags = components
-        .iter()
-        .any(|component| match &component.element.ty.element {
-            AstType::TaggedType(_) => true,
-            AstType::ConstrainedType(_) => false,
-        });
+    let has_tags = components.iter().any(|(component, _)| {
+        matches!(
+            &strip_encoding_prefixes(&component.element.ty).element,
+            AstType::TaggedType(_)
+        )
+    });
     components
         .iter()
         .enumerate()
-        .map(|(i, component)| {
+        .map(|(i, (component, extension_addition))| {
             let forbidden_ident = match component.element.name.element.0.as_str() {
                 "artasn-special" if parser.module != REAL_IDENT.module => Some("artasn-special"),
                 "artasn-external" if parser.module != EXTERNAL_IDENT.module => {
@@ -117,28 +153,50 @@ fn parse_structure_components(
                 }),
                 optional: component.element.optional,
                 component_type: Box::new(ty),
+                extension_addition: *extension_addition,
             })
         })
         .collect::<Result<Vec<StructureComponent>>>()
 }
 
 // TODO: in the future, versioning should matter; but for now, pretend like the versioning does not exist
+/// Returns the components of the structure, and the extension addition that each component belongs to.
 pub(crate) fn flatten_structure_components(
     structure: &AstElement<AstStructure>,
-) -> Vec<&AstElement<AstStructureComponent>> {
-    structure
-        .element
-        .component_groups
-        .iter()
-        .filter_map(|component_group| match &component_group.element {
-            AstStructureComponentGroup::Extensible(_) => None,
-            AstStructureComponentGroup::StructureComponent(component) => Some(vec![component]),
+) -> Vec<(
+    &AstElement<AstStructureComponent>,
+    Option<ExtensionAddition>,
+)> {
+    let mut is_extension = false;
+    let mut addition_count = 0;
+    let mut components = Vec::with_capacity(structure.element.component_groups.len());
+    for group in &structure.element.component_groups {
+        let (group_components, is_group) = match &group.element {
+            AstStructureComponentGroup::Extensible(_) => {
+                is_extension = !is_extension;
+                continue;
+            }
+            AstStructureComponentGroup::StructureComponent(component) => (vec![component], false),
             AstStructureComponentGroup::ComponentExtensionGroup(group) => {
-                Some(group.element.components.iter().collect())
+                (group.element.components.iter().collect(), true)
             }
-        })
-        .flatten()
-        .collect()
+        };
+        let extension_addition = if is_extension {
+            addition_count += 1;
+            Some(ExtensionAddition {
+                index: addition_count - 1,
+                is_group,
+            })
+        } else {
+            None
+        };
+        components.extend(
+            group_components
+                .into_iter()
+                .map(|component| (component, extension_addition)),
+        );
+    }
+    components
 }
 
 fn parse_structure_type(
@@ -153,6 +211,12 @@ fn parse_structure_type(
             AstStructureKind::Set(_) => TagType::Set,
         },
         components: parse_structure_components(parser, &components, parameters)?,
+        extensible: structure.element.extensible
+            || structure
+                .element
+                .component_groups
+                .iter()
+                .any(|group| matches!(group.element, AstStructureComponentGroup::Extensible(_))),
     }))
 }
 
@@ -177,22 +241,48 @@ fn parse_structure_of_type(
     )))
 }
 
+// TODO: in the future, versioning should matter; but for now, pretend like the versioning does not exist
+/// Returns the alternatives of the CHOICE, and whether each alternative is an extension addition.
+pub(crate) fn flatten_choice_alternatives(
+    choice: &AstElement<AstChoice>,
+) -> Vec<(&AstElement<AstChoiceAlternative>, bool)> {
+    let mut is_extension = false;
+    let mut alternatives = Vec::with_capacity(choice.element.0.len());
+    for group in &choice.element.0 {
+        match &group.element {
+            AstChoiceAlternativeGroup::Extensible(_) => is_extension = !is_extension,
+            AstChoiceAlternativeGroup::ChoiceAlternative(alternative) => {
+                alternatives.push((alternative, is_extension))
+            }
+            AstChoiceAlternativeGroup::AlternativeExtensionGroup(group) => alternatives.extend(
+                group
+                    .element
+                    .alternatives
+                    .iter()
+                    .map(|alternative| (alternative, is_extension)),
+            ),
+        }
+    }
+    alternatives
+}
+
 fn parse_choice_type(
     parser: &AstParser<'_>,
-    alternatives: &[AstElement<AstChoiceAlternative>],
+    choice: &AstElement<AstChoice>,
     parameters: &[(&String, &Parameter)],
 ) -> Result<BuiltinType> {
-    let has_tags = alternatives
-        .iter()
-        .any(|alternative| match &alternative.element.ty.element {
-            AstType::TaggedType(_) => true,
-            AstType::ConstrainedType(_) => false,
-        });
+    let alternatives = flatten_choice_alternatives(choice);
+    let has_tags = alternatives.iter().any(|(alternative, _)| {
+        matches!(
+            &strip_encoding_prefixes(&alternative.element.ty).element,
+            AstType::TaggedType(_)
+        )
+    });
     Ok(BuiltinType::Choice(Choice {
         alternatives: alternatives
             .iter()
             .enumerate()
-            .map(|(i, alternative)| {
+            .map(|(i, (alternative, is_extension))| {
                 let ty = parse_type(
                     parser,
                     &alternative.element.ty,
@@ -206,9 +296,15 @@ fn parse_choice_type(
                 Ok(ChoiceAlternative {
                     name: alternative.element.name.as_ref().map(|name| name.0.clone()),
                     alternative_type: Box::new(ty),
+                    is_extension: *is_extension,
                 })
             })
             .collect::<Result<Vec<ChoiceAlternative>>>()?,
+        extensible: choice
+            .element
+            .0
+            .iter()
+            .any(|group| matches!(group.element, AstChoiceAlternativeGroup::Extensible(_))),
     }))
 }
 
@@ -218,7 +314,15 @@ fn parse_enumerated_type(
 ) -> Result<BuiltinType> {
     let mut items = Vec::new();
     let mut implied_index = 0;
-    for ast_item in &enumerated.element.0 {
+    let mut is_extension = false;
+    for item_group in &enumerated.element.0 {
+        let ast_item = match &item_group.element {
+            AstEnumerationItemGroup::Extensible(_) => {
+                is_extension = true;
+                continue;
+            }
+            AstEnumerationItemGroup::EnumerationItem(item) => item,
+        };
         let value = match &ast_item.element.num {
             Some(num) => EnumerationItemValue::Specified(values::parse_value(
                 parser,
@@ -226,8 +330,12 @@ fn parse_enumerated_type(
                 num,
                 &ResolvedType {
                     tag: Some(Tag::universal(TagType::Enumerated)),
-                    ty: BuiltinType::Enumerated(Vec::new()),
+                    ty: BuiltinType::Enumerated(EnumeratedType {
+                        items: Vec::new(),
+                        extensible: false,
+                    }),
                     constraint: None,
+                    xer_instructions: Vec::new(),
                 },
             )?),
             None => {
@@ -239,9 +347,13 @@ fn parse_enumerated_type(
         items.push(EnumerationItem {
             name: ast_item.element.name.as_ref().map(|name| name.0.clone()),
             value,
+            is_extension,
         });
     }
-    Ok(BuiltinType::Enumerated(items))
+    Ok(BuiltinType::Enumerated(EnumeratedType {
+        items,
+        extensible: is_extension,
+    }))
 }
 
 fn parse_named_numbers(
@@ -328,7 +440,7 @@ fn parse_builtin_type(
         AstBuiltinType::RelativeOid(_) => BuiltinType::RelativeOid,
         AstBuiltinType::Time(_) => BuiltinType::Time,
         AstBuiltinType::Structure(sequence) => parse_structure_type(parser, sequence, parameters)?,
-        AstBuiltinType::Choice(choice) => parse_choice_type(parser, &choice.element.0, parameters)?,
+        AstBuiltinType::Choice(choice) => parse_choice_type(parser, choice, parameters)?,
         AstBuiltinType::NumericString(_) => BuiltinType::CharacterString(TagType::NumericString),
         AstBuiltinType::PrintableString(_) => {
             BuiltinType::CharacterString(TagType::PrintableString)
@@ -703,6 +815,7 @@ fn parse_untagged_type(
         tag: None,
         ty: untagged,
         constraint: None,
+        xer_instructions: Vec::new(),
     })
 }
 
@@ -719,6 +832,7 @@ fn parse_constrained_type(
             tag: None,
             ty: parse_structure_of_type(parser, &twc.element.0, parameters)?,
             constraint: None,
+            xer_instructions: Vec::new(),
         },
     })
 }
@@ -756,6 +870,16 @@ pub fn parse_type(
         .expect("lookup_module")
         .tag_default;
     Ok(match &ty.element {
+        AstType::EncodingPrefixedType(prefixed) => {
+            let mut tagged_type =
+                parse_type(parser, &prefixed.element.ty, parameters, type_context)?;
+            if let Some(instruction) =
+                instructions::parse_encoding_prefix(parser, &prefixed.element.prefix)?
+            {
+                tagged_type.xer_instructions.insert(0, instruction);
+            }
+            tagged_type
+        }
         AstType::TaggedType(ast_tagged_type) => {
             let tagged_type =
                 parse_constrained_type(parser, &ast_tagged_type.element.ty, parameters)?;
@@ -843,6 +967,7 @@ pub fn parse_type(
                 tag: Some(tag),
                 ty: tagged_type.ty,
                 constraint: tagged_type.constraint, // TODO: should constraints from parameterized types be applied here?
+                xer_instructions: tagged_type.xer_instructions,
             }
         }
         AstType::ConstrainedType(constrained) => {
@@ -887,11 +1012,21 @@ pub fn parse_type(
                 tag,
                 ty: tagged_type.ty,
                 constraint: tagged_type.constraint, // TODO: should constraints from parameterized types be applied here?
+                xer_instructions: tagged_type.xer_instructions,
             }
         }
     })
 }
 
+/// Returns the type inside all encoding prefixes of the AST type (e.g. `INTEGER` in `[XER:ATTRIBUTE] INTEGER`).
+pub(crate) fn strip_encoding_prefixes(ty: &AstElement<AstType>) -> &AstElement<AstType> {
+    let mut ty = ty;
+    while let AstType::EncodingPrefixedType(prefixed) = &ty.element {
+        ty = &prefixed.element.ty;
+    }
+    ty
+}
+
 pub(crate) struct ParameterDecl {
     _kind: ParameterDeclKind,
     name: String,
@@ -956,9 +1091,10 @@ pub fn parse_parameterized_type_assignment(
 pub(crate) fn ast_type_as_parameterized_type_reference(
     ast: &AstElement<AstType>,
 ) -> Option<&AstElement<AstParameterizedDefinedType>> {
-    let constrained: &AstElement<AstConstrainedType> = match &ast.element {
+    let constrained: &AstElement<AstConstrainedType> = match &strip_encoding_prefixes(ast).element {
         AstType::TaggedType(tagged) => &tagged.element.ty,
         AstType::ConstrainedType(constrained) => constrained,
+        AstType::EncodingPrefixedType(_) => unreachable!(),
     };
     let untagged = match &constrained.element {
         AstConstrainedType::Suffixed(suffixed) => &suffixed.element.ty,
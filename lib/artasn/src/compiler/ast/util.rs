@@ -46,6 +46,11 @@ impl<Input, Data: Clone, Output: Clone> LazyParse<Input, Data, Output> {
 
         output
     }
+
+    /// Returns the output if it has already been parsed.
+    pub fn get(&self) -> Option<Output> {
+        self.loaded_output.borrow().clone()
+    }
 }
 
 impl Display for AstDefinedValue {
@@ -16,15 +16,15 @@ use super::{
 };
 
 lazy_static::lazy_static! {
-    static ref PLUS_INFINITY_IDENT: QualifiedIdentifier = QualifiedIdentifier::new(
+    pub(crate) static ref PLUS_INFINITY_IDENT: QualifiedIdentifier = QualifiedIdentifier::new(
         ModuleIdentifier::with_name(String::from("Real")),
         String::from("plus-infinity"),
     );
-    static ref MINUS_INFINITY_IDENT: QualifiedIdentifier = QualifiedIdentifier::new(
+    pub(crate) static ref MINUS_INFINITY_IDENT: QualifiedIdentifier = QualifiedIdentifier::new(
         ModuleIdentifier::with_name(String::from("Real")),
         String::from("minus-infinity"),
     );
-    static ref NOT_A_NUMBER_IDENT: QualifiedIdentifier = QualifiedIdentifier::new(
+    pub(crate) static ref NOT_A_NUMBER_IDENT: QualifiedIdentifier = QualifiedIdentifier::new(
         ModuleIdentifier::with_name(String::from("Real")),
         String::from("not-a-number"),
     );
@@ -325,6 +325,7 @@ fn parse_object_class_field_reference<C: ComponentLike>(
                                                                 tag: Some(outer_tag),
                                                                 ty: inner_type.ty,
                                                                 constraint: inner_type.constraint,
+                                                                xer_instructions: inner_type.xer_instructions,
                                                             }, kind))
                                                         }
                                                         TagKind::Implicit => {
@@ -332,6 +333,7 @@ fn parse_object_class_field_reference<C: ComponentLike>(
                                                                 tag: struct_component.component_type().tag.clone(),
                                                                 ty: inner_type.ty,
                                                                 constraint: inner_type.constraint,
+                                                                xer_instructions: inner_type.xer_instructions,
                                                             }, kind))
                                                         }
                                                     }
@@ -442,6 +444,13 @@ fn parse_structure_value(
     }
     let mut components = Vec::new();
     for ty_component in struct_ty_components {
+        let default_value = match &ty_component.default_value {
+            Some(default_value) => Some(default_value.parse(
+                parser,
+                &ty_component.component_type.resolve(parser.context)?,
+            )?),
+            None => None,
+        };
         let (value, is_default) = {
             if let Some(val_component) =
                 struct_val.element.components.iter().find(|val_component| {
@@ -471,16 +480,18 @@ fn parse_structure_value(
                     parse_value(parser, stage, &val_component.element.value, &component_type)?,
                     false,
                 )
-            } else if let Some(default_value) = &ty_component.default_value {
-                (
-                    default_value.parse(
-                        parser,
-                        &ty_component.component_type.resolve(parser.context)?,
-                    )?,
-                    true,
-                )
+            } else if let Some(default_value) = &default_value {
+                (default_value.clone(), true)
             } else {
-                if ty_component.optional {
+                let is_absent_extension_addition =
+                    is_absent_extension_addition(struct_ty_components, ty_component, |name| {
+                        struct_val
+                            .element
+                            .components
+                            .iter()
+                            .any(|val_component| val_component.element.name.element.0 == name)
+                    });
+                if ty_component.optional || is_absent_extension_addition {
                     continue;
                 }
                 return Err(Error {
@@ -497,6 +508,7 @@ fn parse_structure_value(
             name: ty_component.name.clone(),
             value,
             is_default,
+            default_value,
         });
     }
     Ok(BuiltinValue::Structure(
@@ -505,6 +517,26 @@ fn parse_structure_value(
     ))
 }
 
+/// Returns whether the character is in the character set of the character string type.
+pub(crate) fn is_valid_character(tag_type: TagType, ch: char) -> bool {
+    match tag_type {
+        TagType::UTF8String
+        | TagType::UniversalString
+        | TagType::GeneralString
+        | TagType::BMPString
+        | TagType::CharacterString => true,
+        TagType::NumericString => ch.is_ascii_digit() || ch == ' ',
+        TagType::PrintableString => ch.is_ascii_alphanumeric() || " '()+,-./:=?".contains(ch),
+        TagType::TeletexString | TagType::VideotexString => {
+            ch.is_ascii_graphic() || ch == ' ' || ch == '\x7f'
+        }
+        TagType::VisibleString => ch.is_ascii_graphic() || ch == ' ',
+        TagType::IA5String => ch <= '\x7f',
+        TagType::GraphicString | TagType::ObjectDescriptor => ch == ' ' || !ch.is_control(),
+        _ => unreachable!(),
+    }
+}
+
 fn parse_character_string(
     str_lit: &AstElement<AstStringLiteral>,
     tag_type: TagType,
@@ -521,27 +553,7 @@ fn parse_character_string(
             });
         }
     };
-    let validator = match tag_type {
-        TagType::UTF8String
-        | TagType::UniversalString
-        | TagType::GeneralString
-        | TagType::BMPString
-        | TagType::CharacterString => |_: char| true,
-        TagType::NumericString => |ch: char| ch.is_ascii_digit() || ch == ' ',
-        TagType::PrintableString => {
-            |ch: char| ch.is_ascii_alphanumeric() || " '()+,-./:=?".contains(ch)
-        }
-        TagType::TeletexString | TagType::VideotexString => {
-            |ch: char| ch.is_ascii_graphic() || ch == ' ' || ch == '\x7f'
-        }
-        TagType::VisibleString => |ch: char| ch.is_ascii_graphic() || ch == ' ',
-        TagType::IA5String => |ch: char| ch <= '\x7f',
-        TagType::GraphicString | TagType::ObjectDescriptor => {
-            |ch: char| ch == ' ' || !ch.is_control()
-        }
-        _ => unreachable!(),
-    };
-    let invalid = cstring.chars().map(validator).any(|valid| !valid);
+    let invalid = cstring.chars().any(|ch| !is_valid_character(tag_type, ch));
     if invalid {
         return Err(Error {
             kind: ErrorKind::Ast(format!(
@@ -554,7 +566,7 @@ fn parse_character_string(
     Ok(BuiltinValue::CharacterString(tag_type, cstring))
 }
 
-fn parse_byte_string(str: &str, radix: u32) -> (Vec<u8>, u8) {
+pub(crate) fn parse_byte_string(str: &str, radix: u32) -> (Vec<u8>, u8) {
     if str.is_empty() {
         return (Vec::new(), 0);
     }
@@ -598,7 +610,7 @@ fn parse_byte_string(str: &str, radix: u32) -> (Vec<u8>, u8) {
     (bytes, 0)
 }
 
-fn create_named_bit_string(mut bit_positions: Vec<u64>) -> BitStringValue {
+pub(crate) fn create_named_bit_string(mut bit_positions: Vec<u64>) -> BitStringValue {
     if bit_positions.is_empty() {
         return BitStringValue {
             data: Vec::new(),
@@ -615,13 +627,7 @@ fn create_named_bit_string(mut bit_positions: Vec<u64>) -> BitStringValue {
     };
     let mut data = vec![0u8; ((max_bit + unused_bits + 1) / 8) as usize];
     for bit_position in bit_positions {
-        let byte_offset = bit_position / 8;
-        if byte_offset == 0 {
-            data[0] |= (1 << (7 - bit_position)) as u8;
-        } else {
-            let bit_offset = bit_position % byte_offset;
-            data[byte_offset as usize] |= (1 << (7 - bit_offset)) as u8;
-        }
+        data[(bit_position / 8) as usize] |= 0x80 >> (bit_position % 8);
     }
 
     BitStringValue {
@@ -1114,9 +1120,9 @@ pub fn parse_value(
             }
         },
         AstValue::DefinedValue(valref) => match &target_type.ty {
-            BuiltinType::Enumerated(items) => 'block: {
+            BuiltinType::Enumerated(enumerated) => 'block: {
                 if valref.element.external_module.is_none() {
-                    for item in items {
+                    for item in &enumerated.items {
                         if item.name.element == valref.element.value.element.0 {
                             break 'block BuiltinValue::Enumerated(Box::new(match &item.value {
                                 EnumerationItemValue::Implied(implied) => AstElement::new(
@@ -1128,9 +1134,7 @@ pub fn parse_value(
                                     },
                                     value.loc,
                                 ),
-                                EnumerationItemValue::Specified(specified) => {
-                                    return Ok(specified.clone());
-                                }
+                                EnumerationItemValue::Specified(specified) => specified.clone(),
                             }));
                         }
                     }
@@ -1143,7 +1147,10 @@ pub fn parse_value(
             {
                 for named_value in integer.named_values.as_ref().unwrap() {
                     if named_value.name.element == valref.element.value.element.0 {
-                        return Ok(named_value.value.clone());
+                        // the named number is typed as a plain INTEGER, so retain the tagging of the target type
+                        let mut value = named_value.value.clone();
+                        value.element.resolved_type = target_type.clone();
+                        return Ok(value);
                     }
                 }
 
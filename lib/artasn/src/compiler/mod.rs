@@ -241,8 +241,10 @@ impl Compiler {
         stage!(register_all_information_object_sets);
         stage!(register_all_information_objects);
         stage!(register_all_constraints);
+        stage!(register_all_encoding_controls);
         stage!(register_all_normal_values);
         stage!(register_all_class_reference_values);
+        stage!(register_all_default_values);
 
         if self.config.verify {
             stage!(verify_all_types);
@@ -124,3 +124,19 @@ pub fn lookup_root_node<'a>(name: &str) -> Option<&'a OidTreeNode> {
 pub fn get_root_node<'a>(num: u64) -> Option<&'a OidTreeNode> {
     ROOT_NODES.iter().find(|&root_node| root_node.node == num)
 }
+
+/// Returns the name of each node of the OID, or `None` for the nodes that do not have a name in the OID tree.
+/// Once a node does not have a name, none of the nodes after it have a name either.
+pub fn lookup_names(oid: &Oid) -> Vec<Option<&'static str>> {
+    let mut names = Vec::with_capacity(oid.0.len());
+    let mut current_node: Option<&'static OidTreeNode> = None;
+    for (i, num) in oid.0.iter().enumerate() {
+        current_node = if i == 0 {
+            get_root_node(*num)
+        } else {
+            current_node.and_then(|node| node.get_node(*num))
+        };
+        names.push(current_node.map(|node| node.name));
+    }
+    names
+}
@@ -5,8 +5,8 @@ use serde::Deserialize;
 use crate::{
     encoding::*,
     module::{ModuleIdentifier, QualifiedIdentifier},
-    types::UntaggedType,
-    values::ValueResolve,
+    types::{BuiltinType, UntaggedType},
+    values::{BitStringValue, BuiltinValue, ResolvedValue, ValueResolve},
 };
 
 use super::{context::DeclaredValue, options::CompilerConfig, CompileError, Compiler, Context};
@@ -48,14 +48,66 @@ pub fn compile_module(context: &mut Context, path: &str, source: &str) {
     }
 }
 
+/// Encodes the value declared with the name in the module with the transfer syntax.
+pub fn encode_declared_value(
+    context: &Context,
+    module: &str,
+    name: &str,
+    syntax: &TransferSyntax,
+    options: &EncodeOptions,
+) -> super::parser::Result<Vec<u8>> {
+    let ident = QualifiedIdentifier::new(
+        ModuleIdentifier::with_name(module.to_string()),
+        name.to_string(),
+    );
+    let value = context
+        .lookup_value(&ident)
+        .expect("lookup_value")
+        .value
+        .resolve(context)
+        .expect("resolve");
+
+    let encoder = syntax.get_codec().encoder.expect("encoder");
+    let mut buf = Vec::new();
+    encoder(
+        syntax,
+        EncodeMode::Normal,
+        options,
+        &mut buf,
+        context,
+        &value,
+    )?;
+    Ok(buf)
+}
+
 #[derive(Deserialize, Debug, PartialEq, Eq)]
 enum TestMode {
     Encode,
     Decode,
+    #[serde(rename = "BER")]
+    Basic,
+    #[serde(rename = "CER")]
+    Canonical,
     #[serde(rename = "DER")]
     Distinguished,
     #[serde(rename = "PER")]
     Packed,
+    #[serde(rename = "CPER")]
+    CanonicalPacked,
+    #[serde(rename = "OER")]
+    Octet,
+    #[serde(rename = "COER")]
+    CanonicalOctet,
+    #[serde(rename = "XER")]
+    Xml,
+    #[serde(rename = "CXER")]
+    CanonicalXml,
+    #[serde(rename = "E-XER")]
+    ExtendedXml,
+    #[serde(rename = "JER")]
+    Json,
+    #[serde(rename = "GSER")]
+    GenericString,
 }
 
 #[derive(Deserialize, Debug)]
@@ -65,12 +117,52 @@ enum PackedEncodings {
     ByAlignment { aligned: String, unaligned: String },
 }
 
+impl PackedEncodings {
+    fn to_encodings(
+        &self,
+        aligned_kind: PackedEncodingKind,
+        unaligned_kind: PackedEncodingKind,
+    ) -> [(TransferSyntax, Vec<u8>); 2] {
+        let (aligned, unaligned) = match self {
+            Self::BothAlignments(hex) => (hex, hex),
+            Self::ByAlignment { aligned, unaligned } => (aligned, unaligned),
+        };
+        [
+            (
+                TransferSyntax::Packed(aligned_kind),
+                hex::decode(aligned).expect("invalid PER hex"),
+            ),
+            (
+                TransferSyntax::Packed(unaligned_kind),
+                hex::decode(unaligned).expect("invalid UPER hex"),
+            ),
+        ]
+    }
+}
+
 #[derive(Deserialize, Debug)]
 struct ValueTestEntry {
     pub tests: Option<Vec<TestMode>>,
     pub name: String,
+    pub ber: Option<String>,
+    pub cer: Option<String>,
     pub der: Option<String>,
     pub per: Option<PackedEncodings>,
+    /// The CANONICAL-PER encodings, if they differ from the BASIC-PER encodings.
+    pub cper: Option<PackedEncodings>,
+    pub oer: Option<String>,
+    /// The CANONICAL-OER encoding, if it differs from the BASIC-OER encoding.
+    pub coer: Option<String>,
+    /// The BASIC-XER encoding, which is XML text rather than hex.
+    pub xer: Option<String>,
+    /// The CANONICAL-XER encoding, which is XML text rather than hex.
+    pub cxer: Option<String>,
+    /// The EXTENDED-XER encoding, which is XML text rather than hex.
+    pub exer: Option<String>,
+    /// The JER encoding, which is JSON text rather than hex.
+    pub jer: Option<String>,
+    /// The GSER encoding, which is text rather than hex.
+    pub gser: Option<String>,
     pub value: Option<serde_json::Value>,
 }
 
@@ -91,6 +183,13 @@ fn test_encode_value(
         .value
         .resolve(context)
         .expect("failed to resolve value");
+    let options = EncodeOptions {
+        type_name: match &declared_value.ty.ty {
+            UntaggedType::Reference(typeref) => Some(typeref.element.name.clone()),
+            _ => None,
+        },
+        ..Default::default()
+    };
 
     for (syntax, expected_encoding) in expected_encodings {
         println!(
@@ -101,22 +200,34 @@ fn test_encode_value(
 
         let mut buf = Vec::with_capacity(expected_encoding.len());
         let encoder = syntax.get_codec().encoder.expect("no encoder");
-        encoder(syntax, EncodeMode::Normal, &mut buf, context, &typed_value).unwrap_or_else(
-            |err| {
-                panic!(
-                    "failed to {} encode value '{}': {}",
-                    syntax, ident, err.kind
-                )
-            },
-        );
-
+        encoder(
+            syntax,
+            EncodeMode::Normal,
+            &options,
+            &mut buf,
+            context,
+            &typed_value,
+        )
+        .unwrap_or_else(|err| {
+            panic!(
+                "failed to {} encode value '{}': {}",
+                syntax, ident, err.kind
+            )
+        });
+
+        let format = |encoding: &[u8]| match syntax {
+            TransferSyntax::Xml(_) | TransferSyntax::Json(_) | TransferSyntax::String(_) => {
+                String::from_utf8_lossy(encoding).to_string()
+            }
+            _ => hex::encode_upper(encoding),
+        };
         assert!(
             expected_encoding == buf.as_slice(),
             "value    = {}\ntransfer syntax = {}\nexpected = {}\nfound    = {}",
             ident,
             syntax,
-            hex::encode_upper(expected_encoding),
-            hex::encode_upper(&buf)
+            format(expected_encoding),
+            format(&buf)
         );
     }
 }
@@ -171,8 +282,8 @@ fn compare_decoded_value_to_json_value(
     match &decoded_value.form {
         DecodedValueForm::Primitive(kind) => match kind {
             DecodedValueKind::Integer(i) => {
-                let i: u64 = i.try_into().expect("INTEGER is too large");
-                assert_eq!(json_value.as_u64().expect("expecting INTEGER"), i);
+                let i: i64 = i.try_into().expect("INTEGER is too large");
+                assert_eq!(json_value.as_i64().expect("expecting INTEGER"), i);
             }
             DecodedValueKind::Boolean(b) => {
                 assert_eq!(json_value.as_bool().expect("expecting BOOLEAN"), *b)
@@ -180,6 +291,14 @@ fn compare_decoded_value_to_json_value(
             DecodedValueKind::Enumerated(i) => {
                 assert_eq!(json_value.as_i64().expect("expecting INTEGER"), *i);
             }
+            DecodedValueKind::OctetString(data) => assert_eq!(
+                json_value.as_str().expect("expecting OCTET STRING"),
+                hex::encode_upper(data)
+            ),
+            DecodedValueKind::BitString(bit_string) => assert_eq!(
+                json_value.as_str().expect("expecting BIT STRING"),
+                bit_string_to_bits(bit_string)
+            ),
             DecodedValueKind::CharacterString(_, str) => assert_eq!(
                 json_value.as_str().expect("expecting character string"),
                 str
@@ -192,12 +311,197 @@ fn compare_decoded_value_to_json_value(
     }
 }
 
+fn bit_string_to_bits(bit_string: &BitStringValue) -> String {
+    let bit_count = bit_string.data.len() * 8 - bit_string.unused_bits as usize;
+    (0..bit_count)
+        .map(|i| match bit_string.data[i / 8] & (0x80 >> (i % 8)) {
+            0 => '0',
+            _ => '1',
+        })
+        .collect::<String>()
+}
+
+/// Compares a decoded value to the declared value that was encoded,
+/// for test entries that do not specify the decoded value in JSON.
+fn compare_decoded_value_to_resolved_value(
+    context: &Context,
+    decoded_value: &DecodedValue,
+    resolved_value: &ResolvedValue,
+) {
+    if let BuiltinValue::Choice(choice) = &resolved_value.value {
+        let alternative_value = choice
+            .value
+            .resolve(context)
+            .expect("failed resolving value");
+        compare_decoded_value_to_resolved_value(context, decoded_value, &alternative_value);
+        return;
+    }
+
+    match (&decoded_value.form, &resolved_value.value) {
+        (DecodedValueForm::Primitive(kind), value) => match (kind, value) {
+            (DecodedValueKind::Boolean(lhs), BuiltinValue::Boolean(rhs)) => assert_eq!(lhs, rhs),
+            (DecodedValueKind::Integer(lhs), BuiltinValue::Integer(rhs)) => assert_eq!(lhs, rhs),
+            (DecodedValueKind::OctetString(lhs), BuiltinValue::OctetString(rhs)) => {
+                assert_eq!(lhs, rhs)
+            }
+            (DecodedValueKind::BitString(lhs), BuiltinValue::BitString(rhs)) => {
+                let (mut lhs, mut rhs) = (bit_string_to_bits(lhs), bit_string_to_bits(rhs));
+                // trailing zero bits are not significant in a BIT STRING with named bits
+                if let BuiltinType::BitString(bs) = &resolved_value.ty.ty {
+                    if bs.named_bits.is_some() {
+                        lhs.truncate(lhs.trim_end_matches('0').len());
+                        rhs.truncate(rhs.trim_end_matches('0').len());
+                    }
+                }
+                assert_eq!(lhs, rhs)
+            }
+            (DecodedValueKind::Null, BuiltinValue::Null) => (),
+            (
+                DecodedValueKind::CharacterString(lhs_tag, lhs),
+                BuiltinValue::CharacterString(rhs_tag, rhs),
+            ) => {
+                assert_eq!(lhs_tag, rhs_tag);
+                assert_eq!(lhs, rhs)
+            }
+            (DecodedValueKind::ObjectIdentifier(lhs), BuiltinValue::ObjectIdentifier(rhs))
+            | (DecodedValueKind::RelativeOid(lhs), BuiltinValue::RelativeOid(rhs)) => {
+                assert_eq!(
+                    lhs,
+                    &rhs.resolve_oid(context).expect("failed resolving OID")
+                )
+            }
+            (DecodedValueKind::Real(lhs), BuiltinValue::RealLiteral(rhs)) => {
+                let rhs: f64 = format!("{}e{}", rhs.mantissa, rhs.exponent)
+                    .parse()
+                    .expect("REAL is not a valid f64");
+                assert_eq!(lhs.to_f64(), rhs)
+            }
+            (DecodedValueKind::Real(lhs), BuiltinValue::Integer(rhs)) => {
+                let rhs: f64 = rhs.to_string().parse().expect("REAL is not a valid f64");
+                assert_eq!(lhs.to_f64(), rhs)
+            }
+            (DecodedValueKind::Enumerated(lhs), BuiltinValue::Enumerated(rhs)) => {
+                match rhs.resolve(context).expect("failed resolving value").value {
+                    BuiltinValue::Integer(rhs) => assert_eq!(num::BigInt::from(*lhs), rhs),
+                    _ => panic!("ENUMERATED value is not an INTEGER"),
+                }
+            }
+            (DecodedValueKind::Date(lhs), BuiltinValue::Date(rhs)) => assert_eq!(lhs, rhs),
+            (DecodedValueKind::TimeOfDay(lhs), BuiltinValue::TimeOfDay(rhs)) => {
+                assert_eq!(lhs, rhs)
+            }
+            (DecodedValueKind::DateTime(lhs), BuiltinValue::DateTime(rhs)) => assert_eq!(lhs, rhs),
+            (kind, value) => panic!("decoded {:?}, but expected {}", kind, value),
+        },
+        (DecodedValueForm::Constructed(elements), BuiltinValue::StructureOf(_, expected)) => {
+            assert_eq!(elements.len(), expected.len());
+            for (element, expected) in elements.iter().zip(expected) {
+                let expected = expected.resolve(context).expect("failed resolving value");
+                compare_decoded_value_to_resolved_value(context, element, &expected);
+            }
+        }
+        (DecodedValueForm::Constructed(components), BuiltinValue::Structure(_, expected)) => {
+            for component in components {
+                let name = component
+                    .metadata
+                    .as_ref()
+                    .and_then(|metadata| metadata.component_name.as_ref())
+                    .expect("missing component name");
+                let expected = expected
+                    .components
+                    .iter()
+                    .find(|expected| &expected.name.element == name)
+                    .unwrap_or_else(|| panic!("decoded unexpected component '{}'", name));
+                let expected = expected
+                    .value
+                    .resolve(context)
+                    .expect("failed resolving value");
+                compare_decoded_value_to_resolved_value(context, component, &expected);
+            }
+            // components that were not decoded must have been omitted because they are OPTIONAL or equal to their DEFAULT value
+            for expected in &expected.components {
+                let is_decoded = components.iter().any(|component| {
+                    component
+                        .metadata
+                        .as_ref()
+                        .and_then(|metadata| metadata.component_name.as_ref())
+                        == Some(&expected.name.element)
+                });
+                assert!(
+                    is_decoded || expected.default_value.is_some(),
+                    "component '{}' was not decoded",
+                    expected.name.element
+                );
+            }
+        }
+        (DecodedValueForm::Constructed(_), value) => {
+            panic!("decoded a constructed value, but expected {}", value)
+        }
+    }
+}
+
+/// Converts the decoded value back into a `ResolvedValue` and checks that it encodes to `encoding` with `syntax`.
+fn test_reencode_value(
+    context: &Context,
+    ident: &QualifiedIdentifier,
+    syntax: &TransferSyntax,
+    mode: &DecodeMode,
+    value: &DecodedValue,
+    encoding: &[u8],
+) {
+    let resolved_value = value
+        .resolve_with_mode(context, mode)
+        .unwrap_or_else(|err| panic!("failed to convert {} value '{}': {}", syntax, ident, err));
+    let options = EncodeOptions {
+        type_name: match mode {
+            DecodeMode::SpecificType {
+                source_ident: Some(source_ident),
+                ..
+            } => Some(source_ident.name.clone()),
+            _ => None,
+        },
+        ..Default::default()
+    };
+    let mut buf = Vec::with_capacity(encoding.len());
+    let encoder = syntax.get_codec().encoder.expect("no encoder");
+    encoder(
+        syntax,
+        EncodeMode::Normal,
+        &options,
+        &mut buf,
+        context,
+        &resolved_value,
+    )
+    .unwrap_or_else(|err| {
+        panic!(
+            "failed to {} re-encode value '{}': {}",
+            syntax, ident, err.kind
+        )
+    });
+
+    let format = |encoding: &[u8]| match syntax {
+        TransferSyntax::Xml(_) | TransferSyntax::Json(_) | TransferSyntax::String(_) => {
+            String::from_utf8_lossy(encoding).to_string()
+        }
+        _ => hex::encode_upper(encoding),
+    };
+    assert!(
+        encoding == buf.as_slice(),
+        "value    = {}\ntransfer syntax = {}\nexpected = {}\nre-encoded = {}",
+        ident,
+        syntax,
+        format(encoding),
+        format(&buf)
+    );
+}
+
 fn test_decode_value(
     context: &Context,
     ident: &QualifiedIdentifier,
     declared_value: &DeclaredValue,
     encodings: &[(TransferSyntax, Vec<u8>)],
-    json_value: &serde_json::Value,
+    json_value: Option<&serde_json::Value>,
+    is_encoded: bool,
 ) {
     let mode = DecodeMode::SpecificType {
         source_ident: match &declared_value.ty.ty {
@@ -207,18 +511,46 @@ fn test_decode_value(
             UntaggedType::Reference(typeref) => Some(typeref.element.clone()),
         },
         component_name: None,
-        resolved: declared_value
-            .ty
-            .resolve(context)
-            .expect("failed resolving type"),
+        resolved: Box::new(
+            declared_value
+                .ty
+                .resolve(context)
+                .expect("failed resolving type"),
+        ),
     };
 
+    // values decoded from any transfer syntax must convert to the DER encoding
+    let der = TransferSyntax::Basic(BasicEncodingKind::Distinguished);
+    let der_encoding = encodings
+        .iter()
+        .find(|(syntax, _)| *syntax == der)
+        .map(|(_, encoding)| encoding);
+
     for (syntax, encoding) in encodings {
         let decoder = syntax.get_codec().decoder.expect("no decoder");
         let values = decoder(syntax, &mode, encoding, context)
-            .unwrap_or_else(|_| panic!("failed to decode value '{}'", ident));
-
-        compare_decoded_values_to_json_values(&values, json_value)
+            .unwrap_or_else(|err| panic!("failed to {} decode value '{}': {}", syntax, ident, err));
+
+        match json_value {
+            Some(json_value) => compare_decoded_values_to_json_values(&values, json_value),
+            None => {
+                let resolved_value = declared_value
+                    .value
+                    .resolve(context)
+                    .expect("failed to resolve value");
+                for value in &values {
+                    compare_decoded_value_to_resolved_value(context, value, &resolved_value);
+                    // encodings that are only decoded can differ from the output of the encoder,
+                    // such as components that are equal to their DEFAULT value
+                    if is_encoded {
+                        test_reencode_value(context, ident, syntax, &mode, value, encoding);
+                    }
+                    if let Some(der_encoding) = der_encoding {
+                        test_reencode_value(context, ident, &der, &mode, value, der_encoding);
+                    }
+                }
+            }
+        }
     }
 }
 
@@ -238,6 +570,20 @@ pub fn execute_json_test(module_file: &str, data_file: &str) {
 
         let mut encodings = Vec::new();
         if let Some(tests) = test_file.tests.as_ref().or(entry.tests.as_ref()) {
+            if tests.contains(&TestMode::Basic) {
+                encodings.push((
+                    TransferSyntax::Basic(BasicEncodingKind::Basic),
+                    hex::decode(entry.ber.as_ref().expect("missing field 'ber'"))
+                        .expect("invalid BER hex"),
+                ));
+            }
+            if tests.contains(&TestMode::Canonical) {
+                encodings.push((
+                    TransferSyntax::Basic(BasicEncodingKind::Canonical),
+                    hex::decode(entry.cer.as_ref().expect("missing field 'cer'"))
+                        .expect("invalid CER hex"),
+                ));
+            }
             if tests.contains(&TestMode::Distinguished) {
                 encodings.push((
                     TransferSyntax::Basic(BasicEncodingKind::Distinguished),
@@ -247,29 +593,94 @@ pub fn execute_json_test(module_file: &str, data_file: &str) {
             }
             if tests.contains(&TestMode::Packed) {
                 let per = entry.per.as_ref().expect("missing field 'per'");
-                match per {
-                    PackedEncodings::BothAlignments(hex) => {
-                        let binary = hex::decode(hex).expect("invalid PER hex");
-                        encodings.push((
-                            TransferSyntax::Packed(PackedEncodingKind::CanonicalAligned),
-                            binary.clone(),
-                        ));
-                        encodings.push((
-                            TransferSyntax::Packed(PackedEncodingKind::CanonicalUnaligned),
-                            binary,
-                        ));
-                    }
-                    PackedEncodings::ByAlignment { aligned, unaligned } => {
-                        encodings.push((
-                            TransferSyntax::Packed(PackedEncodingKind::CanonicalAligned),
-                            hex::decode(aligned).expect("invalid CPER hex"),
-                        ));
-                        encodings.push((
-                            TransferSyntax::Packed(PackedEncodingKind::CanonicalUnaligned),
-                            hex::decode(unaligned).expect("invalid CUPER hex"),
-                        ));
-                    }
-                }
+                encodings.extend(per.to_encodings(
+                    PackedEncodingKind::BasicAligned,
+                    PackedEncodingKind::BasicUnaligned,
+                ));
+            }
+            if tests.contains(&TestMode::CanonicalPacked) {
+                let cper = entry
+                    .cper
+                    .as_ref()
+                    .or(entry.per.as_ref())
+                    .expect("missing field 'cper'");
+                encodings.extend(cper.to_encodings(
+                    PackedEncodingKind::CanonicalAligned,
+                    PackedEncodingKind::CanonicalUnaligned,
+                ));
+            }
+            if tests.contains(&TestMode::Octet) {
+                encodings.push((
+                    TransferSyntax::Octet(OctetEncodingKind::Basic),
+                    hex::decode(entry.oer.as_ref().expect("missing field 'oer'"))
+                        .expect("invalid OER hex"),
+                ));
+            }
+            if tests.contains(&TestMode::CanonicalOctet) {
+                let coer = entry
+                    .coer
+                    .as_ref()
+                    .or(entry.oer.as_ref())
+                    .expect("missing field 'coer'");
+                encodings.push((
+                    TransferSyntax::Octet(OctetEncodingKind::Canonical),
+                    hex::decode(coer).expect("invalid COER hex"),
+                ));
+            }
+            if tests.contains(&TestMode::Xml) {
+                encodings.push((
+                    TransferSyntax::Xml(XmlEncodingKind::Basic),
+                    entry
+                        .xer
+                        .as_ref()
+                        .expect("missing field 'xer'")
+                        .as_bytes()
+                        .to_vec(),
+                ));
+            }
+            if tests.contains(&TestMode::CanonicalXml) {
+                encodings.push((
+                    TransferSyntax::Xml(XmlEncodingKind::Canonical),
+                    entry
+                        .cxer
+                        .as_ref()
+                        .expect("missing field 'cxer'")
+                        .as_bytes()
+                        .to_vec(),
+                ));
+            }
+            if tests.contains(&TestMode::ExtendedXml) {
+                encodings.push((
+                    TransferSyntax::Xml(XmlEncodingKind::Extended),
+                    entry
+                        .exer
+                        .as_ref()
+                        .expect("missing field 'exer'")
+                        .as_bytes()
+                        .to_vec(),
+                ));
+            }
+            if tests.contains(&TestMode::Json) {
+                encodings.push((
+                    TransferSyntax::Json(JsonEncodingKind::Basic),
+                    entry
+                        .jer
+                        .as_ref()
+                        .expect("missing field 'jer'")
+                        .as_bytes()
+                        .to_vec(),
+                ));
+            }
+            if tests.contains(&TestMode::GenericString) {
+                encodings.push((
+                    TransferSyntax::String(StringEncodingKind::Generic),
+                    entry
+                        .gser
+                        .as_ref()
+                        .expect("missing field 'gser'")
+                        .as_bytes()
+                        .to_vec(),
+                ));
             }
         }
 
@@ -299,8 +710,14 @@ pub fn execute_json_test(module_file: &str, data_file: &str) {
             test_encode_value(&context, &ident, declared_value, &encodings);
         }
         if tests.contains(&TestMode::Decode) {
-            let value = entry.value.as_ref().expect("missing 'value' in test JSON");
-            test_decode_value(&context, &ident, declared_value, &encodings, value);
+            test_decode_value(
+                &context,
+                &ident,
+                declared_value,
+                &encodings,
+                entry.value.as_ref(),
+                tests.contains(&TestMode::Encode),
+            );
         }
     }
 }
@@ -429,6 +846,12 @@ json_compile_test!(
     test_choice_inner_type_constraints,
     "../../test-data/compile/ChoiceInnerTypeConstraintTest"
 );
+json_compile_test!(
+    test_encoding_control,
+    "../../test-data/compile/encoding/",
+    "EncodingControlTest",
+    "EncodingControlTargetTest"
+);
 json_compile_test!(
     test_matching_imports,
     "../../test-data/compile/import/",
@@ -1,15 +1,15 @@
 use std::io;
 
-use num::bigint::Sign;
 use num::{BigInt, BigUint};
 use widestring::{Utf16String, Utf32String};
 
 use super::reader::{read_vlq, DerReader};
+use super::validate::DerValidator;
 use crate::compiler::parser::Result;
 use crate::encoding::*;
 use crate::{
     compiler::{
-        parser::{AstElement, Loc},
+        parser::{AstElement, Error, ErrorKind, Loc},
         Context,
     },
     types::*,
@@ -98,8 +98,6 @@ fn type_eq_tlv(
 }
 
 fn ber_decode_integer(value: &[u8]) -> io::Result<BigInt> {
-    const SIGN_MASK: u8 = 0b1000_0000;
-
     if value.is_empty() {
         return Err(io::Error::new(
             io::ErrorKind::InvalidData,
@@ -107,21 +105,82 @@ fn ber_decode_integer(value: &[u8]) -> io::Result<BigInt> {
         ));
     }
 
-    let sign = if value[0] & SIGN_MASK == SIGN_MASK {
-        Sign::Minus
-    } else {
-        Sign::Plus
-    };
+    // X.690 clause 8.3.3: the contents octets are the two's complement of the integer
+    Ok(BigInt::from_signed_bytes_be(value))
+}
+
+fn ber_decode_real(value: &[u8]) -> io::Result<RealValue> {
+    let illegal = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+
+    if value.is_empty() {
+        return Ok(RealValue::Finite {
+            mantissa: BigInt::ZERO,
+            base: 10,
+            exponent: BigInt::ZERO,
+        });
+    }
 
-    Ok(BigInt::from_bytes_be(sign, value))
+    let first = value[0];
+    if first & 0b1000_0000 != 0 {
+        // X.690 clause 8.5.7: binary encoding
+        // bases 8 and 16 are converted to base 2, which keeps the value exact
+        let base_bits: u32 = match (first >> 4) & 0b11 {
+            0b00 => 1,
+            0b01 => 3,
+            0b10 => 4,
+            _ => return Err(illegal("REAL uses a reserved base")),
+        };
+        let scale = (first >> 2) & 0b11;
+        let (exp_start, exp_len) = match first & 0b11 {
+            0b11 => (
+                2,
+                *value
+                    .get(1)
+                    .ok_or_else(|| illegal("REAL is missing its exponent length"))?
+                    as usize,
+            ),
+            len => (1, len as usize + 1),
+        };
+        let exp_end = exp_start + exp_len;
+        if exp_end > value.len() {
+            return Err(illegal("REAL exponent is longer than its contents"));
+        }
+
+        let exponent = ber_decode_integer(&value[exp_start..exp_end])?;
+        let mantissa = BigInt::from(BigUint::from_bytes_be(&value[exp_end..])) << scale;
+        Ok(RealValue::Finite {
+            mantissa: if first & 0b0100_0000 != 0 {
+                -mantissa
+            } else {
+                mantissa
+            },
+            base: 2,
+            exponent: exponent * base_bits,
+        })
+    } else if first & 0b0100_0000 != 0 {
+        // X.690 clause 8.5.9: special real values
+        match first {
+            0x40 => Ok(RealValue::PlusInfinity),
+            0x41 => Ok(RealValue::MinusInfinity),
+            0x42 => Ok(RealValue::NotANumber),
+            0x43 => Ok(RealValue::MinusZero),
+            _ => Err(illegal("REAL is a reserved special value")),
+        }
+    } else {
+        // X.690 clause 8.5.8: decimal encoding with ISO 6093
+        let str = std::str::from_utf8(&value[1..])
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        str.trim_start_matches(' ')
+            .parse()
+            .map_err(|_| illegal("REAL is not a valid ISO 6093 number"))
+    }
 }
 
-fn ber_decode_universal(
+pub(crate) fn ber_decode_universal(
     syntax: BasicEncodingKind,
-    tlv: &Tlv<'_>,
+    value: &[u8],
     tag_type: TagType,
 ) -> io::Result<DecodedValueKind> {
-    let value = tlv.value.element;
     Ok(match tag_type {
         TagType::Any => DecodedValueKind::Raw(value.to_vec()),
         TagType::Boolean => {
@@ -153,21 +212,26 @@ fn ber_decode_universal(
         }
         TagType::Integer => DecodedValueKind::Integer(ber_decode_integer(value)?),
         TagType::BitString => {
-            if value.len() < 2 {
+            if value.is_empty() {
                 return Err(io::Error::new(
                     io::ErrorKind::InvalidData,
                     "BIT STRING must have a value",
                 ));
             }
 
+            // X.690 clause 8.6.2.2 and 8.6.2.3
             let unused_bits = value[0];
-            if unused_bits != 0 {
-                todo!("unimplemented: BIT STRING unused bits");
+            if unused_bits > 7 || (value.len() == 1 && unused_bits != 0) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "BIT STRING has an illegal number of unused bits",
+                ));
             }
 
-            let be_bits = &value[1..];
-
-            DecodedValueKind::BitString(BigUint::from_bytes_be(be_bits))
+            DecodedValueKind::BitString(BitStringValue {
+                data: value[1..].to_vec(),
+                unused_bits,
+            })
         }
         TagType::OctetString => DecodedValueKind::OctetString(value.to_vec()),
         TagType::Null => {
@@ -199,6 +263,17 @@ fn ber_decode_universal(
             }
             DecodedValueKind::ObjectIdentifier(Oid(nodes))
         }
+        TagType::RelativeOid => {
+            let mut nodes = Vec::new();
+            let mut offset = 0;
+            while offset < value.len() {
+                let (node, len) = read_vlq(&value[offset..])?;
+                offset += len;
+                nodes.push(node);
+            }
+            DecodedValueKind::RelativeOid(Oid(nodes))
+        }
+        TagType::Real => DecodedValueKind::Real(ber_decode_real(value)?),
         TagType::Enumerated => {
             let int = ber_decode_integer(value)?;
             let int: i64 = int.try_into().map_err(|_| {
@@ -289,7 +364,7 @@ fn ber_decode_universal(
             }
             Err(err) => return Err(io::Error::new(io::ErrorKind::InvalidData, err)),
         },
-        TagType::Sequence | TagType::Set => {
+        TagType::Sequence | TagType::Set | TagType::External | TagType::EmbeddedPDV => {
             return Err(io::Error::new(
                 io::ErrorKind::InvalidData,
                 format!("{} must have the Constructed bit set", tag_type),
@@ -299,6 +374,10 @@ fn ber_decode_universal(
             UTCTime::parse(&AstElement::new(value, Loc::at(0)))
                 .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.kind.message()))?,
         ),
+        TagType::GeneralizedTime => DecodedValueKind::GeneralizedTime(
+            GeneralizedTime::parse(&AstElement::new(value, Loc::at(0)))
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.kind.message()))?,
+        ),
         TagType::Date => DecodedValueKind::Date(
             Date::parse(&AstElement::new(value, Loc::at(0)))
                 .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.kind.message()))?,
@@ -319,22 +398,195 @@ fn ber_decode_universal(
             ),
             Err(err) => return Err(io::Error::new(io::ErrorKind::InvalidData, err)),
         },
-        other => todo!("decode {:?}", other),
     })
 }
 
+fn is_segmentable_string_type(tag_type: TagType) -> bool {
+    matches!(
+        tag_type,
+        TagType::BitString
+            | TagType::OctetString
+            | TagType::ObjectDescriptor
+            | TagType::UTF8String
+            | TagType::NumericString
+            | TagType::PrintableString
+            | TagType::TeletexString
+            | TagType::VideotexString
+            | TagType::IA5String
+            | TagType::GraphicString
+            | TagType::VisibleString
+            | TagType::GeneralString
+            | TagType::UniversalString
+            | TagType::BMPString
+    )
+}
+
+/// Returns `true` if values of the type can only be encoded in the primitive form (X.690 clause 8).
+fn is_primitive_only_type(tag_type: TagType) -> bool {
+    matches!(
+        tag_type,
+        TagType::Boolean
+            | TagType::Integer
+            | TagType::Enumerated
+            | TagType::Real
+            | TagType::Null
+            | TagType::ObjectIdentifier
+            | TagType::RelativeOid
+    )
+}
+
+/// Returns the UNIVERSAL type of a TLV, which is implied by the IMPLICIT tag of the type if the tag is not UNIVERSAL.
+fn get_universal_tag_type(
+    context: &Context,
+    tlv: &Tlv<'_>,
+    mode: &DecodeMode,
+) -> DecodeResult<Option<TagType>> {
+    let tlv_tag = &tlv.tag.element;
+    let tag_type = match tlv_tag.class {
+        Class::Universal => TagType::try_from(tlv_tag.num).ok(),
+        _ => match mode {
+            DecodeMode::Contextless => None,
+            DecodeMode::SpecificType { resolved, .. } => resolved
+                .get_possible_tags(context)
+                .map_err(DecodeError::Parser)?
+                .into_iter()
+                .find(|(tag, _)| tag.class == tlv_tag.class && tag.num == tlv_tag.num)
+                .and_then(|(tag, ty)| match tag.kind {
+                    // the constructed form of an EXPLICIT tag wraps the inner TLV, not string segments
+                    TagKind::Explicit(_) => None,
+                    TagKind::Implicit => ty.tag_type(),
+                }),
+        },
+    };
+    Ok(tag_type)
+}
+
+/// Returns the UNIVERSAL type of a constructed TLV if it is a string type encoded in segments.
+fn get_constructed_string_type(
+    context: &Context,
+    tlv: &Tlv<'_>,
+    mode: &DecodeMode,
+) -> DecodeResult<Option<TagType>> {
+    Ok(get_universal_tag_type(context, tlv, mode)?
+        .filter(|tag_type| is_segmentable_string_type(*tag_type)))
+}
+
+/// Concatenates the segments of a constructed string encoding into `data`.
+/// See X.690 clauses 8.6.4 and 8.7.3 for how segments are encoded.
+fn ber_reassemble_string(
+    syntax: BasicEncodingKind,
+    tlv: &Tlv<'_>,
+    segment_type: TagType,
+    data: &mut Vec<u8>,
+    unused_bits: &mut u8,
+    mut validator: Option<&mut DerValidator<'_>>,
+) -> DecodeResult<()> {
+    for segment in DerReader::new(tlv.value.element, tlv.value.pos.start, syntax) {
+        let segment = segment.map_err(DecodeError::Io)?;
+        if let Some(validator) = validator.as_deref_mut() {
+            validator.check_header(&segment);
+        }
+        let segment_tag = &segment.tag.element;
+        if segment_tag.class != Class::Universal || segment_tag.num != segment_type as u16 {
+            return Err(DecodeError::Decoder {
+                message: format!(
+                    "segment of constructed {} must have tag [UNIVERSAL {}], but found {}",
+                    segment_type, segment_type as u16, segment_tag
+                ),
+                pos: segment.tag.pos,
+            });
+        }
+        if *unused_bits != 0 {
+            return Err(DecodeError::Decoder {
+                message: "only the last segment of a constructed BIT STRING can have unused bits"
+                    .to_string(),
+                pos: segment.tag.pos,
+            });
+        }
+
+        match segment_tag.form {
+            TypeForm::Constructed => ber_reassemble_string(
+                syntax,
+                &segment,
+                segment_type,
+                data,
+                unused_bits,
+                validator.as_deref_mut(),
+            )?,
+            TypeForm::Primitive => {
+                let value = segment.value.element;
+                if segment_type == TagType::BitString {
+                    if value.is_empty() {
+                        return Err(DecodeError::Decoder {
+                            message: "BIT STRING segment must have a value".to_string(),
+                            pos: segment.value.pos,
+                        });
+                    }
+                    *unused_bits = value[0];
+                    data.extend_from_slice(&value[1..]);
+                } else {
+                    data.extend_from_slice(value);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns whether a constructed value is a SET OF value (`Some(true)`), a SET value (`Some(false)`), or neither.
+fn is_set_of(mode: &DecodeMode, tlv: &Tlv<'_>, elements: &[DecodedValue]) -> Option<bool> {
+    match mode {
+        DecodeMode::SpecificType { resolved, .. } => match &resolved.ty {
+            BuiltinType::StructureOf(of) if of.ty == TagType::Set => Some(true),
+            BuiltinType::Structure(structure) if structure.ty == TagType::Set => Some(false),
+            _ => None,
+        },
+        DecodeMode::Contextless => {
+            let tag = &tlv.tag.element;
+            if tag.class != Class::Universal || tag.num != TagType::Set as u16 {
+                return None;
+            }
+
+            // the components of a SET have distinct tags, so the value is assumed to be a SET OF if all its tags are the same
+            Some(elements.windows(2).all(|window| {
+                window[0].tag.element.class == window[1].tag.element.class
+                    && window[0].tag.element.num == window[1].tag.element.num
+            }))
+        }
+    }
+}
+
 fn ber_decode_tlv(
     syntax: BasicEncodingKind,
     context: &Context,
     tlv: Tlv<'_>,
     mode: &DecodeMode,
+    mut validator: Option<&mut DerValidator<'_>>,
 ) -> DecodeResult<DecodedValue> {
+    if let Some(validator) = validator.as_deref_mut() {
+        validator.check_header(&tlv);
+    }
+
     let form = match tlv.tag.element.form {
         TypeForm::Primitive => {
             let kind = match tlv.tag.element.class {
                 Class::Universal => match TagType::try_from(tlv.tag.element.num) {
                     Ok(tag_type) => {
-                        ber_decode_universal(syntax, &tlv, tag_type).map_err(DecodeError::Io)?
+                        if let Some(validator) = validator.as_deref_mut() {
+                            let ty = match mode {
+                                DecodeMode::SpecificType { resolved, .. } => Some(&resolved.ty),
+                                DecodeMode::Contextless => None,
+                            };
+                            validator.check_contents(
+                                tlv.value.element,
+                                tlv.value.pos,
+                                tag_type,
+                                ty,
+                            );
+                        }
+                        ber_decode_universal(syntax, tlv.value.element, tag_type)
+                            .map_err(DecodeError::Io)?
                     }
                     Err(_) => DecodedValueKind::Raw(tlv.value.element.to_vec()),
                 },
@@ -345,7 +597,16 @@ fn ber_decode_tlv(
                             .map_err(DecodeError::Parser)?
                         {
                             // we use ty.tag_type() here to get the UNIVERSAL tag type for the underlying builtin type, not the user-defined tag
-                            ber_decode_universal(syntax, &tlv, ty.tag_type().expect("tag_type"))
+                            let tag_type = ty.tag_type().expect("tag_type");
+                            if let Some(validator) = validator.as_deref_mut() {
+                                validator.check_contents(
+                                    tlv.value.element,
+                                    tlv.value.pos,
+                                    tag_type,
+                                    Some(&ty),
+                                );
+                            }
+                            ber_decode_universal(syntax, tlv.value.element, tag_type)
                                 .map_err(DecodeError::Io)?
                         } else {
                             let tag_str = resolved
@@ -365,10 +626,60 @@ fn ber_decode_tlv(
             };
             DecodedValueForm::Primitive(kind)
         }
+        TypeForm::Constructed if get_constructed_string_type(context, &tlv, mode)?.is_some() => {
+            let tag_type = get_constructed_string_type(context, &tlv, mode)?.unwrap();
+            if let Some(validator) = validator.as_deref_mut() {
+                validator.check_primitive_string(&tlv, tag_type);
+            } else if syntax == BasicEncodingKind::Distinguished {
+                // X.690 clause 10.2
+                return Err(DecodeError::Decoder {
+                    message: format!("{} must use the primitive form in DER", tag_type),
+                    pos: tlv.tag.pos,
+                });
+            }
+
+            let segment_type = match tag_type {
+                TagType::BitString => TagType::BitString,
+                _ => TagType::OctetString,
+            };
+            let mut data = Vec::with_capacity(tlv.value.element.len());
+            let mut unused_bits = 0;
+            ber_reassemble_string(
+                syntax,
+                &tlv,
+                segment_type,
+                &mut data,
+                &mut unused_bits,
+                validator.as_deref_mut(),
+            )?;
+            if tag_type == TagType::BitString {
+                data.insert(0, unused_bits);
+            }
+            if let Some(validator) = validator.as_deref_mut() {
+                let ty = match mode {
+                    DecodeMode::SpecificType { resolved, .. } => Some(&resolved.ty),
+                    DecodeMode::Contextless => None,
+                };
+                validator.check_contents(&data, tlv.value.pos, tag_type, ty);
+            }
+
+            DecodedValueForm::Primitive(
+                ber_decode_universal(syntax, &data, tag_type).map_err(DecodeError::Io)?,
+            )
+        }
         TypeForm::Constructed => {
+            if let Some(tag_type) = get_universal_tag_type(context, &tlv, mode)?
+                .filter(|tag_type| is_primitive_only_type(*tag_type))
+            {
+                return Err(DecodeError::Decoder {
+                    message: format!("{} must have the Constructed bit cleared", tag_type),
+                    pos: tlv.tag.pos,
+                });
+            }
+
             let mut index = 0;
             let mut elements = Vec::new();
-            for tlv in DerReader::new(tlv.value.element, tlv.value.pos.start) {
+            for tlv in DerReader::new(tlv.value.element, tlv.value.pos.start, syntax) {
                 let tlv = tlv.map_err(DecodeError::Io)?;
                 let component = get_component_by_tag(context, mode, &tlv.tag, index)?;
                 let mode = match component {
@@ -378,15 +689,21 @@ fn ber_decode_tlv(
                             source_ident: match &data.tagged_type.ty {
                                 UntaggedType::BuiltinType(_) => None,
                                 UntaggedType::Reference(typeref) => Some(typeref.element.clone()),
-                                UntaggedType::ObjectClassField(_) => {
-                                    todo!("handle ObjectClassField")
+                                UntaggedType::ObjectClassField(ocf) => {
+                                    return Err(DecodeError::Parser(Error {
+                                        kind: ErrorKind::Ast(format!(
+                                            "decoding a component of the type {} is not yet supported",
+                                            ocf
+                                        )),
+                                        loc: ocf.field.loc,
+                                    }))
                                 }
                             },
                             component_name: data.name,
-                            resolved: data
+                            resolved: Box::new(data
                                 .tagged_type
                                 .resolve(context)
-                                .map_err(DecodeError::Parser)?,
+                                .map_err(DecodeError::Parser)?),
                         }
                     }
                     None => {
@@ -394,7 +711,20 @@ fn ber_decode_tlv(
                         DecodeMode::Contextless
                     }
                 };
-                elements.push(ber_decode_tlv(syntax, context, tlv, &mode)?);
+                elements.push(ber_decode_tlv(
+                    syntax,
+                    context,
+                    tlv,
+                    &mode,
+                    validator.as_deref_mut(),
+                )?);
+            }
+            if let Some(validator) = validator {
+                match is_set_of(mode, &tlv, &elements) {
+                    Some(true) => validator.check_set_of_ordering(&elements),
+                    Some(false) => validator.check_set_ordering(&elements),
+                    None => (),
+                }
             }
             DecodedValueForm::Constructed(elements)
         }
@@ -426,7 +756,7 @@ pub fn ber_decode_value(
     context: &Context,
     mode: &DecodeMode,
 ) -> DecodeResult<Vec<DecodedValue>> {
-    let reader = DerReader::new(buf, 0);
+    let reader = DerReader::new(buf, 0, syntax);
     reader
         .into_iter()
         .map(|tlv| {
@@ -435,19 +765,47 @@ pub fn ber_decode_value(
                 context,
                 tlv.map_err(DecodeError::Io)?,
                 mode,
+                None,
             ))?
         })
         .collect::<DecodeResult<Vec<DecodedValue>>>()
 }
 
+/// Decodes the value with the rules of BER, and reports every violation of the DER restrictions on BER
+/// (X.690 clauses 10 and 11) instead of failing to decode on the first one.
+/// Input that is not valid BER still fails to decode.
+pub fn der_validate_value(
+    buf: &[u8],
+    context: &Context,
+    mode: &DecodeMode,
+) -> DecodeResult<(Vec<DecodedValue>, Vec<DerViolation>)> {
+    let mut validator = DerValidator::new(buf);
+    let mut values = Vec::new();
+    for tlv in DerReader::new(buf, 0, BasicEncodingKind::Basic) {
+        let tlv = tlv.map_err(DecodeError::Io)?;
+        values.push(ber_decode_tlv(
+            BasicEncodingKind::Basic,
+            context,
+            tlv,
+            mode,
+            Some(&mut validator),
+        )?);
+    }
+    Ok((values, validator.violations))
+}
+
 #[cfg(test)]
 mod test {
     use crate::{
-        compiler::{test::json_test, Context},
-        encoding::{BasicEncodingKind, DecodeMode},
+        compiler::{
+            test::{compile_module, json_test},
+            Context,
+        },
+        encoding::{BasicEncodingKind, DecodeError, DecodeMode},
+        module::{ModuleIdentifier, QualifiedIdentifier},
     };
 
-    use super::ber_decode_value;
+    use super::{ber_decode_value, der_validate_value};
 
     #[test]
     fn test_ber_decode_contextless() {
@@ -464,8 +822,120 @@ mod test {
         .unwrap();
     }
 
+    #[test]
+    fn test_der_validate_valid() {
+        let der = include_bytes!("../../../test-data/decode/LetsEncryptX3.der");
+        let context = Context::new();
+        let (_, violations) = der_validate_value(der, &context, &DecodeMode::Contextless).unwrap();
+        assert!(violations.is_empty(), "violations = {:?}", violations);
+    }
+
+    #[test]
+    fn test_der_validate_violations() {
+        let ber = hex::decode(concat!(
+            "30812F",                         // non-minimal length
+            "010101",                         // BOOLEAN TRUE as 0x01
+            "02020005",                       // padded INTEGER
+            "03020781",                       // BIT STRING with non-zero unused bits
+            "3106020105020103",               // unsorted SET OF
+            "180D3230323430313031313230305A", // GeneralizedTime without seconds
+            "24800401AA0000",                 // constructed, indefinite length OCTET STRING
+            "2404048101BB", // constructed OCTET STRING with a non-minimal segment length
+        ))
+        .unwrap();
+        let context = Context::new();
+        let (values, violations) =
+            der_validate_value(&ber, &context, &DecodeMode::Contextless).unwrap();
+        assert_eq!(values.len(), 1);
+
+        let clauses = violations
+            .iter()
+            .map(|violation| violation.clause)
+            .collect::<Vec<&str>>();
+        assert_eq!(
+            clauses,
+            vec!["10.1", "11.1", "8.3.2", "11.2.1", "11.6", "11.7", "10.1", "10.2", "10.2", "10.1"]
+        );
+        assert_eq!(violations[0].pos.start, 1);
+        assert_eq!(violations[1].pos.start, 5);
+    }
+
+    #[test]
+    fn test_ber_decode_illegal_form() {
+        let context = Context::new();
+        for (encoding, is_io_error) in [
+            ("0800", true),        // primitive EXTERNAL
+            ("0B00", true),        // primitive EMBEDDED PDV
+            ("2A030A0101", false), // constructed ENUMERATED
+            ("2103010101", false), // constructed BOOLEAN
+        ] {
+            let err = ber_decode_value(
+                BasicEncodingKind::Basic,
+                &hex::decode(encoding).unwrap(),
+                &context,
+                &DecodeMode::Contextless,
+            )
+            .expect_err(encoding);
+            assert_eq!(
+                matches!(err, DecodeError::Io(_)),
+                is_io_error,
+                "{}: {}",
+                encoding,
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn test_ber_decode_object_class_field() {
+        let mut context = Context::new();
+        compile_module(
+            &mut context,
+            "BerDecodeTest.asn",
+            r#"BerDecodeTest DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+
+MESSAGE-ID ::= CLASS { &id INTEGER UNIQUE } WITH SYNTAX { ID &id }
+
+Identified ::= SEQUENCE {
+    id MESSAGE-ID.&id
+}
+
+END
+"#,
+        );
+
+        let ident = QualifiedIdentifier::new(
+            ModuleIdentifier::with_name("BerDecodeTest".to_string()),
+            "Identified".to_string(),
+        );
+        let mode = DecodeMode::SpecificType {
+            source_ident: Some(ident.clone()),
+            component_name: None,
+            resolved: Box::new(
+                context
+                    .lookup_type(&ident)
+                    .expect("lookup_type")
+                    .ty
+                    .resolve(&context)
+                    .expect("resolve"),
+            ),
+        };
+        let err = ber_decode_value(
+            BasicEncodingKind::Basic,
+            &hex::decode("3003800105").unwrap(),
+            &context,
+            &mode,
+        )
+        .expect_err("ObjectClassField component is decoded");
+        assert!(matches!(err, DecodeError::Parser(_)), "{}", err);
+    }
+
     json_test!(
         test_ber_decode_specific_type,
         "../../../test-data/decode/DecodeTest"
     );
+    json_test!(
+        test_ber_decode_indefinite_and_constructed,
+        "../../../test-data/decode/BerDecodeTest"
+    );
 }
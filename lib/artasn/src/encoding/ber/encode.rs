@@ -1,5 +1,5 @@
 use crate::{
-    compiler::parser::{Error, ErrorKind, Loc},
+    compiler::parser::{AstElement, Error, ErrorKind, Loc},
     encoding::{ber::MAX_VLQ_LEN, *},
 };
 
@@ -47,6 +47,52 @@ fn write_tlv_len(len: u64, buf: &mut Vec<u8>) {
     }
 }
 
+/// The maximum number of contents octets in a CER string segment (X.690 clause 9.2).
+const CER_SEGMENT_LEN: usize = 1000;
+
+/// Rewrites the reversed contents octets written since `start_len` as the segments of a
+/// constructed CER string encoding (X.690 clause 9.2), if the contents are longer than 1000 octets.
+/// Returns whether the contents were segmented.
+fn cer_segment_string(buf: &mut Vec<u8>, start_len: usize, is_bit_string: bool) -> bool {
+    if buf.len() - start_len <= CER_SEGMENT_LEN {
+        return false;
+    }
+
+    let contents: Vec<u8> = buf.drain(start_len..).rev().collect();
+    let (segment_tag, segment_data_len, unused_bits, data) = if is_bit_string {
+        // each BIT STRING segment needs an octet for its unused bits count
+        (
+            TagType::BitString,
+            CER_SEGMENT_LEN - 1,
+            contents[0],
+            &contents[1..],
+        )
+    } else {
+        (TagType::OctetString, CER_SEGMENT_LEN, 0, &contents[..])
+    };
+
+    // end-of-contents
+    buf.extend_from_slice(&[0x00, 0x00]);
+
+    let segment_count = data.len().div_ceil(segment_data_len);
+    for (i, segment) in data.chunks(segment_data_len).enumerate().rev() {
+        let segment_start = buf.len();
+        buf.extend(segment.iter().rev());
+        if is_bit_string {
+            // only the last segment can have unused bits
+            buf.push(if i == segment_count - 1 {
+                unused_bits
+            } else {
+                0
+            });
+        }
+        write_tlv_len((buf.len() - segment_start) as u64, buf);
+        buf.push(segment_tag as u8);
+    }
+
+    true
+}
+
 fn ber_encode_integer(buf: &mut Vec<u8>, num: &BigInt) {
     if num == &BigInt::ZERO {
         // fast encode for 0
@@ -59,7 +105,8 @@ fn ber_encode_integer(buf: &mut Vec<u8>, num: &BigInt) {
     }
 }
 
-fn ber_encode_character_string(buf: &mut Vec<u8>, tag_type: TagType, str: &str) {
+/// Writes the reversed contents octets of a character string value.
+pub(crate) fn ber_encode_character_string(buf: &mut Vec<u8>, tag_type: TagType, str: &str) {
     match tag_type {
         TagType::NumericString
         | TagType::PrintableString
@@ -109,7 +156,54 @@ fn ber_encode_character_string(buf: &mut Vec<u8>, tag_type: TagType, str: &str)
     }
 }
 
-fn ber_encode_real(buf: &mut Vec<u8>, mut mantissa: BigInt, base: i64, mut exponent: BigInt) {
+/// Writes the reversed contents octets of an OBJECT IDENTIFIER value.
+pub(crate) fn ber_encode_object_identifier(
+    buf: &mut Vec<u8>,
+    context: &Context,
+    oid: &ObjectIdentifier,
+) -> Result<()> {
+    let oid = oid.resolve_oid(context)?.0;
+
+    match oid.len() {
+        2.. => {
+            for node in oid.iter().skip(2).rev() {
+                write_vlq(*node, buf);
+            }
+        }
+        _ => {
+            return Err(Error {
+                kind: ErrorKind::Ast(
+                    "illegal OBJECT IDENTIFIER with less than two nodes".to_string(),
+                ),
+                loc: Loc::default(), // TODO: add loc info to the entire oid, not just its elements
+            });
+        }
+    }
+
+    let prefix = oid[0] * 40 + oid[1];
+    write_vlq(prefix, buf);
+    Ok(())
+}
+
+/// Writes the reversed contents octets of a RELATIVE-OID value.
+pub(crate) fn ber_encode_relative_oid(
+    buf: &mut Vec<u8>,
+    context: &Context,
+    oid: &ObjectIdentifier,
+) -> Result<()> {
+    let oid = oid.resolve_oid(context)?.0;
+    for node in oid.iter().rev() {
+        write_vlq(*node, buf);
+    }
+    Ok(())
+}
+
+pub(crate) fn ber_encode_real(
+    buf: &mut Vec<u8>,
+    mut mantissa: BigInt,
+    base: i64,
+    mut exponent: BigInt,
+) {
     if mantissa == BigInt::ZERO {
         return;
     }
@@ -175,22 +269,150 @@ fn ber_encode_real(buf: &mut Vec<u8>, mut mantissa: BigInt, base: i64, mut expon
     }
 }
 
+/// Returns the tag that determines the position of a SET component in canonical order.
+/// DER orders an untagged CHOICE by the tag of its chosen alternative (X.690 clause 10.3),
+/// but CER orders it by the smallest tag in the CHOICE type (X.690 clause 9.3).
+fn get_set_component_tag(
+    kind: BasicEncodingKind,
+    context: &Context,
+    value: &ResolvedValue,
+) -> Result<(Class, u16)> {
+    match &value.ty.tag {
+        Some(tag) => Ok((tag.class, tag.num)),
+        None => match (&value.value, kind) {
+            (BuiltinValue::Choice(choice), BasicEncodingKind::Distinguished) => {
+                let alternative_value = choice.value.resolve(context)?;
+                get_set_component_tag(kind, context, &alternative_value)
+            }
+            _ => Ok(value
+                .ty
+                .get_possible_tags(context)?
+                .into_iter()
+                .map(|(tag, _)| (tag.class, tag.num))
+                .min()
+                .expect("CHOICE has no alternatives")),
+        },
+    }
+}
+
+/// Returns whether the component is omitted from the encoding because its value is equal to its DEFAULT value.
+fn is_omitted_default_value(
+    kind: BasicEncodingKind,
+    options: &EncodeOptions,
+    context: &Context,
+    component: &StructureValueComponent,
+) -> Result<bool> {
+    if kind == BasicEncodingKind::Basic && options.encode_default_values {
+        return Ok(false);
+    }
+    if component.is_default {
+        return Ok(true);
+    }
+    match &component.default_value {
+        Some(default_value) => component.value.try_eq(context, default_value),
+        None => Ok(false),
+    }
+}
+
 fn ber_encode_structure(
+    kind: BasicEncodingKind,
     mode: EncodeMode,
+    options: &EncodeOptions,
     buf: &mut Vec<u8>,
     context: &Context,
+    tag_type: TagType,
     components: &[StructureValueComponent],
 ) -> Result<()> {
     // ast.rs guarantees all components in SEQUENCE/SET type are provided in value,
     // and that the value provides only components in the SEQUENCE/SET type,
     // and that the component values are in the same order as in the type definition
-    for component in components.iter().rev() {
-        // default values aren't encoded
-        if component.is_default {
+    let mut values = Vec::with_capacity(components.len());
+    for component in components {
+        if is_omitted_default_value(kind, options, context, component)? {
             continue;
         }
-        let typed_value = component.value.resolve(context)?;
-        ber_encode_value(mode, buf, context, &typed_value)?;
+        values.push((component, component.value.resolve(context)?));
+    }
+
+    if tag_type == TagType::Set && kind != BasicEncodingKind::Basic {
+        // See X.690 clause 9.3 (CER) and clause 10.3 (DER)
+        let mut tagged_values = Vec::with_capacity(values.len());
+        for (component, value) in values {
+            let tag = get_set_component_tag(kind, context, &value)?;
+            tagged_values.push((tag, component, value));
+        }
+
+        match options.set_ordering {
+            SetOrdering::Sort => tagged_values.sort_by_key(|(tag, _, _)| *tag),
+            SetOrdering::Verify => {
+                if let Some(window) = tagged_values
+                    .windows(2)
+                    .find(|window| window[0].0 > window[1].0)
+                {
+                    return Err(Error {
+                        kind: ErrorKind::Ast(format!(
+                            "SET component '{}' must be placed before component '{}' in canonical order",
+                            window[1].1.name.element, window[0].1.name.element,
+                        )),
+                        loc: window[1].1.name.loc,
+                    });
+                }
+            }
+        }
+        values = tagged_values
+            .into_iter()
+            .map(|(_, component, value)| (component, value))
+            .collect();
+    }
+
+    for (_, typed_value) in values.iter().rev() {
+        ber_encode_value(kind, mode, options, buf, context, typed_value)?;
+    }
+
+    Ok(())
+}
+
+/// Encodes the elements of a SET OF value in ascending order of their encodings,
+/// as required by X.690 clause 11.6.
+fn ber_encode_set_of(
+    kind: BasicEncodingKind,
+    mode: EncodeMode,
+    options: &EncodeOptions,
+    buf: &mut Vec<u8>,
+    context: &Context,
+    elements: &[AstElement<TypedValue>],
+) -> Result<()> {
+    let mut encodings = Vec::with_capacity(elements.len());
+    for element in elements {
+        let resolved = element.resolve(context)?;
+        let mut element_buf = Vec::new();
+        ber_encode_value(kind, mode, options, &mut element_buf, context, &resolved)?;
+        element_buf.reverse();
+        encodings.push((element, element_buf));
+    }
+
+    match options.set_ordering {
+        // comparing the encodings as octet strings with the shorter encoding padded with trailing 0-octets
+        // is equivalent to a lexicographical comparison
+        SetOrdering::Sort => encodings.sort_by(|(_, a), (_, b)| a.cmp(b)),
+        SetOrdering::Verify => {
+            if let Some(window) = encodings
+                .windows(2)
+                .find(|window| window[0].1 > window[1].1)
+            {
+                return Err(Error {
+                    kind: ErrorKind::Ast(
+                        "SET OF element must be placed before the previous element in canonical order"
+                            .to_string(),
+                    ),
+                    loc: window[1].0.loc,
+                });
+            }
+        }
+    }
+
+    for (_, encoding) in encodings.iter().rev() {
+        buf.extend(encoding.iter().rev());
     }
 
     Ok(())
@@ -198,7 +420,9 @@ fn ber_encode_structure(
 
 /// See X.690 clause 8.18 and its subclauses to see what is being encoded here.
 fn ber_encode_external(
+    kind: BasicEncodingKind,
     mode: EncodeMode,
+    options: &EncodeOptions,
     buf: &mut Vec<u8>,
     context: &Context,
     components: &[StructureValueComponent],
@@ -209,21 +433,29 @@ fn ber_encode_external(
     {
         // if the "encoding" component is present, then EXTERNAL is defined as the X.208 version;
         // the X.690 encoding maps one-to-one with X.208 EXTERNAL, and can be encoded as a normal structure
-        ber_encode_structure(mode, buf, context, components)?;
+        ber_encode_structure(
+            kind,
+            mode,
+            options,
+            buf,
+            context,
+            TagType::Sequence,
+            components,
+        )?;
     } else {
         let data_value = components
             .iter()
             .find(|component| component.name.element == "data-value")
             .expect("missing data-value");
         let data_value = data_value.value.resolve(context)?;
-        ber_encode_value(mode, buf, context, &data_value)?;
+        ber_encode_value(kind, mode, options, buf, context, &data_value)?;
 
         if let Some(data_value_descriptor) = components
             .iter()
             .find(|component| component.name.element == "data-value-descriptor")
         {
             let data_value_descriptor = data_value_descriptor.value.resolve(context)?;
-            ber_encode_value(mode, buf, context, &data_value_descriptor)?;
+            ber_encode_value(kind, mode, options, buf, context, &data_value_descriptor)?;
         }
 
         let (direct_reference, indirect_reference) =
@@ -248,10 +480,10 @@ fn ber_encode_external(
                 _ => unreachable!(),
             };
         if let Some(indirect_reference) = indirect_reference {
-            ber_encode_value(mode, buf, context, &indirect_reference)?;
+            ber_encode_value(kind, mode, options, buf, context, &indirect_reference)?;
         }
         if let Some(direct_reference) = direct_reference {
-            ber_encode_value(mode, buf, context, &direct_reference)?;
+            ber_encode_value(kind, mode, options, buf, context, &direct_reference)?;
         }
     }
     Ok(())
@@ -291,7 +523,67 @@ fn ber_encode_tag(
     buf.push(class << 6 | form << 5 | msb_tag);
 }
 
-fn is_real_type(ty: &BuiltinType) -> bool {
+/// Encodes the contents octets of a REAL value written in its SEQUENCE form,
+/// which is either a special value or a mantissa, base and exponent.
+pub(crate) fn ber_encode_real_structure(
+    buf: &mut Vec<u8>,
+    context: &Context,
+    structure: &StructureValue,
+) -> Result<()> {
+    let special = structure
+        .components
+        .iter()
+        .find(|component| component.name.element == "artasn-special");
+    if let Some(special) = special {
+        let special = match special.value.resolve(context)?.value {
+            BuiltinValue::Enumerated(item) => item.resolve(context)?,
+            _ => unreachable!(),
+        };
+        match &special.value {
+            BuiltinValue::Integer(int) => {
+                let int: u32 = int.try_into().expect("SpecialReal is out of bounds");
+                if int == 0 {
+                    // PLUS-INFINITY
+                    buf.push(0x40);
+                } else if int == 1 {
+                    // MINUS-INFINITY
+                    buf.push(0x41);
+                } else if int == 2 {
+                    // NOT-A-NUMBER
+                    buf.push(0x42);
+                } else {
+                    unreachable!();
+                }
+            }
+            _ => unreachable!(),
+        }
+    } else {
+        macro_rules! to_int {
+            ( $component:expr) => {{
+                let component = &$component;
+                match &component.value.resolve(context)?.value {
+                    BuiltinValue::Integer(int) => int.clone(),
+                    _ => unreachable!(),
+                }
+            }};
+        }
+
+        let mantissa = to_int!(structure.components[0]);
+        let base = to_int!(structure.components[1]);
+        let exponent = to_int!(structure.components[2]);
+
+        ber_encode_real(
+            buf,
+            mantissa,
+            base.try_into().expect("base is out of bounds"),
+            exponent,
+        );
+    }
+
+    Ok(())
+}
+
+pub(crate) fn is_real_type(ty: &BuiltinType) -> bool {
     match ty {
         BuiltinType::Structure(structure) => structure
             .components
@@ -313,17 +605,40 @@ fn is_external_type(ty: &BuiltinType) -> bool {
 
 /// Reverse-encodes the value, including its tag.
 /// The resulting bytes are in reverse order.
-/// The bytes of the final output must be reversed to be valid DER.
-/// Regardless of the TransferSyntax provided, the output will always be valid DER.
-/// Since DER is always valid BER and valid CER, this is always acceptable.
+/// The bytes of the final output must be reversed to be valid BER.
+/// BER output uses the same definite-length encodings as DER,
+/// while CER output uses indefinite lengths for constructed encodings
+/// and segments strings longer than 1000 octets (X.690 clause 9).
 pub fn ber_encode_value(
+    kind: BasicEncodingKind,
     mode: EncodeMode,
+    options: &EncodeOptions,
     buf: &mut Vec<u8>,
     context: &Context,
     typed_value: &ResolvedValue,
 ) -> Result<()> {
     let resolved_type = &typed_value.ty;
 
+    let indefinite = kind == BasicEncodingKind::Canonical;
+    let is_constructed = matches!(resolved_type.ty.form(), TypeForm::Constructed)
+        && !is_real_type(&resolved_type.ty);
+    let is_explicit = matches!(
+        resolved_type.tag,
+        Some(Tag {
+            kind: TagKind::Explicit(_),
+            ..
+        })
+    );
+    if indefinite {
+        // the end-of-contents octets are written first, since the encoding is reversed
+        if is_explicit {
+            buf.extend_from_slice(&[0x00, 0x00]);
+        }
+        if is_constructed && resolved_type.tag.is_some() {
+            buf.extend_from_slice(&[0x00, 0x00]);
+        }
+    }
+
     let start_len = buf.len();
     match &typed_value.value {
         BuiltinValue::Boolean(b) => {
@@ -351,34 +666,8 @@ pub fn ber_encode_value(
         }
         BuiltinValue::OctetString(octet_string) => buf.extend(octet_string.iter().rev()),
         BuiltinValue::Null => (),
-        BuiltinValue::ObjectIdentifier(oid) => {
-            let oid = oid.resolve_oid(context)?.0;
-
-            match oid.len() {
-                2.. => {
-                    for node in oid.iter().skip(2).rev() {
-                        write_vlq(*node, buf);
-                    }
-                }
-                _ => {
-                    return Err(Error {
-                        kind: ErrorKind::Ast(
-                            "illegal OBJECT IDENTIFIER with less than two nodes".to_string(),
-                        ),
-                        loc: Loc::default(), // TODO: add loc info to the entire oid, not just its elements
-                    });
-                }
-            }
-
-            let prefix = oid[0] * 40 + oid[1];
-            write_vlq(prefix, buf);
-        }
-        BuiltinValue::RelativeOid(oid) => {
-            let oid = oid.resolve_oid(context)?.0;
-            for node in oid.iter().rev() {
-                write_vlq(*node, buf);
-            }
-        }
+        BuiltinValue::ObjectIdentifier(oid) => ber_encode_object_identifier(buf, context, oid)?,
+        BuiltinValue::RelativeOid(oid) => ber_encode_relative_oid(buf, context, oid)?,
         BuiltinValue::RealLiteral(lit) => {
             ber_encode_real(buf, lit.mantissa.clone(), 10, lit.exponent.clone());
         }
@@ -401,69 +690,36 @@ pub fn ber_encode_value(
         BuiltinValue::Time(time) => {
             buf.extend(time.to_ber_string().into_bytes().into_iter().rev());
         }
-        BuiltinValue::Structure(_, structure) => {
+        BuiltinValue::Structure(tag_type, structure) => {
             if is_real_type(&typed_value.ty.ty) {
-                let special = structure
-                    .components
-                    .iter()
-                    .find(|component| component.name.element == "artasn-special");
-                if let Some(special) = special {
-                    match &special.value.resolve(context)?.value {
-                        BuiltinValue::Integer(int) => {
-                            let int: u32 = int.try_into().expect("SpecialReal is out of bounds");
-                            if int == 0 {
-                                // PLUS-INFINITY
-                                buf.push(0x40);
-                            } else if int == 1 {
-                                // MINUS-INFINITY
-                                buf.push(0x41);
-                            } else if int == 2 {
-                                // NOT-A-NUMBER
-                                buf.push(0x42);
-                            } else {
-                                unreachable!();
-                            }
-                        }
-                        _ => unreachable!(),
-                    }
-                } else {
-                    macro_rules! to_int {
-                        ( $component:expr) => {{
-                            let component = &$component;
-                            match &component.value.resolve(context)?.value {
-                                BuiltinValue::Integer(int) => int.clone(),
-                                _ => unreachable!(),
-                            }
-                        }};
-                    }
-
-                    let mantissa = to_int!(structure.components[0]);
-                    let base = to_int!(structure.components[1]);
-                    let exponent = to_int!(structure.components[2]);
-
-                    ber_encode_real(
-                        buf,
-                        mantissa,
-                        base.try_into().expect("base is out of bounds"),
-                        exponent,
-                    );
-                }
+                ber_encode_real_structure(buf, context, structure)?;
             } else if is_external_type(&typed_value.ty.ty) {
-                ber_encode_external(mode, buf, context, &structure.components)?
+                ber_encode_external(kind, mode, options, buf, context, &structure.components)?
             } else {
-                ber_encode_structure(mode, buf, context, &structure.components)?;
+                ber_encode_structure(
+                    kind,
+                    mode,
+                    options,
+                    buf,
+                    context,
+                    *tag_type,
+                    &structure.components,
+                )?;
             }
         }
-        BuiltinValue::StructureOf(_, structure) => {
-            // TODO: sort elements in content order if SET OF
-            for element in structure.iter().rev() {
-                let resolved = element.resolve(context)?;
-                ber_encode_value(mode, buf, context, &resolved)?;
+        BuiltinValue::StructureOf(tag_type, structure) => {
+            if *tag_type == TagType::Set && kind != BasicEncodingKind::Basic {
+                ber_encode_set_of(kind, mode, options, buf, context, structure)?;
+            } else {
+                for element in structure.iter().rev() {
+                    let resolved = element.resolve(context)?;
+                    ber_encode_value(kind, mode, options, buf, context, &resolved)?;
+                }
             }
         }
         BuiltinValue::Choice(choice) => {
             let value = choice.value.resolve(context)?;
-            ber_encode_value(mode, buf, context, &value)?;
+            ber_encode_value(kind, mode, options, buf, context, &value)?;
         }
         BuiltinValue::CharacterString(tag_type, str) => {
             ber_encode_character_string(buf, *tag_type, str);
@@ -487,57 +743,15 @@ pub fn ber_encode_value(
             buf.extend(duration.to_ber_string().into_bytes().into_iter().rev());
         }
         BuiltinValue::Containing(containing) => {
-            let constraint = resolved_type
-                .constraint
-                .as_ref()
-                .map(|constraint| constraint.get_contents_constraint())
-                .expect("CONTAINING value for type without constraint")
-                .expect("CONTAINING value for type without contents constraint");
-
-            let ts = match &constraint.encoded_by {
-                Some(encoded_by) => {
-                    let resolved = encoded_by.resolve(context)?;
-                    match &resolved.value {
-                        BuiltinValue::ObjectIdentifier(oid) => {
-                            let oid = oid.resolve_oid(context)?;
-                            match TransferSyntax::get_by_oid(&oid) {
-                                    Some(ts) => {
-                                        if ts.get_codec().encoder.is_some() {
-                                            ts
-                                        } else {
-                                            return Err(Error {
-                                                kind: ErrorKind::Ast(format!("encoding with the {} transfer syntax is not yet implemented", ts.get_name())),
-                                                loc: encoded_by.loc,
-                                            })
-                                        }
-                                    },
-                                    None => {
-                                        return Err(Error {
-                                            kind: ErrorKind::Ast(format!("the provided OBJECT IDENTIFIER ({}) does not represent a registered transfer syntax", oid)),
-                                            loc: encoded_by.loc,
-                                        })
-                                    }
-                                }
-                        }
-                        other => {
-                            return Err(Error {
-                                kind: ErrorKind::Ast(format!(
-                                    "expecting OBJECT IDENTIFIER for the transfer  found {}",
-                                    other.tag_type(context)?
-                                )),
-                                loc: encoded_by.loc,
-                            })
-                        }
-                    }
-                }
-                None => &TransferSyntax::Basic(BasicEncodingKind::Distinguished),
-            };
+            let ts = get_contents_transfer_syntax(context, resolved_type)?
+                .unwrap_or(&TransferSyntax::Basic(BasicEncodingKind::Distinguished));
 
             let contained_value = containing.value.resolve(context)?;
             let encoder = ts.get_codec().encoder.unwrap();
             encoder(
                 ts,
                 EncodeMode::ContentsConstraint,
+                options,
                 buf,
                 context,
                 &contained_value,
@@ -552,6 +766,21 @@ pub fn ber_encode_value(
         }
     }
 
+    let is_segmented = indefinite
+        && match &resolved_type.ty {
+            BuiltinType::BitString(_) => cer_segment_string(buf, start_len, true),
+            BuiltinType::OctetString | BuiltinType::CharacterString(_) => {
+                cer_segment_string(buf, start_len, false)
+            }
+            _ => false,
+        };
+    let inner_form_override = if is_segmented {
+        Some(TypeForm::Constructed)
+    } else {
+        None
+    };
+    let inner_indefinite = indefinite && (is_constructed || is_segmented);
+
     let tag = resolved_type.tag.as_ref();
     if let Some(tag) = tag {
         let end_len = buf.len();
@@ -561,7 +790,11 @@ pub fn ber_encode_value(
                 .tag_type()
                 .map(|tag_type| (Class::Universal, tag_type as u16)))
             {
-                write_tlv_len((end_len - start_len) as u64, buf);
+                if inner_indefinite {
+                    buf.push(0x80);
+                } else {
+                    write_tlv_len((end_len - start_len) as u64, buf);
+                }
                 ber_encode_tag(
                     buf,
                     &Tag {
@@ -574,18 +807,24 @@ pub fn ber_encode_value(
                         is_outer_explicit: false,
                         ty: &resolved_type.ty,
                     },
-                    None,
+                    inner_form_override.clone(),
                 );
             }
         }
 
         let end_len = buf.len();
-        write_tlv_len((end_len - start_len) as u64, buf);
+        if (is_explicit && indefinite) || inner_indefinite {
+            buf.push(0x80);
+        } else {
+            write_tlv_len((end_len - start_len) as u64, buf);
+        }
 
         let form_override = if is_real_type(&typed_value.ty.ty) {
             Some(TypeForm::Primitive)
-        } else {
+        } else if is_explicit {
             None
+        } else {
+            inner_form_override
         };
         ber_encode_tag(
             buf,
@@ -609,6 +848,13 @@ pub fn ber_encode_value(
 #[cfg(test)]
 mod test {
     use super::write_vlq;
+    use crate::{
+        compiler::{
+            test::{compile_module, encode_declared_value, json_test},
+            Context,
+        },
+        encoding::*,
+    };
 
     fn val_to_vlq(val: u64) -> Vec<u8> {
         let mut buf = Vec::with_capacity(10);
@@ -630,4 +876,86 @@ mod test {
             vec![0x81, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f]
         );
     }
+
+    #[test]
+    fn test_verify_set_ordering() {
+        let mut context = Context::new();
+        compile_module(
+            &mut context,
+            "SetOrderingTest.asn",
+            include_str!("../../../test-data/encode/SetOrderingTest.asn"),
+        );
+
+        let options = EncodeOptions {
+            set_ordering: SetOrdering::Verify,
+            ..Default::default()
+        };
+        for (name, ordered) in [
+            ("unorderedSet", false),
+            ("integers", false),
+            ("strings", false),
+            ("orderedIntegers", true),
+        ] {
+            let result = encode_declared_value(
+                &context,
+                "SetOrderingTest",
+                name,
+                &TransferSyntax::Basic(BasicEncodingKind::Distinguished),
+                &options,
+            );
+            assert_eq!(result.is_ok(), ordered, "value = {}", name);
+        }
+    }
+
+    #[test]
+    fn test_encode_default_values() {
+        let mut context = Context::new();
+        compile_module(
+            &mut context,
+            "DefaultValueTest.asn",
+            include_str!("../../../test-data/encode/DefaultValueTest.asn"),
+        );
+
+        let options = EncodeOptions {
+            encode_default_values: true,
+            ..Default::default()
+        };
+        for (name, kind, expected) in [
+            (
+                "explicitDefault",
+                BasicEncodingKind::Basic,
+                "300C0603551D1301010004023000",
+            ),
+            (
+                "omittedDefault",
+                BasicEncodingKind::Basic,
+                "300C0603551D1301010004023000",
+            ),
+            (
+                "explicitNested",
+                BasicEncodingKind::Basic,
+                "30083006020101020102",
+            ),
+            (
+                "omittedDefault",
+                BasicEncodingKind::Distinguished,
+                "30090603551D1304023000",
+            ),
+        ] {
+            let encoded = encode_declared_value(
+                &context,
+                "DefaultValueTest",
+                name,
+                &TransferSyntax::Basic(kind),
+                &options,
+            )
+            .expect("encode");
+            assert_eq!(hex::encode_upper(encoded), expected, "value = {}", name);
+        }
+    }
+
+    json_test!(
+        test_cer_encode,
+        "../../../test-data/encode/cer/CerEncodeTest"
+    );
 }
@@ -2,6 +2,7 @@ mod encode;
 pub use encode::*;
 
 mod reader;
+mod validate;
 
 mod decode;
 pub use decode::*;
@@ -28,9 +28,19 @@ pub(crate) fn read_vlq(buf: &[u8]) -> io::Result<(u64, usize)> {
     ))
 }
 
-fn read_tlv_len(buf: &[u8]) -> io::Result<(u64, usize)> {
+/// Reads the length octets of a TLV.
+/// The returned length is `None` if the length is in the indefinite form (X.690 clause 8.1.3.6).
+fn read_tlv_len(buf: &[u8]) -> io::Result<(Option<u64>, usize)> {
     if buf[0] < 0x80 {
-        Ok((buf[0] as u64, 1))
+        Ok((Some(buf[0] as u64), 1))
+    } else if buf[0] == 0x80 {
+        Ok((None, 1))
+    } else if buf[0] == 0xff {
+        // X.690 clause 8.1.3.5(c)
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "TLV length uses the reserved value 0xFF",
+        ))
     } else {
         let be_bytes_len = buf[0] & 0x7f;
         if buf.len() < 1 + be_bytes_len as usize {
@@ -39,48 +49,61 @@ fn read_tlv_len(buf: &[u8]) -> io::Result<(u64, usize)> {
                 "TLV length ended early",
             ));
         }
+
+        let be_bytes = &buf[1..1 + be_bytes_len as usize];
+
+        // BER permits lengths to be encoded with more octets than necessary,
+        // so leading zeros are skipped before checking if the length fits in a u64
+        let be_bytes = match be_bytes.iter().position(|b| *b != 0x00) {
+            Some(index) => &be_bytes[index..],
+            None => &[],
+        };
+
         const BUF_SIZE: usize = mem::size_of::<u64>();
-        if be_bytes_len as usize > BUF_SIZE {
+        if be_bytes.len() > BUF_SIZE {
             return Err(io::Error::new(
-                io::ErrorKind::UnexpectedEof,
+                io::ErrorKind::InvalidData,
                 "TLV length is longer than 8 bytes",
             ));
         }
 
-        let be_bytes = &buf[1..1 + be_bytes_len as usize];
-
         let mut be_buf = [0u8; BUF_SIZE];
         be_buf[BUF_SIZE - be_bytes.len()..].copy_from_slice(be_bytes);
 
-        Ok((u64::from_be_bytes(be_buf), 1 + be_bytes_len as usize))
+        Ok((Some(u64::from_be_bytes(be_buf)), 1 + be_bytes_len as usize))
     }
 }
 
+struct TlvHeader {
+    tag: TlvTag,
+    tag_end: usize,
+    len: Option<u64>,
+    len_end: usize,
+}
+
 pub struct DerReader<'a> {
     source: &'a [u8],
     source_start: usize,
     offset: usize,
+    kind: BasicEncodingKind,
 }
 
 impl<'a> DerReader<'a> {
-    pub fn new(source: &'a [u8], source_start: usize) -> DerReader<'a> {
+    pub fn new(source: &'a [u8], source_start: usize, kind: BasicEncodingKind) -> DerReader<'a> {
         DerReader {
             source,
             source_start,
             offset: 0,
+            kind,
         }
     }
 
-    pub fn read_next(&mut self) -> io::Result<Option<Tlv<'a>>> {
+    fn read_header(&self, tag_start: usize) -> io::Result<TlvHeader> {
         const TAG_MASK: u8 = 0b11111;
 
-        if self.offset == self.source.len() {
-            return Ok(None);
-        }
-
-        let tag_start = self.offset;
-        let tag_prefix = self.source[self.offset];
-        self.offset += 1;
+        let mut offset = tag_start;
+        let tag_prefix = self.source[offset];
+        offset += 1;
 
         let class = match (tag_prefix >> 6) & 0b11 {
             0b00 => Class::Universal,
@@ -96,50 +119,151 @@ impl<'a> DerReader<'a> {
         };
         let num = tag_prefix & TAG_MASK;
         let num = if num & TAG_MASK == TAG_MASK {
-            if self.offset >= self.source.len() {
+            if offset >= self.source.len() {
                 return Err(io::Error::new(
                     io::ErrorKind::InvalidData,
                     "TLV malformed: EOF before large tag",
                 ));
             }
-            let (num, len) = read_vlq(&self.source[self.offset..])?;
-            self.offset += len;
+            let (num, len) = read_vlq(&self.source[offset..])?;
+            offset += len;
             num as u16
         } else {
             num as u16
         };
 
-        if self.offset >= self.source.len() {
+        if offset >= self.source.len() {
             return Err(io::Error::new(
                 io::ErrorKind::InvalidData,
                 "TLV malformed: EOF before length",
             ));
         }
 
-        let tag_end = self.offset;
-        let len_start = self.offset;
-        let (value_len, len) = read_tlv_len(&self.source[self.offset..])?;
-        self.offset += len;
-        let len_end = self.offset;
+        let tag_end = offset;
+        let (len, len_len) = read_tlv_len(&self.source[offset..])?;
+        offset += len_len;
 
-        if self.offset + value_len as usize > self.source.len() {
-            return Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                format!(
-                    "TLV value larger than buffer size (tag at offset {})",
-                    tag_start + self.source_start
-                ),
-            ));
+        if len.is_none() {
+            if self.kind == BasicEncodingKind::Distinguished {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "TLV uses the indefinite length form, which is not permitted in DER (tag at offset {})",
+                        tag_start + self.source_start
+                    ),
+                ));
+            }
+            if let TypeForm::Primitive = form {
+                // X.690 clause 8.1.3.2(a)
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "TLV is primitive but uses the indefinite length form (tag at offset {})",
+                        tag_start + self.source_start
+                    ),
+                ));
+            }
+        }
+
+        Ok(TlvHeader {
+            tag: TlvTag { class, form, num },
+            tag_end,
+            len,
+            len_end: offset,
+        })
+    }
+
+    /// Finds the end of the contents of an indefinite length TLV whose contents start at `contents_start`.
+    /// Returns the offset of the end-of-contents octets that terminate the TLV.
+    fn find_end_of_contents(&self, contents_start: usize) -> io::Result<usize> {
+        let mut offset = contents_start;
+        let mut depth = 1usize;
+        loop {
+            if offset >= self.source.len() {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    format!(
+                        "TLV malformed: EOF before end-of-contents octets (contents at offset {})",
+                        contents_start + self.source_start
+                    ),
+                ));
+            }
+
+            // end-of-contents octets are encoded as a UNIVERSAL 0 primitive with a length of 0
+            if self.source[offset] == 0x00
+                && offset + 1 < self.source.len()
+                && self.source[offset + 1] == 0x00
+            {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(offset);
+                }
+                offset += 2;
+                continue;
+            }
+
+            let header = self.read_header(offset)?;
+            match header.len {
+                Some(len) => {
+                    if header.len_end as u64 + len > self.source.len() as u64 {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!(
+                                "TLV value larger than buffer size (tag at offset {})",
+                                offset + self.source_start
+                            ),
+                        ));
+                    }
+                    offset = header.len_end + len as usize;
+                }
+                None => {
+                    depth += 1;
+                    offset = header.len_end;
+                }
+            }
         }
+    }
+
+    pub fn read_next(&mut self) -> io::Result<Option<Tlv<'a>>> {
+        if self.offset == self.source.len() {
+            return Ok(None);
+        }
+
+        let tag_start = self.offset;
+        let header = self.read_header(tag_start)?;
+        self.offset = header.len_end;
+
+        let tag_end = header.tag_end;
+        let len_start = tag_end;
+        let len_end = header.len_end;
 
         let value_start = self.offset;
-        let value = &self.source[self.offset..self.offset + value_len as usize];
-        self.offset += value.len();
-        let value_end = self.offset;
+        let value_end = match header.len {
+            Some(value_len) => {
+                if self.offset as u64 + value_len > self.source.len() as u64 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "TLV value larger than buffer size (tag at offset {})",
+                            tag_start + self.source_start
+                        ),
+                    ));
+                }
+                self.offset += value_len as usize;
+                self.offset
+            }
+            None => {
+                let value_end = self.find_end_of_contents(value_start)?;
+                // skip the end-of-contents octets
+                self.offset = value_end + 2;
+                value_end
+            }
+        };
+        let value = &self.source[value_start..value_end];
 
         Ok(Some(Tlv {
             tag: TlvElement::new(
-                TlvTag { class, form, num },
+                header.tag,
                 TlvPos::new(tag_start + self.source_start, tag_end + self.source_start),
             ),
             len_pos: TlvPos::new(len_start + self.source_start, len_end + self.source_start),
@@ -167,7 +291,8 @@ impl<'a> Iterator for DerReader<'a> {
 
 #[cfg(test)]
 mod test {
-    use super::read_vlq;
+    use super::{read_tlv_len, read_vlq, DerReader};
+    use crate::encoding::BasicEncodingKind;
 
     fn vlq_to_val(vlq: &[u8]) -> u64 {
         let (val, _) = read_vlq(vlq).unwrap();
@@ -206,4 +331,60 @@ mod test {
             "VLQ data overflowed"
         );
     }
+
+    #[test]
+    fn test_read_tlv_len() {
+        assert_eq!(read_tlv_len(&[0x05]).unwrap(), (Some(5), 1));
+        assert_eq!(read_tlv_len(&[0x80]).unwrap(), (None, 1));
+        assert_eq!(read_tlv_len(&[0x81, 0x80]).unwrap(), (Some(0x80), 2));
+        assert_eq!(read_tlv_len(&[0x82, 0x00, 0x05]).unwrap(), (Some(5), 3));
+        assert_eq!(
+            read_tlv_len(&[0x89, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00]).unwrap(),
+            (Some(0x100), 10)
+        );
+        assert!(read_tlv_len(&[0xff]).is_err());
+        assert!(read_tlv_len(&[0x82, 0x01]).is_err());
+    }
+
+    #[test]
+    fn test_read_indefinite_length() {
+        // SEQUENCE { SEQUENCE { INTEGER 1 }, BOOLEAN TRUE } with indefinite lengths
+        let ber = [
+            0x30, 0x80, 0x30, 0x80, 0x02, 0x01, 0x01, 0x00, 0x00, 0x01, 0x01, 0xff, 0x00, 0x00,
+        ];
+        let mut reader = DerReader::new(&ber, 0, BasicEncodingKind::Basic);
+        let tlv = reader.read_next().unwrap().unwrap();
+        assert_eq!(tlv.value.element, &ber[2..12]);
+        assert_eq!((tlv.len_pos.start, tlv.len_pos.end), (1, 2));
+        assert!(reader.read_next().unwrap().is_none());
+
+        let mut reader = DerReader::new(tlv.value.element, 2, BasicEncodingKind::Basic);
+        let inner = reader.read_next().unwrap().unwrap();
+        assert_eq!(inner.value.element, &ber[4..7]);
+        assert_eq!((inner.value.pos.start, inner.value.pos.end), (4, 7));
+        let boolean = reader.read_next().unwrap().unwrap();
+        assert_eq!(boolean.value.element, &[0xff]);
+        assert!(reader.read_next().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_read_invalid_indefinite_length() {
+        // missing end-of-contents octets
+        let ber = [0x30, 0x80, 0x02, 0x01, 0x01];
+        assert!(DerReader::new(&ber, 0, BasicEncodingKind::Basic)
+            .read_next()
+            .is_err());
+
+        // primitive encodings cannot use the indefinite length form
+        let ber = [0x04, 0x80, 0x01, 0x00, 0x00];
+        assert!(DerReader::new(&ber, 0, BasicEncodingKind::Basic)
+            .read_next()
+            .is_err());
+
+        // DER does not permit the indefinite length form
+        let ber = [0x30, 0x80, 0x00, 0x00];
+        assert!(DerReader::new(&ber, 0, BasicEncodingKind::Distinguished)
+            .read_next()
+            .is_err());
+    }
 }
@@ -0,0 +1,212 @@
+use crate::encoding::*;
+use crate::types::*;
+
+/// Collects the violations of the DER restrictions on BER (X.690 clauses 10 and 11)
+/// while a value is decoded with the relaxed BER rules.
+pub(crate) struct DerValidator<'a> {
+    buf: &'a [u8],
+    pub violations: Vec<DerViolation>,
+}
+
+impl<'a> DerValidator<'a> {
+    pub fn new(buf: &'a [u8]) -> DerValidator<'a> {
+        DerValidator {
+            buf,
+            violations: Vec::new(),
+        }
+    }
+
+    fn report(&mut self, message: impl Into<String>, clause: &'static str, pos: TlvPos) {
+        self.violations.push(DerViolation {
+            message: message.into(),
+            clause,
+            pos,
+        });
+    }
+
+    /// Checks that the identifier and length octets of the TLV are encoded in their minimal form.
+    pub fn check_header(&mut self, tlv: &Tlv<'_>) {
+        let tag_octets = &self.buf[tlv.tag.pos.start..tlv.tag.pos.end];
+        if tag_octets.len() > 1 {
+            if tlv.tag.element.num <= 30 {
+                self.report(
+                    format!(
+                        "tag number {} must be encoded in a single identifier octet",
+                        tlv.tag.element.num
+                    ),
+                    "8.1.2.2",
+                    tlv.tag.pos,
+                );
+            } else if tag_octets[1] == 0x80 {
+                self.report(
+                    "tag number is encoded with a leading zero octet",
+                    "8.1.2.4.2",
+                    tlv.tag.pos,
+                );
+            }
+        }
+
+        let len_octets = &self.buf[tlv.len_pos.start..tlv.len_pos.end];
+        if len_octets[0] == 0x80 {
+            self.report(
+                "length is encoded in the indefinite form",
+                "10.1",
+                tlv.len_pos,
+            );
+        } else if len_octets[0] > 0x80 && (tlv.value.element.len() < 0x80 || len_octets[1] == 0x00)
+        {
+            self.report(
+                "length is not encoded in the minimum number of octets",
+                "10.1",
+                tlv.len_pos,
+            );
+        }
+    }
+
+    /// Reports a string type that is encoded in the constructed form.
+    pub fn check_primitive_string(&mut self, tlv: &Tlv<'_>, tag_type: TagType) {
+        self.report(
+            format!("{} must be encoded in the primitive form", tag_type),
+            "10.2",
+            tlv.tag.pos,
+        );
+    }
+
+    /// Checks the contents octets of a value of a UNIVERSAL type.
+    /// `ty` is the type of the value, if it is known.
+    pub fn check_contents(
+        &mut self,
+        value: &[u8],
+        pos: TlvPos,
+        tag_type: TagType,
+        ty: Option<&BuiltinType>,
+    ) {
+        match tag_type {
+            TagType::Boolean if value.len() == 1 && value[0] != 0x00 && value[0] != 0xff => {
+                self.report(
+                    format!(
+                        "BOOLEAN TRUE must be encoded as 0xFF, but found 0x{:02X}",
+                        value[0]
+                    ),
+                    "11.1",
+                    pos,
+                );
+            }
+            TagType::Integer | TagType::Enumerated
+                if value.len() > 1
+                    && ((value[0] == 0x00 && value[1] & 0x80 == 0)
+                        || (value[0] == 0xff && value[1] & 0x80 != 0)) =>
+            {
+                self.report(
+                    format!(
+                        "{} is not encoded in the minimum number of octets",
+                        tag_type
+                    ),
+                    "8.3.2",
+                    pos,
+                );
+            }
+            TagType::BitString => {
+                if value.len() < 2 {
+                    return;
+                }
+                let unused_bits = value[0];
+                let last = value[value.len() - 1];
+                if unused_bits <= 7 && last & ((1u8 << unused_bits) - 1) != 0 {
+                    self.report(
+                        "BIT STRING has unused bits that are not zero",
+                        "11.2.1",
+                        pos,
+                    );
+                }
+                if let Some(BuiltinType::BitString(BitStringType {
+                    named_bits: Some(_),
+                })) = ty
+                {
+                    if unused_bits <= 7 && last & (1u8 << unused_bits) == 0 {
+                        self.report(
+                            "BIT STRING with named bits has trailing zero bits",
+                            "11.2.2",
+                            pos,
+                        );
+                    }
+                }
+            }
+            TagType::GeneralizedTime => {
+                if let Some(message) = check_generalized_time(value) {
+                    self.report(message, "11.7", pos);
+                }
+            }
+            TagType::UTCTime => {
+                let is_canonical = value.len() == 13
+                    && value[..12].iter().all(u8::is_ascii_digit)
+                    && value[12] == b'Z';
+                if !is_canonical {
+                    self.report(
+                        "UTCTime must be encoded in the form YYMMDDHHMMSSZ",
+                        "11.8",
+                        pos,
+                    );
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Checks that the elements of a SET OF value are in ascending order of their encodings.
+    pub fn check_set_of_ordering(&mut self, elements: &[DecodedValue]) {
+        for window in elements.windows(2) {
+            let lhs = &self.buf[window[0].tag.pos.start..window[0].value_pos.end];
+            let rhs = &self.buf[window[1].tag.pos.start..window[1].value_pos.end];
+            if lhs > rhs {
+                self.report(
+                    "SET OF element is not in ascending order of its encoding",
+                    "11.6",
+                    window[1].tag.pos,
+                );
+            }
+        }
+    }
+
+    /// Checks that the components of a SET value are in canonical order of their tags.
+    pub fn check_set_ordering(&mut self, components: &[DecodedValue]) {
+        for window in components.windows(2) {
+            let lhs = (window[0].tag.element.class, window[0].tag.element.num);
+            let rhs = (window[1].tag.element.class, window[1].tag.element.num);
+            if lhs > rhs {
+                self.report(
+                    "SET component is not in canonical order of its tag",
+                    "10.3",
+                    window[1].tag.pos,
+                );
+            }
+        }
+    }
+}
+
+/// Returns why the GeneralizedTime contents are not in the form YYYYMMDDHHMMSS[.f*]Z,
+/// where the fractional seconds have no trailing zeros.
+fn check_generalized_time(value: &[u8]) -> Option<&'static str> {
+    if value.len() < 15 || !value[..14].iter().all(u8::is_ascii_digit) {
+        return Some("GeneralizedTime must include the seconds");
+    }
+    if value[value.len() - 1] != b'Z' {
+        return Some("GeneralizedTime must be in UTC and terminated by 'Z'");
+    }
+
+    let fraction = &value[14..value.len() - 1];
+    if fraction.is_empty() {
+        return None;
+    }
+    if fraction[0] != b'.' {
+        return Some("GeneralizedTime must use '.' as the decimal separator");
+    }
+    let digits = &fraction[1..];
+    if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) {
+        return Some("GeneralizedTime has malformed fractional seconds");
+    }
+    if digits[digits.len() - 1] == b'0' {
+        return Some("GeneralizedTime fractional seconds must not have trailing zeros");
+    }
+    None
+}
@@ -1,6 +1,6 @@
-use std::io;
+use std::{io, str::FromStr};
 
-use num::{BigInt, BigUint};
+use num::{BigInt, ToPrimitive};
 
 use super::*;
 use crate::{compiler::parser, module::QualifiedIdentifier, types::*, values::*};
@@ -32,7 +32,7 @@ pub enum DecodeMode {
     SpecificType {
         source_ident: Option<QualifiedIdentifier>,
         component_name: Option<String>,
-        resolved: ResolvedType,
+        resolved: Box<ResolvedType>,
     },
 }
 
@@ -60,22 +60,184 @@ impl Display for DecodeError {
 
 pub type DecodeResult<T> = Result<T, DecodeError>;
 
+/// A violation of the DER restrictions on BER found while validating an encoding.
+#[derive(Debug, Clone)]
+pub struct DerViolation {
+    pub message: String,
+    /// The clause of X.690 that the encoding violates.
+    pub clause: &'static str,
+    pub pos: TlvPos,
+}
+
+impl Display for DerViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{} (X.690 clause {}) at bytes {}+{}",
+            self.message,
+            self.clause,
+            self.pos.start,
+            self.pos.end - self.pos.start
+        ))
+    }
+}
+
+/// A decoded REAL value, which keeps the exact mantissa and exponent of its encoding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RealValue {
+    /// The value `mantissa * base^exponent`, where `base` is either 2 or 10.
+    Finite {
+        mantissa: BigInt,
+        base: u32,
+        exponent: BigInt,
+    },
+    PlusInfinity,
+    MinusInfinity,
+    NotANumber,
+    MinusZero,
+}
+
+impl RealValue {
+    /// Returns the nearest `f64` to the value.
+    pub fn to_f64(&self) -> f64 {
+        match self {
+            Self::Finite {
+                mantissa,
+                base: 10,
+                exponent,
+            } => format!("{}e{}", mantissa, exponent)
+                .parse()
+                .unwrap_or(f64::NAN),
+            Self::Finite {
+                mantissa,
+                base,
+                exponent,
+            } => {
+                let mantissa = mantissa.to_f64().unwrap_or(f64::NAN);
+                let exponent = exponent.to_f64().unwrap_or(f64::NAN);
+                mantissa * (*base as f64).powf(exponent)
+            }
+            Self::PlusInfinity => f64::INFINITY,
+            Self::MinusInfinity => f64::NEG_INFINITY,
+            Self::NotANumber => f64::NAN,
+            Self::MinusZero => -0.0,
+        }
+    }
+}
+
+impl FromStr for RealValue {
+    type Err = ();
+
+    /// Parses a decimal number, such as `-1.25`, `1,25E-3` or `125`, without losing precision.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (is_negative, s) = match s.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let (significand, exponent) = match s.split_once(['e', 'E']) {
+            Some((significand, exponent)) => {
+                (significand, exponent.parse::<BigInt>().map_err(|_| ())?)
+            }
+            None => (s, BigInt::ZERO),
+        };
+        let (whole, fraction) = significand
+            .split_once(['.', ','])
+            .unwrap_or((significand, ""));
+        let digits = format!("{}{}", whole, fraction);
+        if digits.is_empty() || !digits.chars().all(|ch| ch.is_ascii_digit()) {
+            return Err(());
+        }
+
+        let mantissa: BigInt = digits.parse().map_err(|_| ())?;
+        if is_negative && mantissa == BigInt::ZERO {
+            return Ok(Self::MinusZero);
+        }
+        Ok(Self::Finite {
+            mantissa: if is_negative { -mantissa } else { mantissa },
+            base: 10,
+            exponent: exponent - fraction.len(),
+        })
+    }
+}
+
+impl Display for RealValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PlusInfinity => f.write_str("PLUS-INFINITY"),
+            Self::MinusInfinity => f.write_str("MINUS-INFINITY"),
+            Self::NotANumber => f.write_str("NOT-A-NUMBER"),
+            _ => f.write_fmt(format_args!("{}", self.to_f64())),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum DecodedValueKind {
     Raw(Vec<u8>),
     Boolean(bool),
     Integer(BigInt),
-    BitString(BigUint),
+    BitString(BitStringValue),
     OctetString(Vec<u8>),
     Null,
     ObjectIdentifier(Oid),
-    Real(f64),
+    RelativeOid(Oid),
+    Real(RealValue),
     Enumerated(i64),
     Time(Time),
     CharacterString(TagType, String),
     UTCTime(UTCTime),
+    GeneralizedTime(GeneralizedTime),
     Date(Date),
     TimeOfDay(TimeOfDay),
     DateTime(DateTime),
     Duration(Duration),
 }
+
+impl Display for DecodedValueKind {
+    /// Formats the value similarly to ASN.1 value notation,
+    /// except that BIT STRING values longer than 64 bits are written in hexadecimal with their number of bits.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Raw(data) | Self::OctetString(data) => {
+                f.write_fmt(format_args!("'{}'H", hex::encode_upper(data)))
+            }
+            Self::Boolean(true) => f.write_str("TRUE"),
+            Self::Boolean(false) => f.write_str("FALSE"),
+            Self::Integer(num) => f.write_fmt(format_args!("{}", num)),
+            Self::BitString(bs) if bs.len() > 64 => f.write_fmt(format_args!(
+                "'{}'H ({} bits)",
+                hex::encode_upper(&bs.data),
+                bs.len()
+            )),
+            Self::BitString(bs) => {
+                f.write_str("'")?;
+                for i in 0..bs.len() as usize {
+                    f.write_str(match bs.data[i / 8] & (0x80 >> (i % 8)) {
+                        0 => "0",
+                        _ => "1",
+                    })?;
+                }
+                f.write_str("'B")
+            }
+            Self::Null => f.write_str("NULL"),
+            Self::ObjectIdentifier(oid) | Self::RelativeOid(oid) => {
+                f.write_fmt(format_args!("{}", oid))
+            }
+            Self::Real(real) => real.fmt(f),
+            Self::Enumerated(num) => f.write_fmt(format_args!("{}", num)),
+            Self::Time(time) => f.write_fmt(format_args!("\"{}\"", time.to_ber_string())),
+            Self::CharacterString(_, str) => f.write_fmt(format_args!("\"{}\"", str)),
+            Self::UTCTime(utc) => f.write_fmt(format_args!("\"{}\"", utc.to_ber_string())),
+            Self::GeneralizedTime(gt) => f.write_fmt(format_args!("\"{}\"", gt.to_ber_string())),
+            Self::Date(date) => f.write_fmt(format_args!("\"{}\"", date.to_ber_string())),
+            Self::TimeOfDay(time_of_day) => {
+                f.write_fmt(format_args!("\"{}\"", time_of_day.to_ber_string()))
+            }
+            Self::DateTime(date_time) => {
+                f.write_fmt(format_args!("\"{}\"", date_time.to_ber_string()))
+            }
+            Self::Duration(duration) => {
+                f.write_fmt(format_args!("\"{}\"", duration.to_ber_string()))
+            }
+        }
+    }
+}
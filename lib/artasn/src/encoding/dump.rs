@@ -0,0 +1,223 @@
+use std::fmt::Write;
+
+use super::*;
+use crate::{compiler::oid_tree, types::TagType, values::Oid};
+
+/// Returns the name of a universal tag, such as `SEQUENCE`, or the tag in ASN.1 notation, such as `[APPLICATION 1]`.
+fn format_tag(tag: &TlvTag) -> String {
+    match tag.class {
+        Class::Universal => match TagType::try_from(tag.num) {
+            Ok(tag_type) => tag_type.to_string(),
+            Err(_) => format!("[UNIVERSAL {}]", tag.num),
+        },
+        Class::Application => format!("[APPLICATION {}]", tag.num),
+        Class::ContextSpecific => format!("[{}]", tag.num),
+        Class::Private => format!("[PRIVATE {}]", tag.num),
+    }
+}
+
+/// Returns the name of the type of the value, or `None` if the value could not be interpreted.
+fn kind_type_name(kind: &DecodedValueKind) -> Option<String> {
+    Some(
+        match kind {
+            DecodedValueKind::Raw(_) => return None,
+            DecodedValueKind::Boolean(_) => TagType::Boolean,
+            DecodedValueKind::Integer(_) => TagType::Integer,
+            DecodedValueKind::BitString(_) => TagType::BitString,
+            DecodedValueKind::OctetString(_) => TagType::OctetString,
+            DecodedValueKind::Null => TagType::Null,
+            DecodedValueKind::ObjectIdentifier(_) => TagType::ObjectIdentifier,
+            DecodedValueKind::RelativeOid(_) => TagType::RelativeOid,
+            DecodedValueKind::Real(_) => TagType::Real,
+            DecodedValueKind::Enumerated(_) => TagType::Enumerated,
+            DecodedValueKind::Time(_) => TagType::Time,
+            DecodedValueKind::CharacterString(tag_type, _) => *tag_type,
+            DecodedValueKind::UTCTime(_) => TagType::UTCTime,
+            DecodedValueKind::GeneralizedTime(_) => TagType::GeneralizedTime,
+            DecodedValueKind::Date(_) => TagType::Date,
+            DecodedValueKind::TimeOfDay(_) => TagType::TimeOfDay,
+            DecodedValueKind::DateTime(_) => TagType::DateTime,
+            DecodedValueKind::Duration(_) => TagType::Duration,
+        }
+        .to_string(),
+    )
+}
+
+/// Returns the OID in ASN.1 value notation, with the names of the nodes that are in the OID tree,
+/// such as `{ iso(1) member-body(2) 840 113549 }`.
+fn format_oid(oid: &Oid) -> String {
+    let mut str = String::from("{");
+    for (node, name) in oid.0.iter().zip(oid_tree::lookup_names(oid)) {
+        match name {
+            Some(name) => write!(str, " {}({})", name, node),
+            None => write!(str, " {}", node),
+        }
+        .expect("write to String");
+    }
+    str.push_str(" }");
+    str
+}
+
+fn dump_value(value: &DecodedValue, depth: usize, dump: &mut String) {
+    let header_len = value.value_pos.start.saturating_sub(value.tag.pos.start);
+    write!(
+        dump,
+        "{:>6} {:>3} {:>6}: {}",
+        value.tag.pos.start,
+        header_len,
+        value.len.element,
+        "  ".repeat(depth)
+    )
+    .expect("write to String");
+
+    if let Some(component_name) = value
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.component_name.as_ref())
+    {
+        dump.push_str(component_name);
+        dump.push(' ');
+    }
+    dump.push_str(&format_tag(&value.tag.element));
+
+    match &value.form {
+        DecodedValueForm::Primitive(kind) => {
+            // the tag does not name the type of values with tags that are not universal
+            if value.tag.element.class != Class::Universal {
+                if let Some(type_name) = kind_type_name(kind) {
+                    dump.push(' ');
+                    dump.push_str(&type_name);
+                }
+            }
+            match kind {
+                // the tag or the type name already reads NULL
+                DecodedValueKind::Null => writeln!(dump),
+                DecodedValueKind::ObjectIdentifier(oid) | DecodedValueKind::RelativeOid(oid) => {
+                    writeln!(dump, " {}", format_oid(oid))
+                }
+                kind => writeln!(dump, " {}", kind),
+            }
+            .expect("write to String");
+        }
+        DecodedValueForm::Constructed(elements) => {
+            dump.push_str(" {\n");
+            for element in elements {
+                dump_value(element, depth + 1, dump);
+            }
+            writeln!(dump, "{:>19}{}}}", ": ", "  ".repeat(depth)).expect("write to String");
+        }
+    }
+}
+
+/// Returns an annotated listing of the decoded values, similar to the output of `dumpasn1` or `openssl asn1parse`.
+/// Each line contains the offset of the tag, the length of the tag and length octets, and the length of the contents,
+/// followed by the component name, the tag and the value, indented by the depth of the value:
+///
+/// ```text
+///      0   2     19: SEQUENCE {
+///      2   2      2:   id [0] INTEGER -300
+///      6   2      2:   name [1] UTF8String "hi"
+///                  : }
+/// ```
+///
+/// The names of OID nodes are looked up in the OID tree, and component names are taken from the metadata of the values,
+/// so values decoded without a type have no component names.
+pub fn dump_decoded_values(values: &[DecodedValue]) -> String {
+    let mut dump = String::new();
+    for value in values {
+        dump_value(value, 0, &mut dump);
+    }
+    dump
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        compiler::{test::compile_module, Context},
+        encoding::*,
+        module::{ModuleIdentifier, QualifiedIdentifier},
+    };
+
+    const MODULE: &str = r#"DumpTest DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+
+Record ::= SEQUENCE {
+    id INTEGER,
+    name UTF8String OPTIONAL,
+    algorithm OBJECT IDENTIFIER,
+    items SEQUENCE OF BOOLEAN
+}
+
+END
+"#;
+
+    const DER: &str = "30188002FED48102686982062A864886F70DA3060101000101FF";
+
+    #[test]
+    fn test_dump_contextless() {
+        let mut context = Context::new();
+        compile_module(&mut context, "DumpTest.asn", MODULE);
+
+        let der = TransferSyntax::Basic(BasicEncodingKind::Distinguished);
+        let decoder = der.get_codec().decoder.expect("decoder");
+        let values = decoder(
+            &der,
+            &DecodeMode::Contextless,
+            &hex::decode(DER).expect("hex"),
+            &context,
+        )
+        .expect("decode");
+        assert_eq!(
+            dump_decoded_values(&values),
+            r#"     0   2     24: SEQUENCE {
+     2   2      2:   [0] 'FED4'H
+     6   2      2:   [1] '6869'H
+    10   2      6:   [2] '2A864886F70D'H
+    18   2      6:   [3] {
+    20   2      1:     BOOLEAN FALSE
+    23   2      1:     BOOLEAN TRUE
+                 :   }
+                 : }
+"#
+        );
+    }
+
+    #[test]
+    fn test_dump_specific_type() {
+        let mut context = Context::new();
+        compile_module(&mut context, "DumpTest.asn", MODULE);
+
+        let ident = QualifiedIdentifier::new(
+            ModuleIdentifier::with_name("DumpTest".to_string()),
+            "Record".to_string(),
+        );
+        let mode = DecodeMode::SpecificType {
+            source_ident: Some(ident.clone()),
+            component_name: None,
+            resolved: Box::new(
+                context
+                    .lookup_type(&ident)
+                    .expect("lookup_type")
+                    .ty
+                    .resolve(&context)
+                    .expect("resolve"),
+            ),
+        };
+        let der = TransferSyntax::Basic(BasicEncodingKind::Distinguished);
+        let decoder = der.get_codec().decoder.expect("decoder");
+        let values =
+            decoder(&der, &mode, &hex::decode(DER).expect("hex"), &context).expect("decode");
+        assert_eq!(
+            dump_decoded_values(&values),
+            r#"     0   2     24: SEQUENCE {
+     2   2      2:   id [0] INTEGER -300
+     6   2      2:   name [1] UTF8String "hi"
+    10   2      6:   algorithm [2] OBJECT IDENTIFIER { iso(1) member-body(2) 840 113549 }
+    18   2      6:   items [3] {
+    20   2      1:     BOOLEAN FALSE
+    23   2      1:     BOOLEAN TRUE
+                 :   }
+                 : }
+"#
+        );
+    }
+}
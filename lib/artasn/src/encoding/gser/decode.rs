@@ -0,0 +1,902 @@
+use std::str::FromStr;
+
+use num::BigInt;
+
+use crate::{
+    compiler::{
+        ast::values::{create_named_bit_string, parse_byte_string},
+        parser::{AstElement, Error, ErrorKind},
+        Context,
+    },
+    encoding::*,
+    module::QualifiedIdentifier,
+    types::*,
+    values::*,
+};
+
+use super::GserDecoder;
+
+fn decoder_error<T>(start: usize, end: usize, message: impl Into<String>) -> DecodeResult<T> {
+    Err(DecodeError::Decoder {
+        message: message.into(),
+        pos: TlvPos::new(start, end),
+    })
+}
+
+fn parser_error(err: crate::compiler::parser::Error) -> DecodeError {
+    DecodeError::Parser(err)
+}
+
+/// Skips the spaces between the parts of a value.
+/// RFC 3641 only allows space characters, but other whitespace is accepted too,
+/// so that values that were formatted over multiple lines can be decoded.
+fn skip_whitespace(decoder: &mut GserDecoder<'_>) {
+    while decoder
+        .buf
+        .get(decoder.pos)
+        .is_some_and(|ch| ch.is_ascii_whitespace())
+    {
+        decoder.pos += 1;
+    }
+}
+
+/// Returns the next character that is not whitespace, without consuming it.
+fn peek(decoder: &mut GserDecoder<'_>) -> Option<u8> {
+    skip_whitespace(decoder);
+    decoder.buf.get(decoder.pos).copied()
+}
+
+fn expect_char(decoder: &mut GserDecoder<'_>, ch: u8) -> DecodeResult<()> {
+    if peek(decoder) != Some(ch) {
+        return decoder_error(
+            decoder.pos,
+            decoder.pos,
+            format!("expecting '{}' in GSER value", ch as char),
+        );
+    }
+    decoder.pos += 1;
+    Ok(())
+}
+
+/// Reads the characters that match the predicate, returning them and their position.
+fn read_while(decoder: &mut GserDecoder<'_>, predicate: impl Fn(u8) -> bool) -> (String, TlvPos) {
+    skip_whitespace(decoder);
+    let start = decoder.pos;
+    while decoder
+        .buf
+        .get(decoder.pos)
+        .is_some_and(|ch| predicate(*ch))
+    {
+        decoder.pos += 1;
+    }
+    let text = String::from_utf8_lossy(&decoder.buf[start..decoder.pos]).to_string();
+    (text, TlvPos::new(start, decoder.pos))
+}
+
+/// Reads an identifier, such as the name of a component, or a keyword such as `TRUE` or `NULL`.
+fn read_identifier(decoder: &mut GserDecoder<'_>) -> DecodeResult<(String, TlvPos)> {
+    let (identifier, pos) = read_while(decoder, |ch| ch.is_ascii_alphanumeric() || ch == b'-');
+    if !identifier.starts_with(|ch: char| ch.is_ascii_alphabetic()) {
+        return decoder_error(pos.start, pos.end, "expecting identifier in GSER value");
+    }
+    Ok((identifier, pos))
+}
+
+/// Reads a number, which may have a sign, a fraction and an exponent.
+fn read_number(decoder: &mut GserDecoder<'_>) -> DecodeResult<(String, TlvPos)> {
+    let (text, pos) = read_while(decoder, |ch| {
+        ch.is_ascii_digit() || matches!(ch, b'-' | b'.' | b'E')
+    });
+    if text.is_empty() {
+        return decoder_error(pos.start, pos.end, "expecting number in GSER value");
+    }
+    Ok((text, pos))
+}
+
+/// Reads a StringValue, returning it without the quotes and with each pair of double quotes replaced by one.
+fn read_string(decoder: &mut GserDecoder<'_>) -> DecodeResult<(String, TlvPos)> {
+    if peek(decoder) != Some(b'"') {
+        return decoder_error(decoder.pos, decoder.pos, "expecting string in GSER value");
+    }
+    let start = decoder.pos;
+    decoder.pos += 1;
+    let mut bytes = Vec::new();
+    loop {
+        match decoder.buf.get(decoder.pos) {
+            Some(b'"') if decoder.buf.get(decoder.pos + 1) == Some(&b'"') => {
+                bytes.push(b'"');
+                decoder.pos += 2;
+            }
+            Some(b'"') => break,
+            Some(ch) => {
+                bytes.push(*ch);
+                decoder.pos += 1;
+            }
+            None => return decoder_error(start, decoder.buf.len(), "string is not closed"),
+        }
+    }
+    decoder.pos += 1;
+    let pos = TlvPos::new(start, decoder.pos);
+    match String::from_utf8(bytes) {
+        Ok(str) => Ok((str, pos)),
+        Err(_) => decoder_error(pos.start, pos.end, "string is not valid UTF-8"),
+    }
+}
+
+/// Reads a bstring or an hstring, such as `'0101'B` or `'0A'H`, returning its octets and its number of unused bits.
+fn read_byte_string(decoder: &mut GserDecoder<'_>) -> DecodeResult<((Vec<u8>, u8), TlvPos)> {
+    if peek(decoder) != Some(b'\'') {
+        return decoder_error(
+            decoder.pos,
+            decoder.pos,
+            "expecting bstring or hstring in GSER value",
+        );
+    }
+    let start = decoder.pos;
+    decoder.pos += 1;
+    let (text, _) = read_while(decoder, |ch| ch != b'\'');
+    if decoder.buf.get(decoder.pos) != Some(&b'\'') {
+        return decoder_error(start, decoder.pos, "bstring or hstring is not closed");
+    }
+    let radix = match decoder.buf.get(decoder.pos + 1) {
+        Some(b'B') => 2,
+        Some(b'H') => 16,
+        _ => {
+            return decoder_error(
+                start,
+                decoder.pos + 1,
+                "bstring or hstring must end with 'B or 'H",
+            )
+        }
+    };
+    decoder.pos += 2;
+    let pos = TlvPos::new(start, decoder.pos);
+    let is_valid = match radix {
+        2 => text.chars().all(|ch| ch == '0' || ch == '1'),
+        _ => text.chars().all(|ch| ch.is_ascii_hexdigit()),
+    };
+    if !is_valid {
+        return decoder_error(
+            pos.start,
+            pos.end,
+            format!("'{}' is not a valid bstring or hstring", text),
+        );
+    }
+    Ok((parse_byte_string(&text.to_ascii_uppercase(), radix), pos))
+}
+
+/// Returns whether the list has another item, after reading the comma before it or the closing brace of the list.
+fn has_list_item(decoder: &mut GserDecoder<'_>, is_first: bool) -> DecodeResult<bool> {
+    if peek(decoder) == Some(b'}') {
+        decoder.pos += 1;
+        return Ok(false);
+    }
+    if !is_first {
+        expect_char(decoder, b',')?;
+    }
+    Ok(true)
+}
+
+/// Skips a value of any type, such as the value of an unknown extension addition.
+fn skip_value(decoder: &mut GserDecoder<'_>) -> DecodeResult<()> {
+    let mut depth = 0usize;
+    loop {
+        match peek(decoder) {
+            Some(b'"') => {
+                read_string(decoder)?;
+            }
+            Some(b'\'') => {
+                read_byte_string(decoder)?;
+            }
+            Some(b'{') => {
+                depth += 1;
+                decoder.pos += 1;
+            }
+            Some(b'}') if depth > 0 => {
+                depth -= 1;
+                decoder.pos += 1;
+            }
+            Some(b',' | b'}') | None if depth == 0 => return Ok(()),
+            Some(_) => decoder.pos += 1,
+            None => return decoder_error(decoder.pos, decoder.pos, "value is not closed"),
+        }
+    }
+}
+
+fn get_integer(context: &Context, value: &AstElement<TypedValue>) -> DecodeResult<BigInt> {
+    match value.resolve(context).map_err(parser_error)?.value {
+        BuiltinValue::Integer(num) => Ok(num),
+        _ => unreachable!("named number value is not an INTEGER"),
+    }
+}
+
+fn find_named_number(
+    context: &Context,
+    named_numbers: Option<&Vec<NamedNumber>>,
+    name: &str,
+) -> DecodeResult<Option<BigInt>> {
+    for named_number in named_numbers.into_iter().flatten() {
+        if named_number.name.element == name {
+            return Ok(Some(get_integer(context, &named_number.value)?));
+        }
+    }
+    Ok(None)
+}
+
+fn parse_number<T: FromStr>(text: &str, pos: TlvPos, type_name: &str) -> DecodeResult<T> {
+    match text.parse() {
+        Ok(num) => Ok(num),
+        Err(_) => decoder_error(
+            pos.start,
+            pos.end,
+            format!("'{}' is not a valid {} value", text, type_name),
+        ),
+    }
+}
+
+fn gser_decode_boolean(decoder: &mut GserDecoder<'_>) -> DecodeResult<bool> {
+    match read_identifier(decoder)? {
+        (identifier, _) if identifier == "TRUE" => Ok(true),
+        (identifier, _) if identifier == "FALSE" => Ok(false),
+        (identifier, pos) => decoder_error(
+            pos.start,
+            pos.end,
+            format!("'{}' is not a valid BOOLEAN value", identifier),
+        ),
+    }
+}
+
+/// Decodes an INTEGER value, which is a number, or the identifier of one of the named numbers of the type.
+fn gser_decode_integer(
+    decoder: &mut GserDecoder<'_>,
+    integer: &IntegerType,
+) -> DecodeResult<BigInt> {
+    if peek(decoder).is_some_and(|ch| ch.is_ascii_alphabetic()) {
+        let (name, pos) = read_identifier(decoder)?;
+        return match find_named_number(decoder.context, integer.named_values.as_ref(), &name)? {
+            Some(num) => Ok(num),
+            None => decoder_error(
+                pos.start,
+                pos.end,
+                format!("INTEGER does not have a named number '{}'", name),
+            ),
+        };
+    }
+
+    let (text, pos) = read_number(decoder)?;
+    let digits = text.strip_prefix('-').unwrap_or(&text);
+    if digits.is_empty() || !digits.chars().all(|ch| ch.is_ascii_digit()) {
+        return decoder_error(
+            pos.start,
+            pos.end,
+            format!("'{}' is not a valid INTEGER value", text),
+        );
+    }
+    parse_number(&text, pos, "INTEGER")
+}
+
+fn gser_decode_enumerated(
+    decoder: &mut GserDecoder<'_>,
+    enumerated: &EnumeratedType,
+) -> DecodeResult<i64> {
+    let (name, pos) = read_identifier(decoder)?;
+    let item = match enumerated
+        .items
+        .iter()
+        .find(|item| item.name.element == name)
+    {
+        Some(item) => item,
+        None => {
+            return decoder_error(
+                pos.start,
+                pos.end,
+                format!("ENUMERATED item '{}' is not defined", name),
+            )
+        }
+    };
+    match &item.value {
+        EnumerationItemValue::Implied(num) => Ok(*num),
+        EnumerationItemValue::Specified(value) => {
+            match i64::try_from(get_integer(decoder.context, value)?) {
+                Ok(num) => Ok(num),
+                Err(_) => decoder_error(
+                    pos.start,
+                    pos.end,
+                    "ENUMERATED value out of bounds for signed 64-bit integer",
+                ),
+            }
+        }
+    }
+}
+
+/// Decodes a REAL value, which is a number, one of the special values such as `PLUS-INFINITY`,
+/// or a SEQUENCE value with the components `mantissa`, `base` and `exponent`.
+fn gser_decode_real(decoder: &mut GserDecoder<'_>) -> DecodeResult<RealValue> {
+    match peek(decoder) {
+        Some(b'{') => {
+            let start = decoder.pos;
+            decoder.pos += 1;
+            let mut components = Vec::with_capacity(3);
+            for name in ["mantissa", "base", "exponent"] {
+                if name != "mantissa" {
+                    expect_char(decoder, b',')?;
+                }
+                let (identifier, pos) = read_identifier(decoder)?;
+                if identifier != name {
+                    return decoder_error(
+                        pos.start,
+                        pos.end,
+                        format!("expecting REAL component '{}'", name),
+                    );
+                }
+                let (text, pos) = read_number(decoder)?;
+                components.push(parse_number::<BigInt>(&text, pos, "REAL")?);
+            }
+            expect_char(decoder, b'}')?;
+            let exponent = components.pop().expect("missing REAL exponent");
+            let base = match u32::try_from(components.pop().expect("missing REAL base")) {
+                Ok(base @ (2 | 10)) => base,
+                _ => return decoder_error(start, decoder.pos, "REAL base must be 2 or 10"),
+            };
+            Ok(RealValue::Finite {
+                mantissa: components.pop().expect("missing REAL mantissa"),
+                base,
+                exponent,
+            })
+        }
+        Some(ch) if ch.is_ascii_alphabetic() => {
+            let (identifier, pos) = read_identifier(decoder)?;
+            match identifier.as_str() {
+                "PLUS-INFINITY" => Ok(RealValue::PlusInfinity),
+                "MINUS-INFINITY" => Ok(RealValue::MinusInfinity),
+                "NOT-A-NUMBER" => Ok(RealValue::NotANumber),
+                _ => decoder_error(
+                    pos.start,
+                    pos.end,
+                    format!("'{}' is not a valid REAL value", identifier),
+                ),
+            }
+        }
+        _ => {
+            let (text, pos) = read_number(decoder)?;
+            parse_number(&text, pos, "REAL")
+        }
+    }
+}
+
+/// Decodes a BIT STRING value, which is a bstring, an hstring, or the list of the names of the bits that are 1.
+fn gser_decode_bit_string(
+    decoder: &mut GserDecoder<'_>,
+    bit_string: &BitStringType,
+) -> DecodeResult<BitStringValue> {
+    if peek(decoder) != Some(b'{') {
+        let ((data, unused_bits), _) = read_byte_string(decoder)?;
+        return Ok(BitStringValue { data, unused_bits });
+    }
+
+    decoder.pos += 1;
+    let mut bit_positions = Vec::new();
+    while has_list_item(decoder, bit_positions.is_empty())? {
+        let (name, pos) = read_identifier(decoder)?;
+        let bit = match find_named_number(decoder.context, bit_string.named_bits.as_ref(), &name)? {
+            Some(bit) => bit,
+            None => {
+                return decoder_error(
+                    pos.start,
+                    pos.end,
+                    format!("BIT STRING does not have a named bit '{}'", name),
+                )
+            }
+        };
+        bit_positions.push(u64::try_from(bit).expect("named bit is out of bounds"));
+    }
+    Ok(create_named_bit_string(bit_positions))
+}
+
+/// Returns the mode of a component, with the type reference of the component type if it has one.
+fn get_component_mode(
+    context: &Context,
+    component_name: Option<String>,
+    component_type: &TaggedType,
+) -> DecodeResult<DecodeMode> {
+    Ok(DecodeMode::SpecificType {
+        source_ident: match &component_type.ty {
+            UntaggedType::BuiltinType(_) => None,
+            UntaggedType::Reference(typeref) => Some(typeref.element.clone()),
+            UntaggedType::ObjectClassField(ocf) => {
+                return Err(DecodeError::Parser(Error {
+                    kind: ErrorKind::Ast(format!(
+                        "decoding a component of the type {} is not yet supported",
+                        ocf
+                    )),
+                    loc: ocf.field.loc,
+                }))
+            }
+        },
+        component_name,
+        resolved: Box::new(component_type.resolve(context).map_err(parser_error)?),
+    })
+}
+
+/// Decodes a SEQUENCE or SET value, which is a list of the components that are present, each preceded by its name.
+/// The components of a SEQUENCE value must be in the order of the type, while the components of a SET value can be in any order.
+fn gser_decode_structure(
+    decoder: &mut GserDecoder<'_>,
+    structure: &Structure,
+) -> DecodeResult<Vec<DecodedValue>> {
+    let context = decoder.context;
+    let start = decoder.pos;
+    expect_char(decoder, b'{')?;
+
+    let mut components = Vec::with_capacity(structure.components.len());
+    let mut is_present = vec![false; structure.components.len()];
+    let mut last_index = None;
+    let mut is_first = true;
+    while has_list_item(decoder, is_first)? {
+        is_first = false;
+        let (name, name_pos) = read_identifier(decoder)?;
+        let index = match structure
+            .components
+            .iter()
+            .position(|component| component.name.element == name)
+        {
+            Some(index) => index,
+            None if structure.extensible => {
+                skip_value(decoder)?;
+                continue;
+            }
+            None => {
+                return decoder_error(
+                    name_pos.start,
+                    name_pos.end,
+                    format!("'{}' is not a component of the type", name),
+                )
+            }
+        };
+        if is_present[index] {
+            return decoder_error(
+                name_pos.start,
+                name_pos.end,
+                format!("component '{}' is present more than once", name),
+            );
+        }
+        if structure.ty == TagType::Sequence && last_index.is_some_and(|last| index < last) {
+            return decoder_error(
+                name_pos.start,
+                name_pos.end,
+                format!("component '{}' is out of order", name),
+            );
+        }
+        is_present[index] = true;
+        last_index = Some(index);
+
+        let component = &structure.components[index];
+        let mode = get_component_mode(
+            context,
+            Some(component.name.element.clone()),
+            &component.component_type,
+        )?;
+        components.push(gser_decode_contents(decoder, &mode, name_pos)?);
+    }
+
+    for (component, is_present) in structure.components.iter().zip(&is_present) {
+        let is_required = !component.optional
+            && component.default_value.is_none()
+            && component.extension_addition.is_none();
+        if is_required && !is_present {
+            return decoder_error(
+                start,
+                decoder.pos,
+                format!("missing component '{}'", component.name.element),
+            );
+        }
+    }
+
+    Ok(components)
+}
+
+/// Decodes a SEQUENCE OF or SET OF value, which is a list of the elements.
+fn gser_decode_structure_of(
+    decoder: &mut GserDecoder<'_>,
+    component_type: &TaggedType,
+) -> DecodeResult<Vec<DecodedValue>> {
+    let mode = get_component_mode(decoder.context, None, component_type)?;
+    expect_char(decoder, b'{')?;
+
+    let mut elements = Vec::new();
+    while has_list_item(decoder, elements.is_empty())? {
+        skip_whitespace(decoder);
+        let pos = TlvPos::new(decoder.pos, decoder.pos);
+        elements.push(gser_decode_contents(decoder, &mode, pos)?);
+    }
+    Ok(elements)
+}
+
+/// Decodes a CHOICE value, which is the name of the alternative and its value, separated by a colon.
+/// Like in BER, the `DecodedValue` of a CHOICE is the value of its alternative, with the metadata of the CHOICE.
+fn gser_decode_choice(
+    decoder: &mut GserDecoder<'_>,
+    choice: &Choice,
+    source_ident: &Option<QualifiedIdentifier>,
+    component_name: &Option<String>,
+) -> DecodeResult<DecodedValue> {
+    let (name, name_pos) = read_identifier(decoder)?;
+    let alternative = match choice
+        .alternatives
+        .iter()
+        .find(|alternative| alternative.name.element == name)
+    {
+        Some(alternative) => alternative,
+        None => {
+            return decoder_error(
+                name_pos.start,
+                name_pos.end,
+                format!("'{}' is not an alternative of the CHOICE", name),
+            )
+        }
+    };
+    expect_char(decoder, b':')?;
+
+    let mode = DecodeMode::SpecificType {
+        source_ident: source_ident.clone(),
+        component_name: component_name.clone(),
+        resolved: Box::new(
+            alternative
+                .alternative_type
+                .resolve(decoder.context)
+                .map_err(parser_error)?,
+        ),
+    };
+    gser_decode_contents(decoder, &mode, TlvPos::new(name_pos.start, decoder.pos))
+}
+
+/// Decodes a value of a type that is not constructed.
+fn gser_decode_primitive(
+    decoder: &mut GserDecoder<'_>,
+    resolved: &ResolvedType,
+) -> DecodeResult<DecodedValueKind> {
+    let start = decoder.pos;
+    Ok(match &resolved.ty {
+        BuiltinType::Boolean => DecodedValueKind::Boolean(gser_decode_boolean(decoder)?),
+        BuiltinType::Integer(integer) => {
+            DecodedValueKind::Integer(gser_decode_integer(decoder, integer)?)
+        }
+        BuiltinType::Enumerated(enumerated) => {
+            DecodedValueKind::Enumerated(gser_decode_enumerated(decoder, enumerated)?)
+        }
+        ty if ber::is_real_type(ty) => DecodedValueKind::Real(gser_decode_real(decoder)?),
+        BuiltinType::BitString(bit_string) => {
+            DecodedValueKind::BitString(gser_decode_bit_string(decoder, bit_string)?)
+        }
+        BuiltinType::OctetString => {
+            let ((bytes, unused_bits), pos) = read_byte_string(decoder)?;
+            if unused_bits != 0 {
+                return decoder_error(
+                    pos.start,
+                    pos.end,
+                    "OCTET STRING value must have a whole number of octets",
+                );
+            }
+            DecodedValueKind::OctetString(bytes)
+        }
+        BuiltinType::Null => match read_identifier(decoder)? {
+            (identifier, _) if identifier == "NULL" => DecodedValueKind::Null,
+            (identifier, pos) => {
+                return decoder_error(
+                    pos.start,
+                    pos.end,
+                    format!("'{}' is not a valid NULL value", identifier),
+                )
+            }
+        },
+        BuiltinType::ObjectIdentifier | BuiltinType::RelativeOid => {
+            let (text, pos) = read_while(decoder, |ch| ch.is_ascii_digit() || ch == b'.');
+            let oid = match Oid::parse_string(&text) {
+                Ok(oid) => oid,
+                Err(_) => {
+                    return decoder_error(
+                        pos.start,
+                        pos.end,
+                        format!("'{}' is not a valid OBJECT IDENTIFIER value", text),
+                    )
+                }
+            };
+            match resolved.ty {
+                BuiltinType::ObjectIdentifier => DecodedValueKind::ObjectIdentifier(oid),
+                _ => DecodedValueKind::RelativeOid(oid),
+            }
+        }
+        BuiltinType::CharacterString(tag_type) => {
+            DecodedValueKind::CharacterString(*tag_type, read_string(decoder)?.0)
+        }
+        ty @ (BuiltinType::UTCTime
+        | BuiltinType::GeneralizedTime
+        | BuiltinType::Date
+        | BuiltinType::TimeOfDay
+        | BuiltinType::DateTime
+        | BuiltinType::Duration
+        | BuiltinType::Time) => {
+            // the strings are the same as the contents octets of BER
+            let (text, _) = read_string(decoder)?;
+            ber::ber_decode_universal(
+                BasicEncodingKind::Basic,
+                text.as_bytes(),
+                ty.tag_type().expect("time type has no tag type"),
+            )
+            .map_err(DecodeError::Io)?
+        }
+        other => {
+            return decoder_error(
+                start,
+                start,
+                format!("GSER decoding is not supported for {}", other),
+            )
+        }
+    })
+}
+
+/// Decodes the GSER value of the type specified by the `DecodeMode`.
+/// The position of the tag of the `DecodedValue` is the position of the name of the component or alternative,
+/// or an empty position before the value if it does not have a name,
+/// and the positions of its value and length are the position of the value and an empty position after it.
+fn gser_decode_contents(
+    decoder: &mut GserDecoder<'_>,
+    mode: &DecodeMode,
+    tag_pos: TlvPos,
+) -> DecodeResult<DecodedValue> {
+    skip_whitespace(decoder);
+    let start = decoder.pos;
+    let (source_ident, component_name, resolved) = match mode {
+        DecodeMode::Contextless => {
+            return decoder_error(start, start, "GSER can only be decoded with a known type")
+        }
+        DecodeMode::SpecificType {
+            source_ident,
+            component_name,
+            resolved,
+        } => (source_ident, component_name, resolved),
+    };
+
+    let form = match &resolved.ty {
+        BuiltinType::Choice(choice) => {
+            return gser_decode_choice(decoder, choice, source_ident, component_name)
+        }
+        BuiltinType::Structure(structure) if !ber::is_real_type(&resolved.ty) => {
+            DecodedValueForm::Constructed(gser_decode_structure(decoder, structure)?)
+        }
+        BuiltinType::StructureOf(of) => {
+            DecodedValueForm::Constructed(gser_decode_structure_of(decoder, &of.component_type)?)
+        }
+        _ => DecodedValueForm::Primitive(gser_decode_primitive(decoder, resolved)?),
+    };
+
+    let tag = resolved.tag.as_ref().expect("non-CHOICE type has no tag");
+    let end = decoder.pos;
+    Ok(DecodedValue {
+        tag: TlvElement::new(
+            TlvTag {
+                class: tag.class,
+                form: match form {
+                    DecodedValueForm::Primitive(_) => TypeForm::Primitive,
+                    DecodedValueForm::Constructed(_) => TypeForm::Constructed,
+                },
+                num: tag.num,
+            },
+            tag_pos,
+        ),
+        len: TlvElement::new((end - start) as u32, TlvPos::new(end, end)),
+        value_pos: TlvPos::new(start, end),
+        form,
+        metadata: Some(DecodedValueMetadata {
+            type_ident: source_ident.clone(),
+            component_name: component_name.clone(),
+        }),
+    })
+}
+
+/// Decodes a GSER value of the type specified by the `DecodeMode`.
+pub fn gser_decode_value(
+    decoder: &mut GserDecoder<'_>,
+    mode: &DecodeMode,
+) -> DecodeResult<DecodedValue> {
+    skip_whitespace(decoder);
+    let pos = TlvPos::new(decoder.pos, decoder.pos);
+    let value = gser_decode_contents(decoder, mode, pos)?;
+    skip_whitespace(decoder);
+    Ok(value)
+}
+
+#[cfg(test)]
+mod test {
+    use num::BigInt;
+
+    use crate::{
+        compiler::{test::compile_module, Context},
+        encoding::*,
+        module::{ModuleIdentifier, QualifiedIdentifier},
+    };
+
+    const MODULE: &str = r#"GserDecodeTest DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+
+Point ::= SEQUENCE {
+    x INTEGER,
+    y INTEGER,
+    label UTF8String OPTIONAL
+}
+
+Pair ::= SET {
+    a BOOLEAN,
+    b INTEGER
+}
+
+Extensible ::= SEQUENCE {
+    a INTEGER,
+    ...
+}
+
+Message ::= CHOICE {
+    data OCTET STRING,
+    flag BOOLEAN
+}
+
+Permissions ::= BIT STRING { read(0), write(1), execute(9) }
+
+Level ::= INTEGER { low(1), high(10) }
+
+Real ::= REAL
+
+Text ::= UTF8String
+
+MESSAGE-ID ::= CLASS { &id INTEGER UNIQUE } WITH SYNTAX { ID &id }
+
+Identified ::= SEQUENCE {
+    id MESSAGE-ID.&id
+}
+
+END
+"#;
+
+    fn decode(context: &Context, type_name: &str, gser: &str) -> DecodeResult<DecodedValue> {
+        let ident = QualifiedIdentifier::new(
+            ModuleIdentifier::with_name("GserDecodeTest".to_string()),
+            type_name.to_string(),
+        );
+        let mode = DecodeMode::SpecificType {
+            source_ident: Some(ident.clone()),
+            component_name: None,
+            resolved: Box::new(
+                context
+                    .lookup_type(&ident)
+                    .expect("lookup_type")
+                    .ty
+                    .resolve(context)
+                    .expect("resolve"),
+            ),
+        };
+
+        let syntax = TransferSyntax::String(StringEncodingKind::Generic);
+        let decoder = syntax.get_codec().decoder.expect("decoder");
+        decoder(&syntax, &mode, gser.as_bytes(), context).map(|mut values| values.remove(0))
+    }
+
+    fn decode_primitive(context: &Context, type_name: &str, gser: &str) -> DecodedValueKind {
+        match decode(context, type_name, gser)
+            .unwrap_or_else(|err| panic!("GSER {} {}: {}", type_name, gser, err))
+            .form
+        {
+            DecodedValueForm::Primitive(kind) => kind,
+            form => panic!("decoded {:?}", form),
+        }
+    }
+
+    #[test]
+    fn test_gser_decode_document() {
+        let mut context = Context::new();
+        compile_module(&mut context, "GserDecodeTest.asn", MODULE);
+
+        for (type_name, gser) in [
+            ("Point", "{\n  x 1,\n  y 2\n}\n"),
+            ("Pair", "{ b 5, a TRUE }"),
+            ("Extensible", "{ a 1, b { c alt:{ \"}\", 'FF'H } } }"),
+            ("Message", "flag:FALSE"),
+            ("Permissions", "{ }"),
+        ] {
+            decode(&context, type_name, gser)
+                .unwrap_or_else(|err| panic!("GSER {} {}: {}", type_name, gser, err));
+        }
+
+        match decode_primitive(&context, "Text", "\"say \"\"hi\"\"\"") {
+            DecodedValueKind::CharacterString(_, str) => assert_eq!(str, "say \"hi\""),
+            kind => panic!("decoded {:?}", kind),
+        }
+        match decode_primitive(&context, "Level", "high") {
+            DecodedValueKind::Integer(num) => assert_eq!(num, 10.into()),
+            kind => panic!("decoded {:?}", kind),
+        }
+        match decode_primitive(&context, "Permissions", "{ read, execute }") {
+            DecodedValueKind::BitString(bs) => {
+                assert_eq!(bs.data, [0x80, 0x40]);
+                assert_eq!(bs.unused_bits, 6);
+            }
+            kind => panic!("decoded {:?}", kind),
+        }
+        match decode_primitive(&context, "Permissions", "'A'H") {
+            DecodedValueKind::BitString(bs) => {
+                assert_eq!(bs.data, [0xa0]);
+                assert_eq!(bs.unused_bits, 4);
+            }
+            kind => panic!("decoded {:?}", kind),
+        }
+        match decode_primitive(&context, "Real", "{ mantissa 3, base 2, exponent -2 }") {
+            DecodedValueKind::Real(real) => assert_eq!(
+                real,
+                RealValue::Finite {
+                    mantissa: BigInt::from(3),
+                    base: 2,
+                    exponent: BigInt::from(-2),
+                }
+            ),
+            kind => panic!("decoded {:?}", kind),
+        }
+    }
+
+    #[test]
+    fn test_gser_decode_invalid() {
+        let mut context = Context::new();
+        compile_module(&mut context, "GserDecodeTest.asn", MODULE);
+
+        for (type_name, gser) in [
+            // missing component
+            ("Point", "{ x 1 }"),
+            // unknown component of a type that is not extensible
+            ("Point", "{ x 1, y 2, z 3 }"),
+            // SEQUENCE components that are out of order
+            ("Point", "{ y 2, x 1 }"),
+            // INTEGER with a fraction
+            ("Point", "{ x 1.5, y 2 }"),
+            // SET component that is present more than once
+            ("Pair", "{ a TRUE, b 1, a TRUE }"),
+            // unknown CHOICE alternative
+            ("Message", "other:NULL"),
+            // OCTET STRING that is not a whole number of octets
+            ("Message", "data:'ABC'H"),
+            // unknown named bit
+            ("Permissions", "{ delete }"),
+            // unknown named number
+            ("Level", "medium"),
+            // unclosed string
+            ("Text", "\"abc"),
+            // trailing data after the value
+            ("Text", "\"a\" \"b\""),
+        ] {
+            assert!(
+                decode(&context, type_name, gser).is_err(),
+                "GSER {} {} is not rejected",
+                type_name,
+                gser
+            );
+        }
+    }
+
+    #[test]
+    fn test_gser_decode_positions() {
+        let mut context = Context::new();
+        compile_module(&mut context, "GserDecodeTest.asn", MODULE);
+
+        let value = decode(&context, "Message", "data:'1234'H").expect("decode");
+        assert_eq!((value.tag.pos.start, value.tag.pos.end), (0, 5));
+        assert_eq!((value.len.pos.start, value.len.pos.end), (12, 12));
+        assert_eq!(value.len.element, 7);
+        assert_eq!((value.value_pos.start, value.value_pos.end), (5, 12));
+    }
+
+    #[test]
+    fn test_gser_decode_object_class_field() {
+        let mut context = Context::new();
+        compile_module(&mut context, "GserDecodeTest.asn", MODULE);
+
+        let err = decode(&context, "Identified", "{ id 5 }")
+            .expect_err("ObjectClassField component is decoded");
+        assert!(matches!(err, DecodeError::Parser(_)), "{}", err);
+    }
+}
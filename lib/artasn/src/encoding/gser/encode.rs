@@ -0,0 +1,249 @@
+use num::BigInt;
+
+use crate::{
+    compiler::{
+        parser::{AstElement, Error, ErrorKind, Result},
+        Context,
+    },
+    encoding::{ber, encode_contained_value, xer, StringEncodingKind, TransferSyntax},
+    types::*,
+    values::*,
+};
+
+use super::GserEncoder;
+
+fn get_integer(context: &Context, value: &AstElement<TypedValue>) -> Result<BigInt> {
+    match value.resolve(context)?.value {
+        BuiltinValue::Integer(num) => Ok(num),
+        other => Err(Error {
+            kind: ErrorKind::Ast(format!(
+                "expecting INTEGER value, but found {}",
+                other.tag_type(context)?
+            )),
+            loc: value.loc,
+        }),
+    }
+}
+
+/// Returns the GSER StringValue of the string, which is enclosed in double quotes,
+/// with each double quote in the string escaped by another double quote (RFC 3641 section 3.2).
+pub(crate) fn format_string(str: &str) -> String {
+    format!("\"{}\"", str.replace('"', "\"\""))
+}
+
+/// Returns the GSER representation of a base 10 REAL value, which always has an exponent unless it is zero.
+fn format_real(mantissa: BigInt, base: u32, exponent: BigInt) -> Result<String> {
+    let real = xer::format_real(mantissa, base, exponent)?;
+    Ok(if real == "0" || real.contains('E') {
+        real
+    } else {
+        format!("{}E0", real)
+    })
+}
+
+/// Returns the GSER representation of a REAL value.
+/// Besides the values defined by RFC 3641, `NOT-A-NUMBER` is written like in ASN.1 value notation.
+fn gser_encode_real(encoder: &GserEncoder<'_>, value: &BuiltinValue) -> Result<String> {
+    let context = encoder.context;
+    Ok(match value {
+        BuiltinValue::Integer(num) => format_real(num.clone(), 10, BigInt::ZERO)?,
+        BuiltinValue::RealLiteral(lit) => {
+            format_real(lit.mantissa.clone(), 10, lit.exponent.clone())?
+        }
+        BuiltinValue::Structure(_, structure) => {
+            let special = structure
+                .components
+                .iter()
+                .find(|component| component.name.element == "artasn-special");
+            match special {
+                Some(special) => {
+                    let special = match special.value.resolve(context)?.value {
+                        BuiltinValue::Enumerated(item) => get_integer(context, &item)?,
+                        _ => unreachable!(),
+                    };
+                    match u32::try_from(special) {
+                        Ok(0) => "PLUS-INFINITY",
+                        Ok(1) => "MINUS-INFINITY",
+                        Ok(2) => "NOT-A-NUMBER",
+                        _ => unreachable!(),
+                    }
+                    .to_string()
+                }
+                None => {
+                    let mantissa = get_integer(context, &structure.components[0].value)?;
+                    let base = get_integer(context, &structure.components[1].value)?;
+                    let exponent = get_integer(context, &structure.components[2].value)?;
+                    format_real(
+                        mantissa,
+                        base.try_into().expect("base is out of bounds"),
+                        exponent,
+                    )?
+                }
+            }
+        }
+        _ => unreachable!(),
+    })
+}
+
+/// Returns the GSER representation of a BIT STRING value, which is the list of the names of the bits that are 1
+/// if the type has named bits and each of them has a name, or otherwise a bstring.
+fn gser_encode_bit_string(
+    encoder: &GserEncoder<'_>,
+    resolved_type: &ResolvedType,
+    bs: &BitStringValue,
+) -> Result<String> {
+    if let BuiltinType::BitString(BitStringType {
+        named_bits: Some(named_bits),
+    }) = &resolved_type.ty
+    {
+        if let Some(names) = xer::get_named_bits(encoder.context, named_bits, bs)? {
+            return Ok(format_list(names));
+        }
+    }
+    Ok(format!("'{}'B", xer::format_bit_string(bs, bs.len())))
+}
+
+fn format_oid(oid: &Oid) -> String {
+    oid.0
+        .iter()
+        .map(|node| node.to_string())
+        .collect::<Vec<String>>()
+        .join(".")
+}
+
+/// Returns the items separated by commas and enclosed in braces, or `{ }` if there are no items.
+fn format_list(items: Vec<String>) -> String {
+    if items.is_empty() {
+        "{ }".to_string()
+    } else {
+        format!("{{ {} }}", items.join(", "))
+    }
+}
+
+/// Returns the GSER representation of a value of a type that is not constructed.
+fn gser_encode_primitive(encoder: &GserEncoder<'_>, typed_value: &ResolvedValue) -> Result<String> {
+    let context = encoder.context;
+    if ber::is_real_type(&typed_value.ty.ty) {
+        return gser_encode_real(encoder, &typed_value.value);
+    }
+
+    Ok(match &typed_value.value {
+        BuiltinValue::Boolean(true) => "TRUE".to_string(),
+        BuiltinValue::Boolean(false) => "FALSE".to_string(),
+        BuiltinValue::Integer(num) => num.to_string(),
+        BuiltinValue::BitString(bs) => gser_encode_bit_string(encoder, &typed_value.ty, bs)?,
+        BuiltinValue::OctetString(bytes) => format!("'{}'H", hex::encode_upper(bytes)),
+        BuiltinValue::Null => "NULL".to_string(),
+        BuiltinValue::ObjectIdentifier(oid) | BuiltinValue::RelativeOid(oid) => {
+            format_oid(&oid.resolve_oid(context)?)
+        }
+        BuiltinValue::Enumerated(item) => match &typed_value.ty.ty {
+            BuiltinType::Enumerated(enumerated) => {
+                xer::get_enumerated_identifier(context, enumerated, item)?
+            }
+            _ => unreachable!(),
+        },
+        BuiltinValue::CharacterString(_, str) => format_string(str),
+        BuiltinValue::Time(time) => format_string(&time.to_ber_string()),
+        BuiltinValue::UTCTime(utc) => format_string(&utc.to_ber_string()),
+        BuiltinValue::GeneralizedTime(gt) => format_string(&gt.to_ber_string()),
+        BuiltinValue::Date(date) => format_string(&date.to_value_string()),
+        BuiltinValue::TimeOfDay(time_of_day) => format_string(&time_of_day.to_value_string()),
+        BuiltinValue::DateTime(date_time) => format_string(&date_time.to_value_string()),
+        BuiltinValue::Duration(duration) => format_string(&duration.to_value_string()),
+        BuiltinValue::Containing(containing) => {
+            // without ENCODED BY, the contained value is encoded with GSER,
+            // and its encoding is written like any other value of the BIT STRING or OCTET STRING type
+            let data = encode_contained_value(
+                context,
+                encoder.options,
+                &typed_value.ty,
+                containing,
+                &TransferSyntax::String(StringEncodingKind::Generic),
+            )?;
+            let value = match containing.container_type {
+                TagType::BitString => BuiltinValue::BitString(BitStringValue {
+                    data,
+                    unused_bits: 0,
+                }),
+                _ => BuiltinValue::OctetString(data),
+            };
+            return gser_encode_primitive(
+                encoder,
+                &ResolvedValue {
+                    ty: typed_value.ty.clone(),
+                    value,
+                },
+            );
+        }
+        _ => unreachable!(),
+    })
+}
+
+/// Returns whether the component is omitted from the encoding because its value is equal to its DEFAULT value.
+fn is_omitted_default_value(
+    encoder: &GserEncoder<'_>,
+    component: &StructureValueComponent,
+) -> Result<bool> {
+    if encoder.options.encode_default_values {
+        return Ok(false);
+    }
+    if component.is_default {
+        return Ok(true);
+    }
+    match &component.default_value {
+        Some(default_value) => component.value.try_eq(encoder.context, default_value),
+        None => Ok(false),
+    }
+}
+
+/// Returns the GSER representation of the value.
+fn gser_format_value(encoder: &GserEncoder<'_>, typed_value: &ResolvedValue) -> Result<String> {
+    let context = encoder.context;
+    Ok(match (&typed_value.value, &typed_value.ty.ty) {
+        (BuiltinValue::Structure(_, structure), BuiltinType::Structure(_))
+            if !ber::is_real_type(&typed_value.ty.ty) =>
+        {
+            let mut components = Vec::with_capacity(structure.components.len());
+            for component in &structure.components {
+                if is_omitted_default_value(encoder, component)? {
+                    continue;
+                }
+                components.push(format!(
+                    "{} {}",
+                    component.name.element,
+                    gser_format_value(encoder, &component.value.resolve(context)?)?
+                ));
+            }
+            format_list(components)
+        }
+        (BuiltinValue::StructureOf(_, elements), BuiltinType::StructureOf(_)) => format_list(
+            elements
+                .iter()
+                .map(|element| gser_format_value(encoder, &element.resolve(context)?))
+                .collect::<Result<Vec<_>>>()?,
+        ),
+        (BuiltinValue::Choice(choice), BuiltinType::Choice(_)) => format!(
+            "{}:{}",
+            choice.alternative.element,
+            gser_format_value(encoder, &choice.value.resolve(context)?)?
+        ),
+        _ => gser_encode_primitive(encoder, typed_value)?,
+    })
+}
+
+/// Writes the GSER encoding of the value (RFC 3641), which is similar to ASN.1 value notation.
+/// SEQUENCE and SET values are written as `{ name value, ... }`, SEQUENCE OF and SET OF values as `{ value, ... }`,
+/// and CHOICE values as `alternative:value`.
+pub fn gser_encode_value(encoder: &mut GserEncoder<'_>, typed_value: &ResolvedValue) -> Result<()> {
+    let str = gser_format_value(encoder, typed_value)?;
+    encoder.buf.extend_from_slice(str.as_bytes());
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::compiler::test::json_test;
+
+    json_test!(test_gser, "../../../test-data/encode/gser/GserTest");
+}
@@ -0,0 +1,19 @@
+mod encode;
+pub use encode::*;
+
+mod decode;
+pub use decode::*;
+
+use crate::{compiler::Context, encoding::EncodeOptions};
+
+pub(crate) struct GserEncoder<'a> {
+    pub context: &'a Context,
+    pub options: &'a EncodeOptions,
+    pub buf: &'a mut Vec<u8>,
+}
+
+pub(crate) struct GserDecoder<'a> {
+    pub context: &'a Context,
+    pub buf: &'a [u8],
+    pub pos: usize,
+}
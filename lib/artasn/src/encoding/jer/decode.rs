@@ -0,0 +1,832 @@
+use std::str::FromStr;
+
+use num::BigInt;
+
+use crate::{
+    compiler::{
+        parser::{AstElement, Error, ErrorKind},
+        Context,
+    },
+    encoding::*,
+    module::QualifiedIdentifier,
+    types::*,
+    values::*,
+};
+
+use super::JerDecoder;
+
+fn decoder_error<T>(start: usize, end: usize, message: impl Into<String>) -> DecodeResult<T> {
+    Err(DecodeError::Decoder {
+        message: message.into(),
+        pos: TlvPos::new(start, end),
+    })
+}
+
+fn parser_error(err: crate::compiler::parser::Error) -> DecodeError {
+    DecodeError::Parser(err)
+}
+
+fn skip_whitespace(decoder: &mut JerDecoder<'_>) {
+    while decoder
+        .buf
+        .get(decoder.pos)
+        .is_some_and(|ch| matches!(ch, b' ' | b'\t' | b'\n' | b'\r'))
+    {
+        decoder.pos += 1;
+    }
+}
+
+/// Returns the next character that is not whitespace, without consuming it.
+fn peek(decoder: &mut JerDecoder<'_>) -> Option<u8> {
+    skip_whitespace(decoder);
+    decoder.buf.get(decoder.pos).copied()
+}
+
+fn expect_char(decoder: &mut JerDecoder<'_>, ch: u8) -> DecodeResult<()> {
+    if peek(decoder) != Some(ch) {
+        return decoder_error(
+            decoder.pos,
+            decoder.pos,
+            format!("expecting '{}' in JSON text", ch as char),
+        );
+    }
+    decoder.pos += 1;
+    Ok(())
+}
+
+/// Reads a JSON string, returning its unescaped value and the position of the string, including its quotes.
+fn read_string(decoder: &mut JerDecoder<'_>) -> DecodeResult<(String, TlvPos)> {
+    if peek(decoder) != Some(b'"') {
+        return decoder_error(decoder.pos, decoder.pos, "expecting JSON string");
+    }
+    let start = decoder.pos;
+    decoder.pos += 1;
+    loop {
+        match decoder.buf.get(decoder.pos) {
+            Some(b'"') => break,
+            Some(b'\\') => decoder.pos += 2,
+            Some(_) => decoder.pos += 1,
+            None => return decoder_error(start, decoder.buf.len(), "JSON string is not closed"),
+        }
+    }
+    decoder.pos += 1;
+    let pos = TlvPos::new(start, decoder.pos);
+    match serde_json::from_slice(&decoder.buf[start..decoder.pos]) {
+        Ok(str) => Ok((str, pos)),
+        Err(err) => decoder_error(pos.start, pos.end, format!("invalid JSON string: {}", err)),
+    }
+}
+
+/// Reads a JSON number, returning its text.
+fn read_number(decoder: &mut JerDecoder<'_>) -> DecodeResult<(String, TlvPos)> {
+    skip_whitespace(decoder);
+    let start = decoder.pos;
+    while decoder
+        .buf
+        .get(decoder.pos)
+        .is_some_and(|ch| ch.is_ascii_digit() || matches!(ch, b'-' | b'+' | b'.' | b'e' | b'E'))
+    {
+        decoder.pos += 1;
+    }
+    if start == decoder.pos {
+        return decoder_error(start, start, "expecting JSON number");
+    }
+    let text = String::from_utf8_lossy(&decoder.buf[start..decoder.pos]).to_string();
+    Ok((text, TlvPos::new(start, decoder.pos)))
+}
+
+/// Reads one of the JSON literals `true`, `false` and `null`.
+fn read_literal(decoder: &mut JerDecoder<'_>) -> DecodeResult<(&'static str, TlvPos)> {
+    skip_whitespace(decoder);
+    let start = decoder.pos;
+    for literal in ["true", "false", "null"] {
+        if decoder.buf[start..].starts_with(literal.as_bytes()) {
+            decoder.pos += literal.len();
+            return Ok((literal, TlvPos::new(start, decoder.pos)));
+        }
+    }
+    decoder_error(start, start, "expecting JSON literal")
+}
+
+/// Reads the name of the next member of an object, including the colon that follows it,
+/// or returns `None` after reading the end of the object.
+fn read_member_name(
+    decoder: &mut JerDecoder<'_>,
+    is_first: bool,
+) -> DecodeResult<Option<(String, TlvPos)>> {
+    if peek(decoder) == Some(b'}') {
+        decoder.pos += 1;
+        return Ok(None);
+    }
+    if !is_first {
+        expect_char(decoder, b',')?;
+    }
+    let (name, pos) = read_string(decoder)?;
+    expect_char(decoder, b':')?;
+    Ok(Some((name, TlvPos::new(pos.start, decoder.pos))))
+}
+
+/// Returns whether the array has another item, after reading the comma before it or the end of the array.
+fn has_array_item(decoder: &mut JerDecoder<'_>, is_first: bool) -> DecodeResult<bool> {
+    if peek(decoder) == Some(b']') {
+        decoder.pos += 1;
+        return Ok(false);
+    }
+    if !is_first {
+        expect_char(decoder, b',')?;
+    }
+    Ok(true)
+}
+
+/// Skips a JSON value of any kind, such as the value of an unknown extension addition.
+fn skip_value(decoder: &mut JerDecoder<'_>) -> DecodeResult<()> {
+    match peek(decoder) {
+        Some(b'{') => {
+            decoder.pos += 1;
+            let mut is_first = true;
+            while read_member_name(decoder, is_first)?.is_some() {
+                skip_value(decoder)?;
+                is_first = false;
+            }
+        }
+        Some(b'[') => {
+            decoder.pos += 1;
+            let mut is_first = true;
+            while has_array_item(decoder, is_first)? {
+                skip_value(decoder)?;
+                is_first = false;
+            }
+        }
+        Some(b'"') => {
+            read_string(decoder)?;
+        }
+        Some(b't' | b'f' | b'n') => {
+            read_literal(decoder)?;
+        }
+        _ => {
+            read_number(decoder)?;
+        }
+    }
+    Ok(())
+}
+
+fn get_integer(context: &Context, value: &AstElement<TypedValue>) -> DecodeResult<BigInt> {
+    match value.resolve(context).map_err(parser_error)?.value {
+        BuiltinValue::Integer(num) => Ok(num),
+        _ => unreachable!("named number value is not an INTEGER"),
+    }
+}
+
+fn parse_number<T: FromStr>(text: &str, pos: TlvPos, type_name: &str) -> DecodeResult<T> {
+    match text.parse() {
+        Ok(num) => Ok(num),
+        Err(_) => decoder_error(
+            pos.start,
+            pos.end,
+            format!("'{}' is not a valid {} value", text, type_name),
+        ),
+    }
+}
+
+fn jer_decode_boolean(decoder: &mut JerDecoder<'_>) -> DecodeResult<bool> {
+    match read_literal(decoder)? {
+        ("true", _) => Ok(true),
+        ("false", _) => Ok(false),
+        (literal, pos) => decoder_error(
+            pos.start,
+            pos.end,
+            format!("'{}' is not a valid BOOLEAN value", literal),
+        ),
+    }
+}
+
+fn jer_decode_integer(decoder: &mut JerDecoder<'_>) -> DecodeResult<BigInt> {
+    let (text, pos) = read_number(decoder)?;
+    // INTEGER values cannot have a fraction or an exponent
+    let digits = text.strip_prefix('-').unwrap_or(&text);
+    if digits.is_empty() || !digits.chars().all(|ch| ch.is_ascii_digit()) {
+        return decoder_error(
+            pos.start,
+            pos.end,
+            format!("'{}' is not a valid INTEGER value", text),
+        );
+    }
+    parse_number(&text, pos, "INTEGER")
+}
+
+/// Decodes an ENUMERATED value, which is the identifier of the item as a string.
+fn jer_decode_enumerated(
+    decoder: &mut JerDecoder<'_>,
+    enumerated: &EnumeratedType,
+) -> DecodeResult<i64> {
+    let (name, pos) = read_string(decoder)?;
+    let item = match enumerated
+        .items
+        .iter()
+        .find(|item| item.name.element == name)
+    {
+        Some(item) => item,
+        None => {
+            return decoder_error(
+                pos.start,
+                pos.end,
+                format!("ENUMERATED item '{}' is not defined", name),
+            )
+        }
+    };
+    match &item.value {
+        EnumerationItemValue::Implied(num) => Ok(*num),
+        EnumerationItemValue::Specified(value) => {
+            match i64::try_from(get_integer(decoder.context, value)?) {
+                Ok(num) => Ok(num),
+                Err(_) => decoder_error(
+                    pos.start,
+                    pos.end,
+                    "ENUMERATED value out of bounds for signed 64-bit integer",
+                ),
+            }
+        }
+    }
+}
+
+/// Decodes a REAL value, which is a number, or one of the strings `"INF"`, `"-INF"`, `"NaN"`, `"0"` and `"-0"`.
+fn jer_decode_real(decoder: &mut JerDecoder<'_>) -> DecodeResult<RealValue> {
+    if peek(decoder) == Some(b'"') {
+        let (text, pos) = read_string(decoder)?;
+        return match text.as_str() {
+            "INF" => Ok(RealValue::PlusInfinity),
+            "-INF" => Ok(RealValue::MinusInfinity),
+            "NaN" => Ok(RealValue::NotANumber),
+            "0" => Ok(RealValue::Finite {
+                mantissa: BigInt::ZERO,
+                base: 10,
+                exponent: BigInt::ZERO,
+            }),
+            "-0" => Ok(RealValue::MinusZero),
+            _ => decoder_error(
+                pos.start,
+                pos.end,
+                format!("'{}' is not a valid REAL value", text),
+            ),
+        };
+    }
+    let (text, pos) = read_number(decoder)?;
+    parse_number(&text, pos, "REAL")
+}
+
+fn decode_hex(text: &str, pos: TlvPos, type_name: &str) -> DecodeResult<Vec<u8>> {
+    match hex::decode(text) {
+        Ok(bytes) => Ok(bytes),
+        Err(_) => decoder_error(
+            pos.start,
+            pos.end,
+            format!("'{}' is not a valid {} value", text, type_name),
+        ),
+    }
+}
+
+/// Returns the BIT STRING value of the first `len` bits of the hexadecimal string.
+fn parse_bits(text: &str, pos: TlvPos, len: u64) -> DecodeResult<BitStringValue> {
+    let mut data = decode_hex(text, pos, "BIT STRING")?;
+    if (data.len() as u64) != len.div_ceil(8) {
+        return decoder_error(
+            pos.start,
+            pos.end,
+            format!(
+                "BIT STRING of {} bits must have {} octets",
+                len,
+                len.div_ceil(8)
+            ),
+        );
+    }
+    let unused_bits = (data.len() as u64 * 8 - len) as u8;
+    if let Some(last) = data.last_mut() {
+        *last &= 0xff << unused_bits;
+    }
+    Ok(BitStringValue { data, unused_bits })
+}
+
+/// Decodes a BIT STRING value, which is a hexadecimal string if the type has a fixed size,
+/// or otherwise an object with the hexadecimal `value` and the `length` in bits.
+fn jer_decode_bit_string(
+    decoder: &mut JerDecoder<'_>,
+    resolved: &ResolvedType,
+) -> DecodeResult<BitStringValue> {
+    let fixed_size = oer::get_fixed_size(decoder.context, resolved).map_err(parser_error)?;
+    if let Some(fixed_size) = fixed_size {
+        let (text, pos) = read_string(decoder)?;
+        return parse_bits(&text, pos, fixed_size);
+    }
+
+    let start = decoder.pos;
+    expect_char(decoder, b'{')?;
+    let mut value = None;
+    let mut length = None;
+    let mut is_first = true;
+    while let Some((name, name_pos)) = read_member_name(decoder, is_first)? {
+        is_first = false;
+        match name.as_str() {
+            "value" if value.is_none() => value = Some(read_string(decoder)?),
+            "length" if length.is_none() => {
+                let (text, pos) = read_number(decoder)?;
+                length = Some(parse_number::<u64>(&text, pos, "BIT STRING length")?);
+            }
+            _ => {
+                return decoder_error(
+                    name_pos.start,
+                    name_pos.end,
+                    format!("unexpected member '{}' of BIT STRING value", name),
+                )
+            }
+        }
+    }
+    match (value, length) {
+        (Some((text, pos)), Some(length)) => parse_bits(&text, pos, length),
+        _ => decoder_error(
+            start,
+            decoder.pos,
+            "BIT STRING value must have the members 'value' and 'length'",
+        ),
+    }
+}
+
+/// Returns the mode of a component, with the type reference of the component type if it has one.
+fn get_component_mode(
+    context: &Context,
+    component_name: Option<String>,
+    component_type: &TaggedType,
+) -> DecodeResult<DecodeMode> {
+    Ok(DecodeMode::SpecificType {
+        source_ident: match &component_type.ty {
+            UntaggedType::BuiltinType(_) => None,
+            UntaggedType::Reference(typeref) => Some(typeref.element.clone()),
+            UntaggedType::ObjectClassField(ocf) => {
+                return Err(DecodeError::Parser(Error {
+                    kind: ErrorKind::Ast(format!(
+                        "decoding a component of the type {} is not yet supported",
+                        ocf
+                    )),
+                    loc: ocf.field.loc,
+                }))
+            }
+        },
+        component_name,
+        resolved: Box::new(component_type.resolve(context).map_err(parser_error)?),
+    })
+}
+
+/// Decodes a SEQUENCE or SET value, which is an object with a member for each component that is present.
+/// The members can be in any order, and unknown members are ignored if the type is extensible.
+fn jer_decode_structure(
+    decoder: &mut JerDecoder<'_>,
+    structure: &Structure,
+) -> DecodeResult<Vec<DecodedValue>> {
+    let context = decoder.context;
+    let start = decoder.pos;
+    expect_char(decoder, b'{')?;
+
+    let mut components = Vec::with_capacity(structure.components.len());
+    let mut is_present = vec![false; structure.components.len()];
+    let mut is_first = true;
+    while let Some((name, name_pos)) = read_member_name(decoder, is_first)? {
+        is_first = false;
+        let index = match structure
+            .components
+            .iter()
+            .position(|component| component.name.element == name)
+        {
+            Some(index) => index,
+            None if structure.extensible => {
+                skip_value(decoder)?;
+                continue;
+            }
+            None => {
+                return decoder_error(
+                    name_pos.start,
+                    name_pos.end,
+                    format!("'{}' is not a component of the type", name),
+                )
+            }
+        };
+        if is_present[index] {
+            return decoder_error(
+                name_pos.start,
+                name_pos.end,
+                format!("component '{}' is present more than once", name),
+            );
+        }
+        is_present[index] = true;
+
+        let component = &structure.components[index];
+        let mode = get_component_mode(
+            context,
+            Some(component.name.element.clone()),
+            &component.component_type,
+        )?;
+        components.push((index, jer_decode_contents(decoder, &mode, name_pos)?));
+    }
+
+    for (component, is_present) in structure.components.iter().zip(&is_present) {
+        let is_required = !component.optional
+            && component.default_value.is_none()
+            && component.extension_addition.is_none();
+        if is_required && !is_present {
+            return decoder_error(
+                start,
+                decoder.pos,
+                format!("missing component '{}'", component.name.element),
+            );
+        }
+    }
+
+    if structure.ty == TagType::Sequence {
+        components.sort_by_key(|(index, _)| *index);
+    }
+    Ok(components.into_iter().map(|(_, value)| value).collect())
+}
+
+/// Decodes a SEQUENCE OF or SET OF value, which is an array.
+fn jer_decode_structure_of(
+    decoder: &mut JerDecoder<'_>,
+    component_type: &TaggedType,
+) -> DecodeResult<Vec<DecodedValue>> {
+    let mode = get_component_mode(decoder.context, None, component_type)?;
+    expect_char(decoder, b'[')?;
+
+    let mut elements = Vec::new();
+    while has_array_item(decoder, elements.is_empty())? {
+        skip_whitespace(decoder);
+        let pos = TlvPos::new(decoder.pos, decoder.pos);
+        elements.push(jer_decode_contents(decoder, &mode, pos)?);
+    }
+    Ok(elements)
+}
+
+/// Decodes a CHOICE value, which is an object with a single member named after the alternative.
+/// Like in BER, the `DecodedValue` of a CHOICE is the value of its alternative, with the metadata of the CHOICE.
+fn jer_decode_choice(
+    decoder: &mut JerDecoder<'_>,
+    choice: &Choice,
+    source_ident: &Option<QualifiedIdentifier>,
+    component_name: &Option<String>,
+) -> DecodeResult<DecodedValue> {
+    let start = decoder.pos;
+    expect_char(decoder, b'{')?;
+    let (name, name_pos) = match read_member_name(decoder, true)? {
+        Some(member) => member,
+        None => {
+            return decoder_error(
+                start,
+                decoder.pos,
+                "CHOICE value must have a member for its alternative",
+            )
+        }
+    };
+    let alternative = match choice
+        .alternatives
+        .iter()
+        .find(|alternative| alternative.name.element == name)
+    {
+        Some(alternative) => alternative,
+        None => {
+            return decoder_error(
+                name_pos.start,
+                name_pos.end,
+                format!("'{}' is not an alternative of the CHOICE", name),
+            )
+        }
+    };
+
+    let mode = DecodeMode::SpecificType {
+        source_ident: source_ident.clone(),
+        component_name: component_name.clone(),
+        resolved: Box::new(
+            alternative
+                .alternative_type
+                .resolve(decoder.context)
+                .map_err(parser_error)?,
+        ),
+    };
+    let value = jer_decode_contents(decoder, &mode, name_pos)?;
+    if read_member_name(decoder, false)?.is_some() {
+        return decoder_error(start, decoder.pos, "CHOICE value must have a single member");
+    }
+    Ok(value)
+}
+
+/// Decodes a value of a type that is not constructed.
+fn jer_decode_primitive(
+    decoder: &mut JerDecoder<'_>,
+    resolved: &ResolvedType,
+) -> DecodeResult<DecodedValueKind> {
+    let start = decoder.pos;
+    Ok(match &resolved.ty {
+        BuiltinType::Boolean => DecodedValueKind::Boolean(jer_decode_boolean(decoder)?),
+        BuiltinType::Integer(_) => DecodedValueKind::Integer(jer_decode_integer(decoder)?),
+        BuiltinType::Enumerated(enumerated) => {
+            DecodedValueKind::Enumerated(jer_decode_enumerated(decoder, enumerated)?)
+        }
+        ty if ber::is_real_type(ty) => DecodedValueKind::Real(jer_decode_real(decoder)?),
+        BuiltinType::BitString(_) => {
+            DecodedValueKind::BitString(jer_decode_bit_string(decoder, resolved)?)
+        }
+        BuiltinType::OctetString => {
+            let (text, pos) = read_string(decoder)?;
+            DecodedValueKind::OctetString(decode_hex(&text, pos, "OCTET STRING")?)
+        }
+        BuiltinType::Null => match read_literal(decoder)? {
+            ("null", _) => DecodedValueKind::Null,
+            (literal, pos) => {
+                return decoder_error(
+                    pos.start,
+                    pos.end,
+                    format!("'{}' is not a valid NULL value", literal),
+                )
+            }
+        },
+        BuiltinType::ObjectIdentifier | BuiltinType::RelativeOid => {
+            let (text, pos) = read_string(decoder)?;
+            let oid = match Oid::parse_string(&text) {
+                Ok(oid) => oid,
+                Err(_) => {
+                    return decoder_error(
+                        pos.start,
+                        pos.end,
+                        format!("'{}' is not a valid OBJECT IDENTIFIER value", text),
+                    )
+                }
+            };
+            match resolved.ty {
+                BuiltinType::ObjectIdentifier => DecodedValueKind::ObjectIdentifier(oid),
+                _ => DecodedValueKind::RelativeOid(oid),
+            }
+        }
+        BuiltinType::CharacterString(tag_type) => {
+            DecodedValueKind::CharacterString(*tag_type, read_string(decoder)?.0)
+        }
+        ty @ (BuiltinType::UTCTime
+        | BuiltinType::GeneralizedTime
+        | BuiltinType::Date
+        | BuiltinType::TimeOfDay
+        | BuiltinType::DateTime
+        | BuiltinType::Duration
+        | BuiltinType::Time) => {
+            // the strings are the same as the contents octets of BER
+            let (text, _) = read_string(decoder)?;
+            ber::ber_decode_universal(
+                BasicEncodingKind::Basic,
+                text.as_bytes(),
+                ty.tag_type().expect("time type has no tag type"),
+            )
+            .map_err(DecodeError::Io)?
+        }
+        other => {
+            return decoder_error(
+                start,
+                start,
+                format!("JER decoding is not supported for {}", other),
+            )
+        }
+    })
+}
+
+/// Decodes the JSON value of the type specified by the `DecodeMode`.
+/// The position of the tag of the `DecodedValue` is the position of the name of the member that contains the value,
+/// or an empty position before the value if it is not the value of a member,
+/// and the positions of its value and length are the position of the JSON value and an empty position after it.
+fn jer_decode_contents(
+    decoder: &mut JerDecoder<'_>,
+    mode: &DecodeMode,
+    tag_pos: TlvPos,
+) -> DecodeResult<DecodedValue> {
+    skip_whitespace(decoder);
+    let start = decoder.pos;
+    let (source_ident, component_name, resolved) = match mode {
+        DecodeMode::Contextless => {
+            return decoder_error(start, start, "JER can only be decoded with a known type")
+        }
+        DecodeMode::SpecificType {
+            source_ident,
+            component_name,
+            resolved,
+        } => (source_ident, component_name, resolved),
+    };
+
+    let form = match &resolved.ty {
+        BuiltinType::Choice(choice) => {
+            return jer_decode_choice(decoder, choice, source_ident, component_name)
+        }
+        BuiltinType::Structure(structure) if !ber::is_real_type(&resolved.ty) => {
+            DecodedValueForm::Constructed(jer_decode_structure(decoder, structure)?)
+        }
+        BuiltinType::StructureOf(of) => {
+            DecodedValueForm::Constructed(jer_decode_structure_of(decoder, &of.component_type)?)
+        }
+        _ => DecodedValueForm::Primitive(jer_decode_primitive(decoder, resolved)?),
+    };
+
+    let tag = resolved.tag.as_ref().expect("non-CHOICE type has no tag");
+    let end = decoder.pos;
+    Ok(DecodedValue {
+        tag: TlvElement::new(
+            TlvTag {
+                class: tag.class,
+                form: match form {
+                    DecodedValueForm::Primitive(_) => TypeForm::Primitive,
+                    DecodedValueForm::Constructed(_) => TypeForm::Constructed,
+                },
+                num: tag.num,
+            },
+            tag_pos,
+        ),
+        len: TlvElement::new((end - start) as u32, TlvPos::new(end, end)),
+        value_pos: TlvPos::new(start, end),
+        form,
+        metadata: Some(DecodedValueMetadata {
+            type_ident: source_ident.clone(),
+            component_name: component_name.clone(),
+        }),
+    })
+}
+
+/// Decodes a JSON text containing a single value of the type specified by the `DecodeMode`.
+pub fn jer_decode_value(
+    decoder: &mut JerDecoder<'_>,
+    mode: &DecodeMode,
+) -> DecodeResult<DecodedValue> {
+    skip_whitespace(decoder);
+    let pos = TlvPos::new(decoder.pos, decoder.pos);
+    let value = jer_decode_contents(decoder, mode, pos)?;
+    skip_whitespace(decoder);
+    Ok(value)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        compiler::{test::compile_module, Context},
+        encoding::*,
+        module::{ModuleIdentifier, QualifiedIdentifier},
+    };
+
+    const MODULE: &str = r#"JerDecodeTest DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+
+Point ::= SEQUENCE {
+    x INTEGER,
+    y INTEGER,
+    label UTF8String OPTIONAL
+}
+
+Pair ::= SET {
+    a BOOLEAN,
+    b INTEGER
+}
+
+Extensible ::= SEQUENCE {
+    a INTEGER,
+    ...
+}
+
+Message ::= CHOICE {
+    data OCTET STRING,
+    flag BOOLEAN
+}
+
+Bits ::= BIT STRING
+
+Text ::= UTF8String
+
+MESSAGE-ID ::= CLASS { &id INTEGER UNIQUE } WITH SYNTAX { ID &id }
+
+Identified ::= SEQUENCE {
+    id MESSAGE-ID.&id
+}
+
+END
+"#;
+
+    fn decode(context: &Context, type_name: &str, json: &str) -> DecodeResult<DecodedValue> {
+        let ident = QualifiedIdentifier::new(
+            ModuleIdentifier::with_name("JerDecodeTest".to_string()),
+            type_name.to_string(),
+        );
+        let mode = DecodeMode::SpecificType {
+            source_ident: Some(ident.clone()),
+            component_name: None,
+            resolved: Box::new(
+                context
+                    .lookup_type(&ident)
+                    .expect("lookup_type")
+                    .ty
+                    .resolve(context)
+                    .expect("resolve"),
+            ),
+        };
+
+        let syntax = TransferSyntax::Json(JsonEncodingKind::Basic);
+        let decoder = syntax.get_codec().decoder.expect("decoder");
+        decoder(&syntax, &mode, json.as_bytes(), context).map(|mut values| values.remove(0))
+    }
+
+    #[test]
+    fn test_jer_decode_document() {
+        let mut context = Context::new();
+        compile_module(&mut context, "JerDecodeTest.asn", MODULE);
+
+        for (type_name, json) in [
+            ("Point", " {\n\t\"y\" : 2 , \"x\":1 }\n"),
+            ("Pair", "{\"b\": 5, \"a\": true}"),
+            ("Extensible", "{\"a\": 1, \"b\": {\"c\": [1, \"}\", null]}}"),
+            ("Message", "{\"flag\": false}"),
+            ("Bits", "{\"length\": 12, \"value\": \"b0f0\"}"),
+            ("Text", "\"a\\\"b\\u00e9\\n\""),
+        ] {
+            decode(&context, type_name, json)
+                .unwrap_or_else(|err| panic!("JER {} {}: {}", type_name, json, err));
+        }
+
+        let value = decode(&context, "Point", "{\"y\": 2, \"x\": 1}").expect("decode");
+        match value.form {
+            DecodedValueForm::Constructed(components) => {
+                let names: Vec<_> = components
+                    .iter()
+                    .map(|component| {
+                        component
+                            .metadata
+                            .as_ref()
+                            .and_then(|metadata| metadata.component_name.clone())
+                            .expect("component name")
+                    })
+                    .collect();
+                assert_eq!(names, ["x", "y"]);
+            }
+            form => panic!("decoded {:?}", form),
+        }
+
+        let value = decode(&context, "Text", "\"a\\\"b\\u00e9\\n\"").expect("decode");
+        match value.form {
+            DecodedValueForm::Primitive(DecodedValueKind::CharacterString(_, str)) => {
+                assert_eq!(str, "a\"b\u{e9}\n")
+            }
+            form => panic!("decoded {:?}", form),
+        }
+    }
+
+    #[test]
+    fn test_jer_decode_invalid() {
+        let mut context = Context::new();
+        compile_module(&mut context, "JerDecodeTest.asn", MODULE);
+
+        for (type_name, json) in [
+            // missing component
+            ("Point", "{\"x\": 1}"),
+            // unknown component of a type that is not extensible
+            ("Point", "{\"x\": 1, \"y\": 2, \"z\": 3}"),
+            // INTEGER with a fraction
+            ("Point", "{\"x\": 1.5, \"y\": 2}"),
+            // SET component that is present more than once
+            ("Pair", "{\"a\": true, \"b\": 1, \"a\": true}"),
+            // unknown CHOICE alternative
+            ("Message", "{\"other\": null}"),
+            // CHOICE with more than one member
+            ("Message", "{\"flag\": true, \"data\": \"00\"}"),
+            // OCTET STRING that is not hexadecimal
+            ("Message", "{\"data\": \"xyz\"}"),
+            // BIT STRING with a length that does not match its value
+            ("Bits", "{\"value\": \"B0\", \"length\": 12}"),
+            // unclosed string
+            ("Text", "\"abc"),
+            // trailing data after the value
+            ("Text", "\"a\" \"b\""),
+        ] {
+            assert!(
+                decode(&context, type_name, json).is_err(),
+                "JER {} {} is not rejected",
+                type_name,
+                json
+            );
+        }
+    }
+
+    #[test]
+    fn test_jer_decode_positions() {
+        let mut context = Context::new();
+        compile_module(&mut context, "JerDecodeTest.asn", MODULE);
+
+        let value = decode(&context, "Message", "{\"data\": \"1234\"}").expect("decode");
+        assert_eq!((value.tag.pos.start, value.tag.pos.end), (1, 8));
+        assert_eq!((value.len.pos.start, value.len.pos.end), (15, 15));
+        assert_eq!(value.len.element, 6);
+        assert_eq!((value.value_pos.start, value.value_pos.end), (9, 15));
+    }
+
+    #[test]
+    fn test_jer_decode_object_class_field() {
+        let mut context = Context::new();
+        compile_module(&mut context, "JerDecodeTest.asn", MODULE);
+
+        let err = decode(&context, "Identified", r#"{"id": 5}"#)
+            .expect_err("ObjectClassField component is decoded");
+        assert!(matches!(err, DecodeError::Parser(_)), "{}", err);
+    }
+}
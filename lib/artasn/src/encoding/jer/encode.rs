@@ -0,0 +1,298 @@
+use num::BigInt;
+
+use crate::{
+    compiler::{
+        parser::{AstElement, Error, ErrorKind, Result},
+        Context,
+    },
+    encoding::{ber, encode_contained_value, oer, xer, JsonEncodingKind, TransferSyntax},
+    types::*,
+    values::*,
+};
+
+use super::JerEncoder;
+
+fn get_integer(context: &Context, value: &AstElement<TypedValue>) -> Result<BigInt> {
+    match value.resolve(context)?.value {
+        BuiltinValue::Integer(num) => Ok(num),
+        other => Err(Error {
+            kind: ErrorKind::Ast(format!(
+                "expecting INTEGER value, but found {}",
+                other.tag_type(context)?
+            )),
+            loc: value.loc,
+        }),
+    }
+}
+
+/// Returns the JSON string literal of the string, with the characters that cannot appear in JSON strings escaped.
+pub(crate) fn format_string(str: &str) -> String {
+    serde_json::to_string(str).expect("failed to serialize JSON string")
+}
+
+/// Returns the JSON representation of a REAL value, which is a number,
+/// or one of the strings `"INF"`, `"-INF"` and `"NaN"` for the special values.
+fn jer_encode_real(encoder: &JerEncoder<'_>, value: &BuiltinValue) -> Result<String> {
+    let context = encoder.context;
+    Ok(match value {
+        BuiltinValue::Integer(num) => xer::format_real(num.clone(), 10, BigInt::ZERO)?,
+        BuiltinValue::RealLiteral(lit) => {
+            xer::format_real(lit.mantissa.clone(), 10, lit.exponent.clone())?
+        }
+        BuiltinValue::Structure(_, structure) => {
+            let special = structure
+                .components
+                .iter()
+                .find(|component| component.name.element == "artasn-special");
+            match special {
+                Some(special) => {
+                    let special = match special.value.resolve(context)?.value {
+                        BuiltinValue::Enumerated(item) => get_integer(context, &item)?,
+                        _ => unreachable!(),
+                    };
+                    let name = match u32::try_from(special) {
+                        Ok(0) => "INF",
+                        Ok(1) => "-INF",
+                        Ok(2) => "NaN",
+                        _ => unreachable!(),
+                    };
+                    format_string(name)
+                }
+                None => {
+                    let mantissa = get_integer(context, &structure.components[0].value)?;
+                    let base = get_integer(context, &structure.components[1].value)?;
+                    let exponent = get_integer(context, &structure.components[2].value)?;
+                    xer::format_real(
+                        mantissa,
+                        base.try_into().expect("base is out of bounds"),
+                        exponent,
+                    )?
+                }
+            }
+        }
+        _ => unreachable!(),
+    })
+}
+
+/// Returns the hexadecimal representation of the first `len` bits of the BIT STRING value,
+/// where the unused bits of the last octet are 0.
+fn format_bits(bs: &BitStringValue, len: u64) -> String {
+    let byte_len = len.div_ceil(8) as usize;
+    let unused_bits = (byte_len as u64 * 8 - len) as u8;
+    let mut bytes = Vec::with_capacity(byte_len);
+    for i in 0..byte_len {
+        let mut byte = bs.data.get(i).copied().unwrap_or(0);
+        if i == byte_len - 1 {
+            byte &= 0xff << unused_bits;
+        }
+        bytes.push(byte);
+    }
+    hex::encode_upper(bytes)
+}
+
+/// Writes a BIT STRING value, which is a hexadecimal string if the type has a fixed size,
+/// or otherwise an object with the hexadecimal `value` and the `length` in bits.
+fn jer_encode_bit_string(
+    encoder: &mut JerEncoder<'_>,
+    resolved_type: &ResolvedType,
+    bs: &BitStringValue,
+) -> Result<()> {
+    match oer::get_fixed_size(encoder.context, resolved_type)? {
+        Some(fixed_size) => write_str(encoder, &format_string(&format_bits(bs, fixed_size))),
+        None => {
+            write_str(encoder, "{");
+            encoder.depth += 1;
+            write_line_start(encoder);
+            write_str(encoder, "\"value\": ");
+            write_str(encoder, &format_string(&format_bits(bs, bs.len())));
+            write_str(encoder, ",");
+            write_line_start(encoder);
+            write_str(encoder, &format!("\"length\": {}", bs.len()));
+            encoder.depth -= 1;
+            write_line_start(encoder);
+            write_str(encoder, "}");
+        }
+    }
+    Ok(())
+}
+
+fn format_oid(oid: &Oid) -> String {
+    oid.0
+        .iter()
+        .map(|node| node.to_string())
+        .collect::<Vec<String>>()
+        .join(".")
+}
+
+/// Returns the JSON representation of a value of a type that is not constructed.
+fn jer_encode_primitive(encoder: &JerEncoder<'_>, typed_value: &ResolvedValue) -> Result<String> {
+    let context = encoder.context;
+    if ber::is_real_type(&typed_value.ty.ty) {
+        return jer_encode_real(encoder, &typed_value.value);
+    }
+
+    Ok(match &typed_value.value {
+        BuiltinValue::Boolean(value) => value.to_string(),
+        BuiltinValue::Integer(num) => num.to_string(),
+        BuiltinValue::OctetString(bytes) => format_string(&hex::encode_upper(bytes)),
+        BuiltinValue::Null => "null".to_string(),
+        BuiltinValue::ObjectIdentifier(oid) | BuiltinValue::RelativeOid(oid) => {
+            format_string(&format_oid(&oid.resolve_oid(context)?))
+        }
+        BuiltinValue::Enumerated(item) => match &typed_value.ty.ty {
+            BuiltinType::Enumerated(enumerated) => {
+                format_string(&xer::get_enumerated_identifier(context, enumerated, item)?)
+            }
+            _ => unreachable!(),
+        },
+        BuiltinValue::CharacterString(_, str) => format_string(str),
+        BuiltinValue::Time(time) => format_string(&time.to_ber_string()),
+        BuiltinValue::UTCTime(utc) => format_string(&utc.to_ber_string()),
+        BuiltinValue::GeneralizedTime(gt) => format_string(&gt.to_ber_string()),
+        BuiltinValue::Date(date) => format_string(&date.to_value_string()),
+        BuiltinValue::TimeOfDay(time_of_day) => format_string(&time_of_day.to_value_string()),
+        BuiltinValue::DateTime(date_time) => format_string(&date_time.to_value_string()),
+        BuiltinValue::Duration(duration) => format_string(&duration.to_value_string()),
+        _ => unreachable!(),
+    })
+}
+
+/// Writes the whitespace that begins a line at the current depth.
+fn write_line_start(encoder: &mut JerEncoder<'_>) {
+    encoder.buf.push(b'\n');
+    for _ in 0..encoder.depth {
+        encoder.buf.extend_from_slice(b"  ");
+    }
+}
+
+fn write_str(encoder: &mut JerEncoder<'_>, str: &str) {
+    encoder.buf.extend_from_slice(str.as_bytes());
+}
+
+/// Returns whether the component is omitted from the encoding because its value is equal to its DEFAULT value.
+fn is_omitted_default_value(
+    encoder: &JerEncoder<'_>,
+    component: &StructureValueComponent,
+) -> Result<bool> {
+    if encoder.options.encode_default_values {
+        return Ok(false);
+    }
+    if component.is_default {
+        return Ok(true);
+    }
+    match &component.default_value {
+        Some(default_value) => component.value.try_eq(encoder.context, default_value),
+        None => Ok(false),
+    }
+}
+
+/// Writes the members of a JSON object, with each member on its own line.
+fn write_object(encoder: &mut JerEncoder<'_>, members: Vec<(&str, ResolvedValue)>) -> Result<()> {
+    if members.is_empty() {
+        write_str(encoder, "{}");
+        return Ok(());
+    }
+
+    write_str(encoder, "{");
+    encoder.depth += 1;
+    for (i, (name, value)) in members.iter().enumerate() {
+        if i > 0 {
+            write_str(encoder, ",");
+        }
+        write_line_start(encoder);
+        write_str(encoder, &format_string(name));
+        write_str(encoder, ": ");
+        jer_encode_value(encoder, value)?;
+    }
+    encoder.depth -= 1;
+    write_line_start(encoder);
+    write_str(encoder, "}");
+    Ok(())
+}
+
+/// Writes the JER encoding of the value (X.697).
+/// SEQUENCE and SET values are objects with a member for each component, SEQUENCE OF and SET OF values are arrays,
+/// and CHOICE values are objects with a single member named after the alternative.
+pub fn jer_encode_value(encoder: &mut JerEncoder<'_>, typed_value: &ResolvedValue) -> Result<()> {
+    let context = encoder.context;
+    match (&typed_value.value, &typed_value.ty.ty) {
+        (BuiltinValue::Structure(_, structure), BuiltinType::Structure(_))
+            if !ber::is_real_type(&typed_value.ty.ty) =>
+        {
+            let mut members = Vec::with_capacity(structure.components.len());
+            for component in &structure.components {
+                if is_omitted_default_value(encoder, component)? {
+                    continue;
+                }
+                members.push((
+                    component.name.element.as_str(),
+                    component.value.resolve(context)?,
+                ));
+            }
+            write_object(encoder, members)?;
+        }
+        (BuiltinValue::StructureOf(_, elements), BuiltinType::StructureOf(_)) => {
+            if elements.is_empty() {
+                write_str(encoder, "[]");
+                return Ok(());
+            }
+
+            write_str(encoder, "[");
+            encoder.depth += 1;
+            for (i, element) in elements.iter().enumerate() {
+                if i > 0 {
+                    write_str(encoder, ",");
+                }
+                write_line_start(encoder);
+                jer_encode_value(encoder, &element.resolve(context)?)?;
+            }
+            encoder.depth -= 1;
+            write_line_start(encoder);
+            write_str(encoder, "]");
+        }
+        (BuiltinValue::Choice(choice), BuiltinType::Choice(_)) => {
+            let value = choice.value.resolve(context)?;
+            write_object(encoder, vec![(choice.alternative.element.as_str(), value)])?;
+        }
+        (BuiltinValue::BitString(bs), _) => {
+            jer_encode_bit_string(encoder, &typed_value.ty, bs)?;
+        }
+        (BuiltinValue::Containing(containing), _) => {
+            // without ENCODED BY, the contained value is encoded with JER,
+            // and its encoding is written like any other value of the BIT STRING or OCTET STRING type
+            let data = encode_contained_value(
+                context,
+                encoder.options,
+                &typed_value.ty,
+                containing,
+                &TransferSyntax::Json(JsonEncodingKind::Basic),
+            )?;
+            let value = match containing.container_type {
+                TagType::BitString => BuiltinValue::BitString(BitStringValue {
+                    data,
+                    unused_bits: 0,
+                }),
+                _ => BuiltinValue::OctetString(data),
+            };
+            jer_encode_value(
+                encoder,
+                &ResolvedValue {
+                    ty: typed_value.ty.clone(),
+                    value,
+                },
+            )?;
+        }
+        _ => {
+            let json = jer_encode_primitive(encoder, typed_value)?;
+            write_str(encoder, &json);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::compiler::test::json_test;
+
+    json_test!(test_jer, "../../../test-data/encode/jer/JerTest");
+}
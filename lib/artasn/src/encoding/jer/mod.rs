@@ -0,0 +1,21 @@
+mod encode;
+pub use encode::*;
+
+mod decode;
+pub use decode::*;
+
+use crate::{compiler::Context, encoding::EncodeOptions};
+
+pub(crate) struct JerEncoder<'a> {
+    pub context: &'a Context,
+    pub options: &'a EncodeOptions,
+    pub buf: &'a mut Vec<u8>,
+    /// The number of objects and arrays that enclose the value being written.
+    pub depth: usize,
+}
+
+pub(crate) struct JerDecoder<'a> {
+    pub context: &'a Context,
+    pub buf: &'a [u8],
+    pub pos: usize,
+}
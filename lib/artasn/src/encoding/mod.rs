@@ -13,8 +13,24 @@ pub use transfer::*;
 pub(crate) mod strings;
 
 mod ber;
+pub use ber::der_validate_value;
+pub(crate) use ber::is_real_type;
+
+mod dump;
+pub use dump::dump_decoded_values;
+
 mod per;
 
+mod oer;
+
+mod xer;
+
+mod jer;
+
+mod gser;
+
+mod resolve;
+
 // Encodes a u64 to the least amount of little-endian bytes required to encode its full value.
 fn u64_to_le_bytes(num: u64) -> ([u8; mem::size_of::<u64>()], usize) {
     if num == 0 {
@@ -0,0 +1,912 @@
+use std::io;
+
+use num::{bigint::Sign, BigInt, Signed};
+
+use crate::{
+    compiler::{
+        parser::{Error, ErrorKind},
+        Context,
+    },
+    encoding::*,
+    module::QualifiedIdentifier,
+    types::*,
+    values::*,
+};
+
+use super::{get_fixed_integer_size, get_fixed_size, get_integer_bounds, OerDecoder};
+
+fn decoder_error<T>(
+    decoder: &OerDecoder<'_>,
+    start: usize,
+    message: impl Into<String>,
+) -> DecodeResult<T> {
+    Err(DecodeError::Decoder {
+        message: message.into(),
+        pos: TlvPos::new(start, decoder.pos),
+    })
+}
+
+fn read_bytes<'a>(decoder: &mut OerDecoder<'a>, len: usize) -> DecodeResult<&'a [u8]> {
+    match decoder.buf.get(decoder.pos..decoder.pos + len) {
+        Some(bytes) => {
+            decoder.pos += len;
+            Ok(bytes)
+        }
+        None => Err(DecodeError::Io(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "unexpected end of OER encoding",
+        ))),
+    }
+}
+
+fn read_byte(decoder: &mut OerDecoder<'_>) -> DecodeResult<u8> {
+    Ok(read_bytes(decoder, 1)?[0])
+}
+
+/// Reads a length determinant in either its short form or its long form (X.696 clause 8.6).
+/// COER requires the short form for lengths less than 128, and the minimum number of length octets otherwise.
+///
+/// Returns the length, and the position of the length determinant.
+fn read_length_determinant(decoder: &mut OerDecoder<'_>) -> DecodeResult<(usize, TlvPos)> {
+    let start = decoder.pos;
+    let first = read_byte(decoder)?;
+    if first & 0x80 == 0 {
+        return Ok((first as usize, TlvPos::new(start, decoder.pos)));
+    }
+
+    let len_octets = (first & 0x7f) as usize;
+    if len_octets == 0 || len_octets > std::mem::size_of::<usize>() {
+        return decoder_error(
+            decoder,
+            start,
+            format!("invalid length determinant 0x{:02X}", first),
+        );
+    }
+    let len = read_bytes(decoder, len_octets)?
+        .iter()
+        .fold(0usize, |len, byte| (len << 8) | *byte as usize);
+    if decoder.kind.is_canonical() && (len < 0x80 || len >> ((len_octets - 1) * 8) == 0) {
+        return decoder_error(decoder, start, "COER length determinant is not minimal");
+    }
+    Ok((len, TlvPos::new(start, decoder.pos)))
+}
+
+fn read_length_prefixed_bytes<'a>(
+    decoder: &mut OerDecoder<'a>,
+) -> DecodeResult<(&'a [u8], TlvPos)> {
+    let (len, len_pos) = read_length_determinant(decoder)?;
+    Ok((read_bytes(decoder, len)?, len_pos))
+}
+
+/// Reads an open type field, and decodes its contents as a complete encoding with `decode`.
+fn read_open_type<'a, T>(
+    decoder: &mut OerDecoder<'a>,
+    decode: impl FnOnce(&mut OerDecoder<'a>) -> DecodeResult<T>,
+) -> DecodeResult<T> {
+    let (len, _) = read_length_determinant(decoder)?;
+    let start = decoder.pos;
+    let end = start + len;
+    if end > decoder.buf.len() {
+        return Err(DecodeError::Io(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "unexpected end of OER encoding",
+        )));
+    }
+
+    let mut open_type_decoder = OerDecoder {
+        context: decoder.context,
+        kind: decoder.kind,
+        buf: &decoder.buf[..end],
+        pos: start,
+    };
+    let value = decode(&mut open_type_decoder)?;
+    if open_type_decoder.pos != end {
+        return Err(DecodeError::Decoder {
+            message: format!(
+                "{} bytes of trailing data in open type",
+                end - open_type_decoder.pos
+            ),
+            pos: TlvPos::new(open_type_decoder.pos, end),
+        });
+    }
+    decoder.pos = end;
+    Ok(value)
+}
+
+/// Reads the bits packed into `byte_len` octets, returning the first `bit_len` bits.
+/// COER requires the remaining padding bits to be 0.
+fn read_bits(
+    decoder: &mut OerDecoder<'_>,
+    byte_len: usize,
+    bit_len: usize,
+) -> DecodeResult<Vec<bool>> {
+    let start = decoder.pos;
+    let bytes = read_bytes(decoder, byte_len)?;
+    let mut bits = Vec::with_capacity(bit_len);
+    for i in 0..byte_len * 8 {
+        let bit = bytes[i / 8] & (0x80 >> (i % 8)) != 0;
+        if i < bit_len {
+            bits.push(bit);
+        } else if bit && decoder.kind.is_canonical() {
+            return decoder_error(decoder, start, "COER padding bits must be 0");
+        }
+    }
+    Ok(bits)
+}
+
+/// Returns whether the minimal two's complement (or unsigned, if `unsigned`) encoding of the integer
+/// is the given octets, as required by COER.
+fn is_minimal_integer(bytes: &[u8], unsigned: bool) -> bool {
+    match bytes {
+        [first, _, ..] if unsigned => *first != 0x00,
+        [first, second, ..] => {
+            !(*first == 0x00 && *second & 0x80 == 0 || *first == 0xff && *second & 0x80 != 0)
+        }
+        _ => true,
+    }
+}
+
+fn read_length_prefixed_integer(
+    decoder: &mut OerDecoder<'_>,
+    unsigned: bool,
+) -> DecodeResult<(BigInt, TlvPos)> {
+    let start = decoder.pos;
+    let (bytes, len_pos) = read_length_prefixed_bytes(decoder)?;
+    if bytes.is_empty() {
+        return decoder_error(decoder, start, "INTEGER must have a value");
+    }
+    if decoder.kind.is_canonical() && !is_minimal_integer(bytes, unsigned) {
+        return decoder_error(decoder, start, "COER INTEGER is not minimally encoded");
+    }
+
+    let int = if unsigned {
+        BigInt::from_bytes_be(Sign::Plus, bytes)
+    } else {
+        BigInt::from_signed_bytes_be(bytes)
+    };
+    Ok((int, len_pos))
+}
+
+/// See X.696 clause 10 for the encodings of INTEGER values.
+fn oer_decode_integer(
+    decoder: &mut OerDecoder<'_>,
+    resolved_type: &ResolvedType,
+) -> DecodeResult<(BigInt, Option<TlvPos>)> {
+    let bounds = get_integer_bounds(decoder.context, resolved_type).map_err(DecodeError::Parser)?;
+    Ok(match bounds {
+        (Some(min), Some(max)) if get_fixed_integer_size(&min, &max).is_some() => {
+            let size = get_fixed_integer_size(&min, &max).expect("fixed integer size");
+            let bytes = read_bytes(decoder, size)?;
+            let int = if min.is_negative() {
+                BigInt::from_signed_bytes_be(bytes)
+            } else {
+                BigInt::from_bytes_be(Sign::Plus, bytes)
+            };
+            (int, None)
+        }
+        (Some(min), _) if !min.is_negative() => {
+            let (int, len_pos) = read_length_prefixed_integer(decoder, true)?;
+            (int, Some(len_pos))
+        }
+        _ => {
+            let (int, len_pos) = read_length_prefixed_integer(decoder, false)?;
+            (int, Some(len_pos))
+        }
+    })
+}
+
+fn oer_decode_bit_string(
+    decoder: &mut OerDecoder<'_>,
+    resolved_type: &ResolvedType,
+) -> DecodeResult<(BitStringValue, Option<TlvPos>)> {
+    let start = decoder.pos;
+    let (bs, len_pos) =
+        match get_fixed_size(decoder.context, resolved_type).map_err(DecodeError::Parser)? {
+            Some(fixed_size) => {
+                let bytes = read_bytes(decoder, fixed_size.div_ceil(8) as usize)?;
+                let bs = BitStringValue {
+                    data: bytes.to_vec(),
+                    unused_bits: ((8 - fixed_size % 8) % 8) as u8,
+                };
+                (bs, None)
+            }
+            None => {
+                let (bytes, len_pos) = read_length_prefixed_bytes(decoder)?;
+                // the initial octet is the number of unused bits in the last octet
+                let unused_bits = match bytes.first() {
+                    Some(unused_bits)
+                        if *unused_bits <= 7 && (bytes.len() > 1 || *unused_bits == 0) =>
+                    {
+                        *unused_bits
+                    }
+                    _ => {
+                        return decoder_error(
+                            decoder,
+                            start,
+                            "BIT STRING has an illegal number of unused bits",
+                        )
+                    }
+                };
+                let bs = BitStringValue {
+                    data: bytes[1..].to_vec(),
+                    unused_bits,
+                };
+                (bs, Some(len_pos))
+            }
+        };
+
+    if decoder.kind.is_canonical() {
+        if let Some(last) = bs.data.last() {
+            if last & !(0xff << bs.unused_bits) != 0 {
+                return decoder_error(decoder, start, "COER BIT STRING unused bits must be 0");
+            }
+        }
+        if let BuiltinType::BitString(bs_type) = &resolved_type.ty {
+            if bs_type.named_bits.is_some()
+                && len_pos.is_some()
+                && bs.len_without_trailing_zeros() != bs.len()
+            {
+                return decoder_error(
+                    decoder,
+                    start,
+                    "COER BIT STRING with named bits cannot have trailing 0 bits",
+                );
+            }
+        }
+    }
+
+    Ok((bs, len_pos))
+}
+
+/// Returns the number of octets that encode each character of a known-multiplier character string type.
+fn get_character_width(tag_type: TagType) -> Option<u64> {
+    match tag_type {
+        TagType::NumericString
+        | TagType::PrintableString
+        | TagType::VisibleString
+        | TagType::IA5String => Some(1),
+        TagType::BMPString => Some(2),
+        TagType::UniversalString => Some(4),
+        _ => None,
+    }
+}
+
+fn oer_decode_character_string(
+    decoder: &mut OerDecoder<'_>,
+    resolved_type: &ResolvedType,
+    tag_type: TagType,
+) -> DecodeResult<(DecodedValueKind, Option<TlvPos>)> {
+    let fixed_size = get_fixed_size(decoder.context, resolved_type).map_err(DecodeError::Parser)?;
+    let (bytes, len_pos) = match (get_character_width(tag_type), fixed_size) {
+        (Some(width), Some(fixed_size)) => {
+            (read_bytes(decoder, (width * fixed_size) as usize)?, None)
+        }
+        _ => {
+            let (bytes, len_pos) = read_length_prefixed_bytes(decoder)?;
+            (bytes, Some(len_pos))
+        }
+    };
+    let kind = ber::ber_decode_universal(BasicEncodingKind::Basic, bytes, tag_type)
+        .map_err(DecodeError::Io)?;
+    Ok((kind, len_pos))
+}
+
+/// Reads a length-prefixed field containing the contents octets of a BER encoding,
+/// and decodes them as a value of the given universal type.
+fn read_ber_contents(
+    decoder: &mut OerDecoder<'_>,
+    tag_type: TagType,
+) -> DecodeResult<(DecodedValueKind, Option<TlvPos>)> {
+    let (bytes, len_pos) = read_length_prefixed_bytes(decoder)?;
+    let kind = ber::ber_decode_universal(BasicEncodingKind::Basic, bytes, tag_type)
+        .map_err(DecodeError::Io)?;
+    Ok((kind, Some(len_pos)))
+}
+
+/// Reads the value of the ENUMERATED item, which is a single octet if it is between 0 and 127,
+/// and a length-prefixed two's complement integer otherwise.
+fn oer_decode_enumerated(decoder: &mut OerDecoder<'_>) -> DecodeResult<i64> {
+    let start = decoder.pos;
+    let first = read_byte(decoder)?;
+    if first & 0x80 == 0 {
+        return Ok(first as i64);
+    }
+
+    let len = (first & 0x7f) as usize;
+    if len == 0 {
+        return decoder_error(decoder, start, "ENUMERATED must have a value");
+    }
+    let bytes = read_bytes(decoder, len)?;
+    if decoder.kind.is_canonical() && !is_minimal_integer(bytes, false) {
+        return decoder_error(decoder, start, "COER ENUMERATED is not minimally encoded");
+    }
+    let value = BigInt::from_signed_bytes_be(bytes);
+    if decoder.kind.is_canonical() && (BigInt::ZERO..BigInt::from(0x80)).contains(&value) {
+        return decoder_error(
+            decoder,
+            start,
+            "COER ENUMERATED value between 0 and 127 must be encoded in a single octet",
+        );
+    }
+    match i64::try_from(value) {
+        Ok(value) => Ok(value),
+        Err(_) => decoder_error(
+            decoder,
+            start,
+            "ENUMERATED value out of bounds for signed 64-bit integer",
+        ),
+    }
+}
+
+fn get_component_mode(
+    context: &Context,
+    component_name: Option<String>,
+    component_type: &TaggedType,
+) -> DecodeResult<DecodeMode> {
+    Ok(DecodeMode::SpecificType {
+        source_ident: match &component_type.ty {
+            UntaggedType::BuiltinType(_) => None,
+            UntaggedType::Reference(typeref) => Some(typeref.element.clone()),
+            UntaggedType::ObjectClassField(ocf) => {
+                return Err(DecodeError::Parser(Error {
+                    kind: ErrorKind::Ast(format!(
+                        "decoding a component of the type {} is not yet supported",
+                        ocf
+                    )),
+                    loc: ocf.field.loc,
+                }))
+            }
+        },
+        component_name,
+        resolved: Box::new(
+            component_type
+                .resolve(context)
+                .map_err(DecodeError::Parser)?,
+        ),
+    })
+}
+
+/// Reads the values of the components in `order`, where `is_present` has the presence bit
+/// of each OPTIONAL and DEFAULT component.
+fn read_components(
+    decoder: &mut OerDecoder<'_>,
+    structure: &Structure,
+    order: &[usize],
+    is_present: &[bool],
+    components: &mut Vec<DecodedValue>,
+) -> DecodeResult<()> {
+    let context = decoder.context;
+
+    let mut is_present = is_present.iter();
+    for index in order {
+        let component = &structure.components[*index];
+        let is_optional = component.optional || component.default_value.is_some();
+        if !is_optional || *is_present.next().expect("presence bit") {
+            let mode = get_component_mode(
+                context,
+                Some(component.name.element.clone()),
+                &component.component_type,
+            )?;
+            components.push(oer_decode_value(decoder, &mode)?);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the number of OPTIONAL and DEFAULT components in `order`.
+fn count_optional_components(structure: &Structure, order: &[usize]) -> usize {
+    order
+        .iter()
+        .map(|index| &structure.components[*index])
+        .filter(|component| component.optional || component.default_value.is_some())
+        .count()
+}
+
+fn oer_decode_structure(
+    decoder: &mut OerDecoder<'_>,
+    structure: &Structure,
+) -> DecodeResult<Vec<DecodedValue>> {
+    let context = decoder.context;
+    let (root, additions) =
+        per::get_component_order(context, structure).map_err(DecodeError::Parser)?;
+
+    // the preamble is a bit-map of the extension bit, followed by the presence bits of the OPTIONAL and DEFAULT components
+    let bit_len = structure.extensible as usize + count_optional_components(structure, &root);
+    let mut preamble = read_bits(decoder, bit_len.div_ceil(8), bit_len)?;
+    let has_additions = structure.extensible && preamble.remove(0);
+
+    let mut components = Vec::with_capacity(structure.components.len());
+    read_components(decoder, structure, &root, &preamble, &mut components)?;
+
+    if has_additions {
+        // the presence of the extension additions is encoded like a BIT STRING
+        let start = decoder.pos;
+        let (len, _) = read_length_determinant(decoder)?;
+        let unused_bits = read_byte(decoder)? as usize;
+        if len < 2 || unused_bits > 7 {
+            return decoder_error(decoder, start, "invalid extension addition bit-map");
+        }
+        let is_present = read_bits(decoder, len - 1, (len - 1) * 8 - unused_bits)?;
+        if decoder.kind.is_canonical() && is_present.last() != Some(&true) {
+            // COER uses the minimal bit-map, which ends at the last extension addition that is present
+            return decoder_error(
+                decoder,
+                start,
+                "COER extension addition bit-map must end with an extension addition that is present",
+            );
+        }
+
+        for (index, is_present) in is_present.into_iter().enumerate() {
+            if !is_present {
+                continue;
+            }
+            match additions.get(index) {
+                Some(addition) => {
+                    let component = &structure.components[addition[0]];
+                    let is_group = component
+                        .extension_addition
+                        .is_some_and(|addition| addition.is_group);
+                    if is_group {
+                        read_open_type(decoder, |decoder| {
+                            let bit_len = count_optional_components(structure, addition);
+                            let is_present = read_bits(decoder, bit_len.div_ceil(8), bit_len)?;
+                            read_components(
+                                decoder,
+                                structure,
+                                addition,
+                                &is_present,
+                                &mut components,
+                            )
+                        })?;
+                    } else {
+                        let mode = get_component_mode(
+                            context,
+                            Some(component.name.element.clone()),
+                            &component.component_type,
+                        )?;
+                        components.push(read_open_type(decoder, |decoder| {
+                            oer_decode_value(decoder, &mode)
+                        })?);
+                    }
+                }
+                // extension additions that are not defined in this version of the type are skipped
+                None => read_open_type(decoder, |decoder| {
+                    decoder.pos = decoder.buf.len();
+                    Ok(())
+                })?,
+            }
+        }
+    }
+
+    Ok(components)
+}
+
+fn oer_decode_structure_of(
+    decoder: &mut OerDecoder<'_>,
+    tag_type: TagType,
+    component_type: &TaggedType,
+) -> DecodeResult<Vec<DecodedValue>> {
+    let context = decoder.context;
+
+    // the quantity field is the number of elements as a length-prefixed unsigned integer
+    let start = decoder.pos;
+    let (quantity, _) = read_length_prefixed_bytes(decoder)?;
+    if decoder.kind.is_canonical() && !is_minimal_integer(quantity, true) {
+        return decoder_error(decoder, start, "COER quantity is not minimally encoded");
+    }
+    let quantity = match usize::try_from(BigInt::from_bytes_be(Sign::Plus, quantity)) {
+        Ok(quantity) => quantity,
+        Err(_) => return decoder_error(decoder, start, "quantity is too large"),
+    };
+
+    let mode = get_component_mode(context, None, component_type)?;
+    let mut elements = Vec::new();
+    let mut previous: Option<&[u8]> = None;
+    for _ in 0..quantity {
+        let start = decoder.pos;
+        elements.push(oer_decode_value(decoder, &mode)?);
+
+        if tag_type == TagType::Set && decoder.kind.is_canonical() {
+            // COER places the elements of a SET OF in ascending order of their encodings
+            let encoding = &decoder.buf[start..decoder.pos];
+            if previous.is_some_and(|previous| previous > encoding) {
+                return decoder_error(
+                    decoder,
+                    start,
+                    "COER SET OF element must be placed before the previous element",
+                );
+            }
+            previous = Some(encoding);
+        }
+    }
+    Ok(elements)
+}
+
+/// Reads the tag of a CHOICE alternative (X.696 clause 8.7).
+fn read_tag(decoder: &mut OerDecoder<'_>) -> DecodeResult<(Class, u16)> {
+    let start = decoder.pos;
+    let first = read_byte(decoder)?;
+    let class = match first >> 6 {
+        0b00 => Class::Universal,
+        0b01 => Class::Application,
+        0b10 => Class::ContextSpecific,
+        _ => Class::Private,
+    };
+    if first & 0x3f < 0x3f {
+        return Ok((class, (first & 0x3f) as u16));
+    }
+
+    let mut num = 0u32;
+    loop {
+        let byte = read_byte(decoder)?;
+        if num == 0 && byte == 0x80 {
+            return decoder_error(decoder, start, "tag number is not minimally encoded");
+        }
+        num = (num << 7) | (byte & 0x7f) as u32;
+        if num > u16::MAX as u32 {
+            return decoder_error(decoder, start, "tag number is too large");
+        }
+        if byte & 0x80 == 0 {
+            break;
+        }
+    }
+    if num < 0x3f {
+        return decoder_error(decoder, start, "tag number is not minimally encoded");
+    }
+    Ok((class, num as u16))
+}
+
+/// Decodes the chosen alternative of the CHOICE.
+/// Like in BER, the `DecodedValue` of a CHOICE is the value of its alternative, with the metadata of the CHOICE,
+/// and the position of its tag is the position of the CHOICE tag.
+fn oer_decode_choice(
+    decoder: &mut OerDecoder<'_>,
+    choice: &Choice,
+    source_ident: &Option<QualifiedIdentifier>,
+    component_name: &Option<String>,
+) -> DecodeResult<DecodedValue> {
+    let context = decoder.context;
+
+    let start = decoder.pos;
+    let (class, num) = read_tag(decoder)?;
+    let tag_pos = TlvPos::new(start, decoder.pos);
+
+    let mut found = None;
+    for alternative in &choice.alternatives {
+        let resolved = alternative
+            .alternative_type
+            .resolve(context)
+            .map_err(DecodeError::Parser)?;
+        let is_match = match &resolved.tag {
+            Some(tag) => tag.class == class && tag.num == num,
+            // an untagged CHOICE alternative is identified by the tags of its own alternatives
+            None => resolved
+                .get_possible_tags(context)
+                .map_err(DecodeError::Parser)?
+                .iter()
+                .any(|(tag, _)| tag.class == class && tag.num == num),
+        };
+        if is_match {
+            found = Some((alternative, resolved));
+            break;
+        }
+    }
+    let (alternative, resolved) = match found {
+        Some(found) => found,
+        None => {
+            return decoder_error(
+                decoder,
+                start,
+                format!(
+                    "CHOICE alternative with tag [{} {}] is not defined",
+                    class, num
+                ),
+            )
+        }
+    };
+
+    let is_untagged = resolved.tag.is_none();
+    if is_untagged {
+        // the nested CHOICE reads the tag itself
+        decoder.pos = start;
+    }
+    let mode = DecodeMode::SpecificType {
+        source_ident: source_ident.clone(),
+        component_name: component_name.clone(),
+        resolved: Box::new(resolved),
+    };
+    let mut value = if alternative.is_extension {
+        read_open_type(decoder, |decoder| oer_decode_value(decoder, &mode))?
+    } else {
+        oer_decode_value(decoder, &mode)?
+    };
+    if !is_untagged {
+        value.tag.pos = tag_pos;
+    }
+    Ok(value)
+}
+
+/// Decodes a single OER value of the type specified by the `DecodeMode`.
+/// OER encodings only contain the tags of CHOICE alternatives, so the `DecodedValue` has the tag of the type,
+/// and its tag position is empty unless it is the value of a CHOICE alternative.
+/// Its length position is the position of the length determinant, if the value has one.
+pub fn oer_decode_value(
+    decoder: &mut OerDecoder<'_>,
+    mode: &DecodeMode,
+) -> DecodeResult<DecodedValue> {
+    let (source_ident, component_name, resolved) = match mode {
+        DecodeMode::Contextless => {
+            return decoder_error(
+                decoder,
+                decoder.pos,
+                "OER can only be decoded with a known type",
+            )
+        }
+        DecodeMode::SpecificType {
+            source_ident,
+            component_name,
+            resolved,
+        } => (source_ident, component_name, resolved),
+    };
+
+    if let BuiltinType::Choice(choice) = &resolved.ty {
+        return oer_decode_choice(decoder, choice, source_ident, component_name);
+    }
+
+    let start = decoder.pos;
+    let (kind, len_pos) = match &resolved.ty {
+        BuiltinType::Boolean => {
+            let byte = read_byte(decoder)?;
+            let value = match (byte, decoder.kind.is_canonical()) {
+                (0x00, _) => false,
+                (0xff, _) | (_, false) => true,
+                (_, true) => {
+                    return decoder_error(decoder, start, "COER BOOLEAN must be 0x00 or 0xFF")
+                }
+            };
+            (Some(DecodedValueKind::Boolean(value)), None)
+        }
+        BuiltinType::Integer(_) => {
+            let (int, len_pos) = oer_decode_integer(decoder, resolved)?;
+            (Some(DecodedValueKind::Integer(int)), len_pos)
+        }
+        BuiltinType::BitString(_) => {
+            let (bs, len_pos) = oer_decode_bit_string(decoder, resolved)?;
+            (Some(DecodedValueKind::BitString(bs)), len_pos)
+        }
+        BuiltinType::OctetString => {
+            match get_fixed_size(decoder.context, resolved).map_err(DecodeError::Parser)? {
+                Some(fixed_size) => {
+                    let bytes = read_bytes(decoder, fixed_size as usize)?;
+                    (Some(DecodedValueKind::OctetString(bytes.to_vec())), None)
+                }
+                None => {
+                    let (bytes, len_pos) = read_length_prefixed_bytes(decoder)?;
+                    (
+                        Some(DecodedValueKind::OctetString(bytes.to_vec())),
+                        Some(len_pos),
+                    )
+                }
+            }
+        }
+        BuiltinType::Null => (Some(DecodedValueKind::Null), None),
+        BuiltinType::CharacterString(tag_type) => {
+            let (kind, len_pos) = oer_decode_character_string(decoder, resolved, *tag_type)?;
+            (Some(kind), len_pos)
+        }
+        BuiltinType::UTCTime => {
+            let (kind, len_pos) = read_ber_contents(decoder, TagType::UTCTime)?;
+            (Some(kind), len_pos)
+        }
+        BuiltinType::GeneralizedTime => {
+            let (kind, len_pos) = read_ber_contents(decoder, TagType::GeneralizedTime)?;
+            (Some(kind), len_pos)
+        }
+        BuiltinType::ObjectIdentifier => {
+            let (kind, len_pos) = read_ber_contents(decoder, TagType::ObjectIdentifier)?;
+            (Some(kind), len_pos)
+        }
+        BuiltinType::RelativeOid => {
+            let (kind, len_pos) = read_ber_contents(decoder, TagType::RelativeOid)?;
+            (Some(kind), len_pos)
+        }
+        ty if ber::is_real_type(ty) => {
+            let (kind, len_pos) = read_ber_contents(decoder, TagType::Real)?;
+            (Some(kind), len_pos)
+        }
+        BuiltinType::Enumerated(_) => (
+            Some(DecodedValueKind::Enumerated(oer_decode_enumerated(
+                decoder,
+            )?)),
+            None,
+        ),
+        BuiltinType::Structure(_) | BuiltinType::StructureOf(_) => (None, None),
+        other => {
+            return decoder_error(
+                decoder,
+                start,
+                format!("OER decoding is not supported for {}", other),
+            )
+        }
+    };
+    let form = match kind {
+        Some(kind) => DecodedValueForm::Primitive(kind),
+        None => DecodedValueForm::Constructed(match &resolved.ty {
+            BuiltinType::Structure(structure) => oer_decode_structure(decoder, structure)?,
+            BuiltinType::StructureOf(of) => {
+                oer_decode_structure_of(decoder, of.ty, &of.component_type)?
+            }
+            _ => unreachable!(),
+        }),
+    };
+    let end = decoder.pos;
+
+    let tag = match &resolved.tag {
+        Some(tag) => TlvTag {
+            class: tag.class,
+            form: match form {
+                DecodedValueForm::Primitive(_) => TypeForm::Primitive,
+                DecodedValueForm::Constructed(_) => TypeForm::Constructed,
+            },
+            num: tag.num,
+        },
+        None => {
+            return decoder_error(
+                decoder,
+                start,
+                format!("OER decoding is not supported for {}", resolved.ty),
+            )
+        }
+    };
+
+    let (len_pos, value_start) = match len_pos {
+        Some(len_pos) => (len_pos, len_pos.end),
+        None => (TlvPos::new(start, start), start),
+    };
+    Ok(DecodedValue {
+        tag: TlvElement::new(tag, TlvPos::new(start, start)),
+        len: TlvElement::new((end - value_start) as u32, len_pos),
+        value_pos: TlvPos::new(value_start, end),
+        form,
+        metadata: Some(DecodedValueMetadata {
+            type_ident: source_ident.clone(),
+            component_name: component_name.clone(),
+        }),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        compiler::{test::compile_module, Context},
+        encoding::*,
+        module::{ModuleIdentifier, QualifiedIdentifier},
+    };
+
+    const MODULE: &str = r#"OerDecodeTest DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+
+Simple ::= SEQUENCE {
+    a BOOLEAN,
+    b INTEGER (0..7)
+}
+
+Unbounded ::= INTEGER
+
+Color ::= ENUMERATED { red, green, blue(10) }
+
+Flags ::= BIT STRING { a(0), b(1), c(2) }
+
+Extensible ::= SEQUENCE {
+    a BOOLEAN,
+    ...,
+    b INTEGER (0..7) OPTIONAL,
+    [[ c BOOLEAN, d OCTET STRING OPTIONAL ]]
+}
+
+Bag ::= SET OF INTEGER (0..255)
+
+Message ::= CHOICE {
+    data OCTET STRING,
+    flag BOOLEAN
+}
+
+MESSAGE-ID ::= CLASS { &id INTEGER UNIQUE } WITH SYNTAX { ID &id }
+
+Identified ::= SEQUENCE {
+    id MESSAGE-ID.&id
+}
+
+END
+"#;
+
+    fn decode(
+        context: &Context,
+        type_name: &str,
+        kind: OctetEncodingKind,
+        hex: &str,
+    ) -> DecodeResult<DecodedValue> {
+        let ident = QualifiedIdentifier::new(
+            ModuleIdentifier::with_name("OerDecodeTest".to_string()),
+            type_name.to_string(),
+        );
+        let mode = DecodeMode::SpecificType {
+            source_ident: Some(ident.clone()),
+            component_name: None,
+            resolved: Box::new(
+                context
+                    .lookup_type(&ident)
+                    .expect("lookup_type")
+                    .ty
+                    .resolve(context)
+                    .expect("resolve"),
+            ),
+        };
+
+        let syntax = TransferSyntax::Octet(kind);
+        let decoder = syntax.get_codec().decoder.expect("decoder");
+        let buf = hex::decode(hex).expect("hex");
+        decoder(&syntax, &mode, &buf, context).map(|mut values| values.remove(0))
+    }
+
+    #[test]
+    fn test_coer_decode_non_canonical() {
+        let mut context = Context::new();
+        compile_module(&mut context, "OerDecodeTest.asn", MODULE);
+
+        for (type_name, hex) in [
+            // long form length determinant of a length less than 128
+            ("Unbounded", "810100"),
+            // INTEGER with a redundant leading octet
+            ("Unbounded", "020001"),
+            // BOOLEAN TRUE that is not 0xFF
+            ("Simple", "0105"),
+            // ENUMERATED value less than 128 in the long form
+            ("Color", "8101"),
+            // named BIT STRING with trailing 0 bits
+            ("Flags", "020440"),
+            // extension bit-map without any extension additions that are present
+            ("Extensible", "80FF020600"),
+            // extension bit-map that is not minimal
+            ("Extensible", "80000206800105"),
+            // SET OF elements that are not in ascending order
+            ("Bag", "0103070305"),
+        ] {
+            decode(&context, type_name, OctetEncodingKind::Basic, hex)
+                .unwrap_or_else(|err| panic!("OER {} {}: {}", type_name, hex, err));
+            assert!(
+                decode(&context, type_name, OctetEncodingKind::Canonical, hex).is_err(),
+                "COER {} {} is not rejected",
+                type_name,
+                hex
+            );
+        }
+    }
+
+    #[test]
+    fn test_oer_decode_positions() {
+        let mut context = Context::new();
+        compile_module(&mut context, "OerDecodeTest.asn", MODULE);
+
+        let value = decode(
+            &context,
+            "Message",
+            OctetEncodingKind::Canonical,
+            "80021234",
+        )
+        .expect("decode");
+        assert_eq!((value.tag.pos.start, value.tag.pos.end), (0, 1));
+        assert_eq!((value.len.pos.start, value.len.pos.end), (1, 2));
+        assert_eq!(value.len.element, 2);
+        assert_eq!((value.value_pos.start, value.value_pos.end), (2, 4));
+    }
+
+    #[test]
+    fn test_oer_decode_object_class_field() {
+        let mut context = Context::new();
+        compile_module(&mut context, "OerDecodeTest.asn", MODULE);
+
+        let err = decode(&context, "Identified", OctetEncodingKind::Basic, "0105")
+            .expect_err("ObjectClassField component is decoded");
+        assert!(matches!(err, DecodeError::Parser(_)), "{}", err);
+    }
+}
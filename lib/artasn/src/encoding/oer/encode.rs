@@ -0,0 +1,649 @@
+use num::{BigInt, Signed};
+
+use crate::{
+    compiler::parser::{AstElement, Error, ErrorKind, Loc, Result},
+    encoding::{ber, encode_contained_value, per, SetOrdering, TransferSyntax},
+    types::*,
+    values::*,
+};
+
+use super::{get_fixed_integer_size, get_fixed_size, get_integer_bounds, OerEncoder};
+
+/// Writes a length determinant in its short form if the length is less than 128,
+/// and in its long form otherwise (X.696 clause 8.6).
+fn write_length_determinant(buf: &mut Vec<u8>, len: usize) {
+    if len < 0x80 {
+        buf.push(len as u8);
+    } else {
+        let bytes = (len as u64).to_be_bytes();
+        let leading_zeros = bytes.iter().take_while(|byte| **byte == 0).count();
+        buf.push(0x80 | (bytes.len() - leading_zeros) as u8);
+        buf.extend_from_slice(&bytes[leading_zeros..]);
+    }
+}
+
+fn write_length_prefixed_bytes(buf: &mut Vec<u8>, bytes: &[u8]) {
+    write_length_determinant(buf, bytes.len());
+    buf.extend_from_slice(bytes);
+}
+
+/// Writes the bits packed into octets, where the last octet is padded with 0 bits.
+fn write_bits(buf: &mut Vec<u8>, bits: &[bool]) {
+    for chunk in bits.chunks(8) {
+        let mut byte = 0;
+        for (i, bit) in chunk.iter().enumerate() {
+            if *bit {
+                byte |= 0x80 >> i;
+            }
+        }
+        buf.push(byte);
+    }
+}
+
+/// Writes the complete encoding written by `encode` as an open type field, prefixed by its length.
+fn write_open_type(
+    encoder: &mut OerEncoder<'_>,
+    encode: impl FnOnce(&mut OerEncoder<'_>) -> Result<()>,
+) -> Result<()> {
+    let mut buf = Vec::new();
+    encode(&mut OerEncoder {
+        context: encoder.context,
+        kind: encoder.kind,
+        options: encoder.options,
+        buf: &mut buf,
+    })?;
+    write_length_prefixed_bytes(encoder.buf, &buf);
+    Ok(())
+}
+
+/// Writes the value in the given number of octets, as an unsigned integer if `min` is non-negative,
+/// and as a two's complement integer otherwise.
+fn write_fixed_size_integer(buf: &mut Vec<u8>, value: &BigInt, min: &BigInt, size: usize) {
+    let bytes = if min.is_negative() {
+        value.to_signed_bytes_be()
+    } else {
+        value.to_bytes_be().1
+    };
+    let padding = if value.is_negative() { 0xff } else { 0x00 };
+    buf.extend(std::iter::repeat_n(padding, size - bytes.len()));
+    buf.extend_from_slice(&bytes);
+}
+
+/// See X.696 clause 10 for the encodings of INTEGER values.
+fn oer_encode_integer(
+    encoder: &mut OerEncoder<'_>,
+    resolved_type: &ResolvedType,
+    value: &BigInt,
+) -> Result<()> {
+    match get_integer_bounds(encoder.context, resolved_type)? {
+        (Some(min), Some(max)) if get_fixed_integer_size(&min, &max).is_some() => {
+            let size = get_fixed_integer_size(&min, &max).expect("fixed integer size");
+            write_fixed_size_integer(encoder.buf, value, &min, size);
+        }
+        (Some(min), _) if !min.is_negative() => {
+            write_length_prefixed_bytes(encoder.buf, &value.to_bytes_be().1);
+        }
+        _ => write_length_prefixed_bytes(encoder.buf, &value.to_signed_bytes_be()),
+    }
+    Ok(())
+}
+
+fn oer_encode_bit_string(
+    encoder: &mut OerEncoder<'_>,
+    resolved_type: &ResolvedType,
+    bs: &BitStringValue,
+) -> Result<()> {
+    let mut total_bits = bs.len();
+    if let BuiltinType::BitString(bs_type) = &resolved_type.ty {
+        if bs_type.named_bits.is_some() && encoder.kind.is_canonical() {
+            // COER removes the trailing 0 bits of BIT STRING with named bits
+            total_bits = bs.len_without_trailing_zeros();
+        }
+    }
+
+    let fixed_size = get_fixed_size(encoder.context, resolved_type)?;
+    if let Some(fixed_size) = fixed_size {
+        // BIT STRING with named bits are permitted to have values whose size are less than the fixed size
+        total_bits = fixed_size;
+    }
+
+    let byte_len = total_bits.div_ceil(8) as usize;
+    let unused_bits = (byte_len as u64 * 8 - total_bits) as u8;
+    if fixed_size.is_none() {
+        // the length includes the initial octet, which is the number of unused bits in the last octet
+        write_length_determinant(encoder.buf, byte_len + 1);
+        encoder.buf.push(unused_bits);
+    }
+    for i in 0..byte_len {
+        let mut byte = bs.data.get(i).copied().unwrap_or(0);
+        if i == byte_len - 1 {
+            byte &= 0xff << unused_bits;
+        }
+        encoder.buf.push(byte);
+    }
+    Ok(())
+}
+
+/// Returns whether each character of the character string type is encoded in a fixed number of octets.
+fn is_known_multiplier_type(tag_type: TagType) -> bool {
+    matches!(
+        tag_type,
+        TagType::NumericString
+            | TagType::PrintableString
+            | TagType::VisibleString
+            | TagType::IA5String
+            | TagType::BMPString
+            | TagType::UniversalString
+    )
+}
+
+fn oer_encode_character_string(
+    encoder: &mut OerEncoder<'_>,
+    resolved_type: &ResolvedType,
+    tag_type: TagType,
+    str: &str,
+) -> Result<()> {
+    let mut contents = Vec::new();
+    ber::ber_encode_character_string(&mut contents, tag_type, str);
+    contents.reverse();
+
+    // the length is omitted when a known-multiplier character string type has a fixed size
+    if is_known_multiplier_type(tag_type)
+        && get_fixed_size(encoder.context, resolved_type)?.is_some()
+    {
+        encoder.buf.extend_from_slice(&contents);
+    } else {
+        write_length_prefixed_bytes(encoder.buf, &contents);
+    }
+    Ok(())
+}
+
+/// Writes the value of the ENUMERATED item in a single octet if it is between 0 and 127,
+/// and as a length-prefixed two's complement integer otherwise.
+fn oer_encode_enumerated(
+    encoder: &mut OerEncoder<'_>,
+    value: &AstElement<TypedValue>,
+) -> Result<()> {
+    let context = encoder.context;
+    let num = match value.resolve(context)?.value {
+        BuiltinValue::Integer(num) => num,
+        other => {
+            return Err(Error {
+                kind: ErrorKind::Ast(format!(
+                    "ENUMERATED value must be of type INTEGER, but found {}",
+                    other.tag_type(context)?
+                )),
+                loc: value.loc,
+            })
+        }
+    };
+
+    match u8::try_from(&num) {
+        Ok(num) if num < 0x80 => encoder.buf.push(num),
+        _ => {
+            let bytes = num.to_signed_bytes_be();
+            encoder.buf.push(0x80 | bytes.len() as u8);
+            encoder.buf.extend_from_slice(&bytes);
+        }
+    }
+    Ok(())
+}
+
+/// Returns whether the component is omitted from the encoding because its value is equal to its DEFAULT value.
+/// COER never encodes these components.
+fn is_omitted_default_value(
+    encoder: &OerEncoder<'_>,
+    component: &StructureValueComponent,
+) -> Result<bool> {
+    if !encoder.kind.is_canonical() && encoder.options.encode_default_values {
+        return Ok(false);
+    }
+    if component.is_default {
+        return Ok(true);
+    }
+    match &component.default_value {
+        Some(default_value) => component.value.try_eq(encoder.context, default_value),
+        None => Ok(false),
+    }
+}
+
+/// Writes the values of the components that are present.
+fn write_components(
+    encoder: &mut OerEncoder<'_>,
+    components: &[(&StructureComponent, Option<&StructureValueComponent>)],
+) -> Result<()> {
+    for (_, component_value) in components {
+        if let Some(component_value) = component_value {
+            let component_value = component_value.value.resolve(encoder.context)?;
+            oer_encode_value(encoder, &component_value)?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the presence bits of the OPTIONAL and DEFAULT components.
+fn get_presence_bits(
+    components: &[(&StructureComponent, Option<&StructureValueComponent>)],
+) -> Vec<bool> {
+    components
+        .iter()
+        .filter(|(component, _)| component.optional || component.default_value.is_some())
+        .map(|(_, component_value)| component_value.is_some())
+        .collect()
+}
+
+fn oer_encode_structure(
+    encoder: &mut OerEncoder<'_>,
+    structure_type: &Structure,
+    value: &StructureValue,
+) -> Result<()> {
+    let context = encoder.context;
+
+    let get_components = |order: Vec<usize>| {
+        let mut components = Vec::with_capacity(order.len());
+        for index in order {
+            let component = &structure_type.components[index];
+            let component_value = match value
+                .components
+                .iter()
+                .find(|value| value.name.element == component.name.element)
+            {
+                Some(component_value) if !is_omitted_default_value(encoder, component_value)? => {
+                    Some(component_value)
+                }
+                _ => None,
+            };
+            components.push((component, component_value));
+        }
+        Ok::<_, Error>(components)
+    };
+
+    // the root components of a SET are encoded in the canonical order of their tags, like in PER
+    let (root, additions) = per::get_component_order(context, structure_type)?;
+    let components = get_components(root)?;
+    let additions = additions
+        .into_iter()
+        .map(get_components)
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut is_present = additions
+        .iter()
+        .map(|addition| addition.iter().any(|(_, value)| value.is_some()))
+        .collect::<Vec<_>>();
+    if encoder.kind.is_canonical() {
+        // COER uses the minimal bit-map, which ends at the last extension addition that is present
+        let len = is_present
+            .iter()
+            .rposition(|is_present| *is_present)
+            .map_or(0, |index| index + 1);
+        is_present.truncate(len);
+    }
+    let has_additions = is_present.contains(&true);
+
+    // the preamble is a bit-map of the extension bit, followed by the presence bits of the OPTIONAL and DEFAULT components
+    let mut preamble = Vec::with_capacity(components.len() + 1);
+    if structure_type.extensible {
+        preamble.push(has_additions);
+    }
+    preamble.extend(get_presence_bits(&components));
+    write_bits(encoder.buf, &preamble);
+
+    write_components(encoder, &components)?;
+
+    if has_additions {
+        // the presence of the extension additions is encoded like a BIT STRING
+        let byte_len = is_present.len().div_ceil(8);
+        write_length_determinant(encoder.buf, byte_len + 1);
+        encoder.buf.push((byte_len * 8 - is_present.len()) as u8);
+        write_bits(encoder.buf, &is_present);
+
+        // each extension addition is encoded as an open type,
+        // where an extension addition group is encoded as a SEQUENCE of its components
+        for addition in additions {
+            let Some((component, component_value)) = addition.first() else {
+                continue;
+            };
+            let is_group = component
+                .extension_addition
+                .is_some_and(|addition| addition.is_group);
+            if is_group {
+                if addition.iter().any(|(_, value)| value.is_some()) {
+                    write_open_type(encoder, |encoder| {
+                        write_bits(encoder.buf, &get_presence_bits(&addition));
+                        write_components(encoder, &addition)
+                    })?;
+                }
+            } else if let Some(component_value) = component_value {
+                let component_value = component_value.value.resolve(context)?;
+                write_open_type(encoder, |encoder| {
+                    oer_encode_value(encoder, &component_value)
+                })?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the tag of a CHOICE alternative, with its class in the two most significant bits of the first octet,
+/// followed by its number in the remaining bits, or in subsequent octets if the number is at least 63.
+fn write_tag(buf: &mut Vec<u8>, tag: &Tag) {
+    let class = match tag.class {
+        Class::Universal => 0b00,
+        Class::Application => 0b01,
+        Class::ContextSpecific => 0b10,
+        Class::Private => 0b11,
+    } << 6;
+    if tag.num < 0x3f {
+        buf.push(class | tag.num as u8);
+    } else {
+        buf.push(class | 0x3f);
+        let mut num = tag.num;
+        let mut bytes = vec![(num & 0x7f) as u8];
+        num >>= 7;
+        while num > 0 {
+            bytes.push(0x80 | (num & 0x7f) as u8);
+            num >>= 7;
+        }
+        buf.extend(bytes.into_iter().rev());
+    }
+}
+
+fn oer_encode_choice(
+    encoder: &mut OerEncoder<'_>,
+    choice_type: &Choice,
+    value: &ChoiceValue,
+) -> Result<()> {
+    let context = encoder.context;
+    let alternative = choice_type
+        .alternatives
+        .iter()
+        .find(|alternative| alternative.name.element == value.alternative.element)
+        .expect("CHOICE value has an alternative that is not in the CHOICE type");
+    let alternative_value = value.value.resolve(context)?;
+
+    // an untagged CHOICE alternative is identified by the tag of its own alternative
+    if let Some(tag) = &value.alternative_type.tag {
+        write_tag(encoder.buf, tag);
+    }
+    if alternative.is_extension {
+        // extension additions are encoded as an open type
+        write_open_type(encoder, |encoder| {
+            oer_encode_value(encoder, &alternative_value)
+        })
+    } else {
+        oer_encode_value(encoder, &alternative_value)
+    }
+}
+
+/// Returns the elements of a SET OF value in ascending order of their encodings, as required by COER.
+fn sort_set_of(
+    encoder: &OerEncoder<'_>,
+    elements: &[AstElement<TypedValue>],
+) -> Result<Vec<AstElement<TypedValue>>> {
+    let mut encodings = Vec::with_capacity(elements.len());
+    for element in elements {
+        let resolved = element.resolve(encoder.context)?;
+        let mut buf = Vec::new();
+        oer_encode_value(
+            &mut OerEncoder {
+                context: encoder.context,
+                kind: encoder.kind,
+                options: encoder.options,
+                buf: &mut buf,
+            },
+            &resolved,
+        )?;
+        encodings.push((element, buf));
+    }
+
+    match encoder.options.set_ordering {
+        SetOrdering::Sort => encodings.sort_by(|(_, a), (_, b)| a.cmp(b)),
+        SetOrdering::Verify => {
+            if let Some(window) = encodings
+                .windows(2)
+                .find(|window| window[0].1 > window[1].1)
+            {
+                return Err(Error {
+                    kind: ErrorKind::Ast(
+                        "SET OF element must be placed before the previous element in canonical order"
+                            .to_string(),
+                    ),
+                    loc: window[1].0.loc,
+                });
+            }
+        }
+    }
+
+    Ok(encodings
+        .into_iter()
+        .map(|(element, _)| element.clone())
+        .collect())
+}
+
+pub fn oer_encode_value(encoder: &mut OerEncoder<'_>, typed_value: &ResolvedValue) -> Result<()> {
+    let context = encoder.context;
+
+    if ber::is_real_type(&typed_value.ty.ty) {
+        // the contents octets of the CER encoding, prefixed by their length
+        let mut contents = Vec::new();
+        match &typed_value.value {
+            BuiltinValue::Integer(num) => {
+                ber::ber_encode_real(&mut contents, num.clone(), 10, BigInt::ZERO)
+            }
+            BuiltinValue::RealLiteral(lit) => ber::ber_encode_real(
+                &mut contents,
+                lit.mantissa.clone(),
+                10,
+                lit.exponent.clone(),
+            ),
+            BuiltinValue::Structure(_, structure) => {
+                ber::ber_encode_real_structure(&mut contents, context, structure)?
+            }
+            _ => unreachable!(),
+        }
+        contents.reverse();
+        write_length_prefixed_bytes(encoder.buf, &contents);
+        return Ok(());
+    }
+
+    match &typed_value.value {
+        BuiltinValue::Boolean(b) => {
+            encoder.buf.push(if *b { 0xff } else { 0x00 });
+        }
+        BuiltinValue::Integer(int) => {
+            oer_encode_integer(encoder, &typed_value.ty, int)?;
+        }
+        BuiltinValue::BitString(bs) => {
+            oer_encode_bit_string(encoder, &typed_value.ty, bs)?;
+        }
+        BuiltinValue::OctetString(bytes) => {
+            if get_fixed_size(encoder.context, &typed_value.ty)?.is_some() {
+                encoder.buf.extend_from_slice(bytes);
+            } else {
+                write_length_prefixed_bytes(encoder.buf, bytes);
+            }
+        }
+        BuiltinValue::Null => (),
+        BuiltinValue::CharacterString(tag_type, str) => {
+            oer_encode_character_string(encoder, &typed_value.ty, *tag_type, str)?;
+        }
+        BuiltinValue::UTCTime(utc) => {
+            write_length_prefixed_bytes(encoder.buf, utc.to_ber_string().as_bytes());
+        }
+        BuiltinValue::GeneralizedTime(gt) => {
+            write_length_prefixed_bytes(encoder.buf, gt.to_ber_string().as_bytes());
+        }
+        BuiltinValue::ObjectIdentifier(oid) => {
+            // the contents octets of the BER encoding, prefixed by their length
+            let mut contents = Vec::new();
+            ber::ber_encode_object_identifier(&mut contents, context, oid)?;
+            contents.reverse();
+            write_length_prefixed_bytes(encoder.buf, &contents);
+        }
+        BuiltinValue::RelativeOid(oid) => {
+            let mut contents = Vec::new();
+            ber::ber_encode_relative_oid(&mut contents, context, oid)?;
+            contents.reverse();
+            write_length_prefixed_bytes(encoder.buf, &contents);
+        }
+        BuiltinValue::Enumerated(enumerated) => {
+            oer_encode_enumerated(encoder, enumerated)?;
+        }
+        BuiltinValue::Structure(_, structure) => match &typed_value.ty.ty {
+            BuiltinType::Structure(structure_type) => {
+                oer_encode_structure(encoder, structure_type, structure)?;
+            }
+            _ => unreachable!(),
+        },
+        BuiltinValue::Choice(choice) => match &typed_value.ty.ty {
+            BuiltinType::Choice(choice_type) => {
+                oer_encode_choice(encoder, choice_type, choice)?;
+            }
+            _ => unreachable!(),
+        },
+        BuiltinValue::StructureOf(tag_type, seq_of) => {
+            let sorted;
+            let seq_of = if *tag_type == TagType::Set && encoder.kind.is_canonical() {
+                sorted = sort_set_of(encoder, seq_of)?;
+                &sorted
+            } else {
+                seq_of
+            };
+
+            // the quantity field is the number of elements as a length-prefixed unsigned integer
+            let quantity = BigInt::from(seq_of.len()).to_bytes_be().1;
+            write_length_prefixed_bytes(encoder.buf, &quantity);
+            for element in seq_of {
+                let element = element.resolve(context)?;
+                oer_encode_value(encoder, &element)?;
+            }
+        }
+        BuiltinValue::Containing(containing) => {
+            // without ENCODED BY, the contained value is encoded with the same encoding rules
+            let data = encode_contained_value(
+                context,
+                encoder.options,
+                &typed_value.ty,
+                containing,
+                &TransferSyntax::Octet(encoder.kind),
+            )?;
+            let value = match containing.container_type {
+                TagType::BitString => BuiltinValue::BitString(BitStringValue {
+                    data,
+                    unused_bits: 0,
+                }),
+                _ => BuiltinValue::OctetString(data),
+            };
+            oer_encode_value(
+                encoder,
+                &ResolvedValue {
+                    ty: typed_value.ty.clone(),
+                    value,
+                },
+            )?;
+        }
+        BuiltinValue::Time(_)
+        | BuiltinValue::Date(_)
+        | BuiltinValue::TimeOfDay(_)
+        | BuiltinValue::DateTime(_)
+        | BuiltinValue::Duration(_) => {
+            return Err(Error {
+                kind: ErrorKind::Ast(format!(
+                    "OER encoding of {} values is not yet supported",
+                    typed_value.ty.ty
+                )),
+                loc: Loc::default(),
+            });
+        }
+        BuiltinValue::RealLiteral(_) => unreachable!(),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        compiler::{
+            test::{compile_module, json_test},
+            Context,
+        },
+        encoding::*,
+        module::{ModuleIdentifier, QualifiedIdentifier},
+        values::ValueResolve,
+    };
+
+    #[test]
+    fn test_oer_unsupported_time() {
+        let mut context = Context::new();
+        compile_module(
+            &mut context,
+            "OerTimeTest.asn",
+            include_str!("../../../test-data/encode/oer/OerTimeTest.asn"),
+        );
+
+        let syntax = TransferSyntax::Octet(OctetEncodingKind::Basic);
+        let encoder = syntax.get_codec().encoder.expect("encoder");
+        for name in ["date", "time-of-day", "date-time", "duration"] {
+            let ident = QualifiedIdentifier::new(
+                ModuleIdentifier::with_name("OerTimeTest".to_string()),
+                name.to_string(),
+            );
+            let value = context
+                .lookup_value(&ident)
+                .expect("lookup_value")
+                .value
+                .resolve(&context)
+                .expect("resolve");
+            let mut buf = Vec::new();
+            let result = encoder(
+                &syntax,
+                EncodeMode::Normal,
+                &EncodeOptions::default(),
+                &mut buf,
+                &context,
+                &value,
+            );
+            assert!(result.is_err(), "value = {}", name);
+        }
+    }
+
+    json_test!(
+        test_oer_integer,
+        "../../../test-data/encode/oer/OerIntegerTest"
+    );
+    json_test!(
+        test_oer_enumerated,
+        "../../../test-data/encode/oer/OerEnumeratedTest"
+    );
+    json_test!(
+        test_oer_bit_string,
+        "../../../test-data/encode/oer/OerBitStringTest"
+    );
+    json_test!(
+        test_oer_octet_string,
+        "../../../test-data/encode/oer/OerOctetStringTest"
+    );
+    json_test!(
+        test_oer_character_string,
+        "../../../test-data/encode/oer/OerCharacterStringTest"
+    );
+    json_test!(
+        test_oer_object_identifier,
+        "../../../test-data/encode/oer/OerObjectIdentifierTest"
+    );
+    json_test!(
+        test_oer_structure,
+        "../../../test-data/encode/oer/OerStructureTest"
+    );
+    json_test!(
+        test_oer_choice,
+        "../../../test-data/encode/oer/OerChoiceTest"
+    );
+    json_test!(test_oer_time, "../../../test-data/encode/oer/OerTimeTest");
+    json_test!(
+        test_oer_contents_constraint,
+        "../../../test-data/encode/oer/OerContentsConstraintTest"
+    );
+}
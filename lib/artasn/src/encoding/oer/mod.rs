@@ -0,0 +1,92 @@
+mod encode;
+pub use encode::*;
+
+mod decode;
+pub use decode::*;
+
+use num::{BigInt, Signed};
+
+use crate::{
+    compiler::{parser::Result, Context},
+    encoding::{EncodeOptions, OctetEncodingKind},
+    types::*,
+};
+
+pub(crate) struct OerEncoder<'a> {
+    pub context: &'a Context,
+    pub kind: OctetEncodingKind,
+    pub options: &'a EncodeOptions,
+    pub buf: &'a mut Vec<u8>,
+}
+
+pub(crate) struct OerDecoder<'a> {
+    pub context: &'a Context,
+    pub kind: OctetEncodingKind,
+    pub buf: &'a [u8],
+    pub pos: usize,
+}
+
+/// Returns the bounds of the value constraint of the INTEGER type that are visible to OER.
+/// Extensible constraints are not visible to OER, so an INTEGER with one is encoded as if it is unconstrained.
+fn get_integer_bounds(
+    context: &Context,
+    resolved_type: &ResolvedType,
+) -> Result<(Option<BigInt>, Option<BigInt>)> {
+    let constraint = match &resolved_type.constraint {
+        Some(constraint) => constraint.resolve(context, &resolved_type.ty)?,
+        None => return Ok((None, None)),
+    };
+    if constraint.is_extensible(None) {
+        return Ok((None, None));
+    }
+    Ok(match constraint.integer_value_bounds()? {
+        Some(bounds) => {
+            let min = match bounds.lower_bound {
+                Bound::Integer(int) => Some(int),
+                Bound::Unbounded => None,
+            };
+            let max = match bounds.upper_bound {
+                Some(Bound::Integer(int)) => Some(int),
+                None | Some(Bound::Unbounded) => None,
+            };
+            (min, max)
+        }
+        None => (None, None),
+    })
+}
+
+/// Returns the number of octets of the smallest fixed-size encoding that can hold all values of the range,
+/// or `None` if the range requires a variable-size encoding.
+fn get_fixed_integer_size(min: &BigInt, max: &BigInt) -> Option<usize> {
+    [1, 2, 4, 8].into_iter().find(|size| {
+        let bits = size * 8;
+        if min.is_negative() {
+            let limit = BigInt::from(1) << (bits - 1);
+            *min >= -&limit && *max < limit
+        } else {
+            *max < BigInt::from(1) << bits
+        }
+    })
+}
+
+/// Returns the size of the type if it has a SIZE constraint that permits exactly one size, and is not extensible.
+pub(crate) fn get_fixed_size(
+    context: &Context,
+    resolved_type: &ResolvedType,
+) -> Result<Option<u64>> {
+    let constraint = match &resolved_type.constraint {
+        Some(constraint) => constraint.resolve(context, &resolved_type.ty)?,
+        None => return Ok(None),
+    };
+    if constraint.has_value_constraint() || constraint.is_extensible(None) {
+        return Ok(None);
+    }
+    Ok(match constraint.size_bounds()? {
+        Some(ConstraintBounds {
+            lower_bound: Bound::Integer(lower_bound),
+            upper_bound: Some(Bound::Integer(upper_bound)),
+            ..
+        }) if lower_bound == upper_bound => lower_bound.try_into().ok(),
+        _ => None,
+    })
+}
@@ -0,0 +1,763 @@
+use num::{bigint::Sign, BigInt};
+
+use crate::{
+    compiler::{
+        parser::{Error, ErrorKind},
+        Context,
+    },
+    encoding::*,
+    module::QualifiedIdentifier,
+    types::*,
+    values::*,
+};
+
+use super::{
+    get_alternative_order, get_component_order, get_enumeration_order, get_size_upper_bound,
+    Alignment, BitReader, KnownMultiplierAlphabet, LengthDeterminantKind, PerDecoder,
+};
+
+fn decoder_error<T>(reader: &BitReader<'_>, message: impl Into<String>) -> DecodeResult<T> {
+    Err(DecodeError::Decoder {
+        message: message.into(),
+        pos: TlvPos::new(reader.byte_pos(), reader.end_byte_pos()),
+    })
+}
+
+fn read_int(
+    reader: &mut BitReader<'_>,
+    range_low: u64,
+    range_high: u64,
+    alignment: Alignment,
+) -> DecodeResult<u64> {
+    let max_encoded_value = range_high - range_low;
+    let bit_count = u64::BITS - max_encoded_value.leading_zeros();
+
+    let int = reader
+        .read_int(bit_count, alignment)
+        .map_err(DecodeError::Io)?;
+    Ok(int + range_low)
+}
+
+/// The number of items in a length determinant fragment unit (X.691 clause 11.9.3.8).
+const FRAGMENT_SIZE: u64 = 0x4000;
+
+/// Reads an unbounded length determinant.
+/// If it is a fragment header, the number of items in the fragment is returned with [`LengthDeterminantKind::Fragmented`].
+fn read_per_vlq(reader: &mut BitReader<'_>) -> DecodeResult<(u64, LengthDeterminantKind)> {
+    reader.align();
+
+    let first = reader.read_byte().map_err(DecodeError::Io)?;
+    if first & 0x80 == 0 {
+        Ok((first as u64, LengthDeterminantKind::VariableSize))
+    } else if first & 0x40 == 0 {
+        let lsb = reader.read_byte().map_err(DecodeError::Io)?;
+        Ok((
+            (((first & 0x3f) as u64) << 8) | lsb as u64,
+            LengthDeterminantKind::VariableSize,
+        ))
+    } else {
+        let multiplier = (first & 0x3f) as u64;
+        if !(1..=4).contains(&multiplier) {
+            return decoder_error(reader, format!("invalid fragment header 0x{:02X}", first));
+        }
+        Ok((
+            multiplier * FRAGMENT_SIZE,
+            LengthDeterminantKind::Fragmented,
+        ))
+    }
+}
+
+/// Reads the items that follow a length determinant with `read_items`, which is called with the number of items to read.
+/// If the length determinant is a fragment header, the fragments are reassembled by reading
+/// the length determinant following each fragment, until one is not a fragment header (X.691 clause 11.9.3.8).
+///
+/// Returns the total number of items.
+fn read_items<'a>(
+    decoder: &mut PerDecoder<'a>,
+    (mut len, mut determinant_kind): (u64, LengthDeterminantKind),
+    mut read_items: impl FnMut(&mut PerDecoder<'a>, u64) -> DecodeResult<()>,
+) -> DecodeResult<u64> {
+    let mut total = 0;
+    loop {
+        read_items(decoder, len)?;
+        total += len;
+        match determinant_kind {
+            LengthDeterminantKind::Fragmented => {
+                (len, determinant_kind) = read_per_vlq(&mut decoder.reader)?;
+            }
+            _ => return Ok(total),
+        }
+    }
+}
+
+/// Reads octets prefixed by their unbounded length, reassembling them if they are fragmented.
+fn read_length_prefixed_bytes(decoder: &mut PerDecoder<'_>) -> DecodeResult<Vec<u8>> {
+    let determinant = read_per_vlq(&mut decoder.reader)?;
+    let mut bytes = Vec::with_capacity(determinant.0 as usize);
+    read_items(decoder, determinant, |decoder, len| {
+        decoder
+            .reader
+            .read_bytes_to(len as usize, &mut bytes)
+            .map_err(DecodeError::Io)
+    })?;
+    Ok(bytes)
+}
+
+fn read_length_determinant(
+    reader: &mut BitReader<'_>,
+    length_range: (Option<u64>, Option<u64>),
+) -> DecodeResult<(u64, LengthDeterminantKind)> {
+    let (length_min, length_max) = length_range;
+    Ok(match (length_min, length_max) {
+        (Some(min), Some(max)) if min == max => (min, LengthDeterminantKind::Const),
+        (_, None) => read_per_vlq(reader)?,
+        (low, Some(high @ ..65536)) => (
+            read_int(reader, low.unwrap_or(0), high, Alignment::None)?,
+            LengthDeterminantKind::FixedSize,
+        ),
+        (_, Some(65536..)) => read_per_vlq(reader)?,
+    })
+}
+
+fn read_size_determinant(
+    decoder: &mut PerDecoder<'_>,
+    resolved_type: &ResolvedType,
+) -> DecodeResult<(u64, LengthDeterminantKind)> {
+    let (mut size_bounds, has_extension_bit) = match &resolved_type.constraint {
+        Some(constraint) => {
+            let constraint = constraint
+                .resolve(decoder.context, &resolved_type.ty)
+                .map_err(DecodeError::Parser)?;
+            if !constraint.has_value_constraint() {
+                let size_bounds = match constraint.size_bounds().map_err(DecodeError::Parser)? {
+                    Some(bounds) => {
+                        let min = match bounds.lower_bound {
+                            Bound::Integer(int) => int.try_into().ok(),
+                            Bound::Unbounded => None,
+                        };
+                        let max = match bounds.upper_bound {
+                            Some(Bound::Integer(int)) => int.try_into().ok(),
+                            None | Some(Bound::Unbounded) => None,
+                        };
+                        (min, max)
+                    }
+                    None => (None, None),
+                };
+                // the encoder only writes the extension bit when the type has a SIZE constraint
+                let has_size_constraint = constraint.specs.iter().any(|spec| {
+                    spec.items
+                        .iter()
+                        .any(|item| matches!(item, ConstraintSpecItem::Size(_)))
+                });
+                let has_extension_bit = has_size_constraint && constraint.is_extensible(None);
+                (size_bounds, has_extension_bit)
+            } else {
+                ((None, None), false)
+            }
+        }
+        None => ((None, None), false),
+    };
+    if has_extension_bit {
+        let is_extension = decoder.reader.read_bit().map_err(DecodeError::Io)?;
+        if is_extension {
+            decoder.reader.align();
+
+            // sizes outside of the extension root are encoded as if there are no size bounds
+            size_bounds = (None, None);
+        }
+    }
+
+    read_length_determinant(&mut decoder.reader, size_bounds)
+}
+
+fn read_length_prefixed_integer(
+    decoder: &mut PerDecoder<'_>,
+    range_low: Option<&BigInt>,
+) -> DecodeResult<BigInt> {
+    let bytes = read_length_prefixed_bytes(decoder)?;
+    if bytes.is_empty() {
+        return decoder_error(&decoder.reader, "INTEGER must have a value");
+    }
+
+    Ok(match range_low {
+        Some(range_low) => BigInt::from_bytes_be(Sign::Plus, &bytes) + range_low,
+        None => BigInt::from_signed_bytes_be(&bytes),
+    })
+}
+
+fn read_constrained_integer(
+    decoder: &mut PerDecoder<'_>,
+    range_low: &BigInt,
+    range_high: &BigInt,
+) -> DecodeResult<BigInt> {
+    let max_encoded_value = range_high - range_low;
+    let bit_count = max_encoded_value.bits();
+
+    // X.691 clause 13, note 1
+    let alignment = match bit_count {
+        ..8 => Alignment::None,
+        8..16 => Alignment::End,
+        16.. => return read_length_prefixed_integer(decoder, Some(range_low)),
+    };
+
+    let int = decoder
+        .reader
+        .read_bigint(bit_count, alignment)
+        .map_err(DecodeError::Io)?;
+    Ok(int + range_low)
+}
+
+fn per_decode_integer(
+    decoder: &mut PerDecoder<'_>,
+    resolved_type: &ResolvedType,
+) -> DecodeResult<BigInt> {
+    let (mut value_bounds, has_extension_bit) = match &resolved_type.constraint {
+        Some(constraint) => {
+            let resolved_constraint = constraint
+                .resolve(decoder.context, &resolved_type.ty)
+                .map_err(DecodeError::Parser)?;
+
+            let value_bounds = match resolved_constraint
+                .integer_value_bounds()
+                .map_err(DecodeError::Parser)?
+            {
+                Some(bounds) => {
+                    let min = match bounds.lower_bound {
+                        Bound::Integer(int) => Some(int),
+                        Bound::Unbounded => None,
+                    };
+                    let max = match bounds.upper_bound {
+                        Some(Bound::Integer(int)) => Some(int),
+                        None | Some(Bound::Unbounded) => None,
+                    };
+                    (min, max)
+                }
+                None => (None, None),
+            };
+            // the encoder only writes the extension bit when the type has a value constraint
+            let has_extension_bit = resolved_constraint.has_value_constraint()
+                && resolved_constraint.is_extensible(None);
+            (value_bounds, has_extension_bit)
+        }
+        None => ((None, None), false),
+    };
+    if has_extension_bit {
+        let is_extension = decoder.reader.read_bit().map_err(DecodeError::Io)?;
+        if is_extension {
+            decoder.reader.align();
+
+            // values outside of the extension root are encoded as if there are no value bounds
+            value_bounds = (None, None);
+        }
+    }
+
+    match value_bounds {
+        (Some(min), Some(max)) => read_constrained_integer(decoder, &min, &max),
+        (min, _) => read_length_prefixed_integer(decoder, min.as_ref()),
+    }
+}
+
+fn per_decode_bit_string(
+    decoder: &mut PerDecoder<'_>,
+    resolved_type: &ResolvedType,
+) -> DecodeResult<BitStringValue> {
+    let (len, determinant_kind) = read_size_determinant(decoder, resolved_type)?;
+    match determinant_kind {
+        LengthDeterminantKind::Const => (),
+        _ => {
+            if len > 0 {
+                decoder.reader.align();
+            }
+        }
+    }
+
+    let mut data = Vec::with_capacity(len.div_ceil(8) as usize);
+    let mut bit_index = 0u64;
+    let total_bits = read_items(decoder, (len, determinant_kind), |decoder, len| {
+        data.resize((bit_index + len).div_ceil(8) as usize, 0);
+        for _ in 0..len {
+            if decoder.reader.read_bit().map_err(DecodeError::Io)? {
+                let byte_index = (bit_index / 8) as usize;
+                data[byte_index] |= 0x80 >> (bit_index % 8);
+            }
+            bit_index += 1;
+        }
+        Ok(())
+    })?;
+    Ok(BitStringValue {
+        data,
+        unused_bits: ((8 - total_bits % 8) % 8) as u8,
+    })
+}
+
+fn per_decode_octet_string(
+    decoder: &mut PerDecoder<'_>,
+    resolved_type: &ResolvedType,
+) -> DecodeResult<Vec<u8>> {
+    let (len, determinant_kind) = read_size_determinant(decoder, resolved_type)?;
+    match determinant_kind {
+        LengthDeterminantKind::Const => (),
+        _ => {
+            if len > 0 {
+                decoder.reader.align();
+            }
+        }
+    }
+
+    let mut bytes = Vec::with_capacity(len as usize);
+    read_items(decoder, (len, determinant_kind), |decoder, len| {
+        decoder
+            .reader
+            .read_bytes_to(len as usize, &mut bytes)
+            .map_err(DecodeError::Io)
+    })?;
+    Ok(bytes)
+}
+
+fn get_component_mode(
+    context: &Context,
+    component_name: Option<String>,
+    component_type: &TaggedType,
+) -> DecodeResult<DecodeMode> {
+    Ok(DecodeMode::SpecificType {
+        source_ident: match &component_type.ty {
+            UntaggedType::BuiltinType(_) => None,
+            UntaggedType::Reference(typeref) => Some(typeref.element.clone()),
+            UntaggedType::ObjectClassField(ocf) => {
+                return Err(DecodeError::Parser(Error {
+                    kind: ErrorKind::Ast(format!(
+                        "decoding a component of the type {} is not yet supported",
+                        ocf
+                    )),
+                    loc: ocf.field.loc,
+                }))
+            }
+        },
+        component_name,
+        resolved: Box::new(
+            component_type
+                .resolve(context)
+                .map_err(DecodeError::Parser)?,
+        ),
+    })
+}
+
+/// Reads the preamble of the OPTIONAL and DEFAULT components (X.691 clause 19.2),
+/// followed by the values of the components that are present.
+fn read_components(
+    decoder: &mut PerDecoder<'_>,
+    structure: &Structure,
+    order: &[usize],
+    components: &mut Vec<DecodedValue>,
+) -> DecodeResult<()> {
+    let context = decoder.context;
+
+    let mut is_present = Vec::with_capacity(order.len());
+    for index in order {
+        let component = &structure.components[*index];
+        is_present.push(if component.optional || component.default_value.is_some() {
+            decoder.reader.read_bit().map_err(DecodeError::Io)?
+        } else {
+            true
+        });
+    }
+
+    for (index, is_present) in order.iter().zip(is_present) {
+        if is_present {
+            let component = &structure.components[*index];
+            let mode = get_component_mode(
+                context,
+                Some(component.name.element.clone()),
+                &component.component_type,
+            )?;
+            components.push(per_decode_value(decoder, &mode)?);
+        }
+    }
+    Ok(())
+}
+
+fn per_decode_structure(
+    decoder: &mut PerDecoder<'_>,
+    structure: &Structure,
+) -> DecodeResult<Vec<DecodedValue>> {
+    let context = decoder.context;
+    let (root, additions) = get_component_order(context, structure).map_err(DecodeError::Parser)?;
+
+    let has_additions =
+        structure.extensible && decoder.reader.read_bit().map_err(DecodeError::Io)?;
+
+    let mut components = Vec::with_capacity(structure.components.len());
+    read_components(decoder, structure, &root, &mut components)?;
+
+    if has_additions {
+        // X.691 clause 19.8: a bit-map of the extension additions that are present, prefixed by its length
+        let addition_count = read_normally_small_length(decoder)?;
+        let mut is_present = Vec::with_capacity(addition_count as usize);
+        for _ in 0..addition_count {
+            is_present.push(decoder.reader.read_bit().map_err(DecodeError::Io)?);
+        }
+
+        for (index, is_present) in is_present.into_iter().enumerate() {
+            if !is_present {
+                continue;
+            }
+            match additions.get(index) {
+                Some(addition) => {
+                    let component = &structure.components[addition[0]];
+                    let is_group = component
+                        .extension_addition
+                        .is_some_and(|addition| addition.is_group);
+                    if is_group {
+                        read_open_type(decoder, |decoder| {
+                            read_components(decoder, structure, addition, &mut components)
+                        })?;
+                    } else {
+                        let mode = get_component_mode(
+                            context,
+                            Some(component.name.element.clone()),
+                            &component.component_type,
+                        )?;
+                        components.push(read_open_type(decoder, |decoder| {
+                            per_decode_value(decoder, &mode)
+                        })?);
+                    }
+                }
+                // extension additions that are not defined in this version of the type are skipped
+                None => read_open_type(decoder, |_| Ok(()))?,
+            }
+        }
+    }
+
+    Ok(components)
+}
+
+fn per_decode_structure_of(
+    decoder: &mut PerDecoder<'_>,
+    resolved_type: &ResolvedType,
+    component_type: &TaggedType,
+) -> DecodeResult<Vec<DecodedValue>> {
+    let context = decoder.context;
+    let determinant = read_size_determinant(decoder, resolved_type)?;
+
+    let mode = get_component_mode(context, None, component_type)?;
+    let mut elements = Vec::new();
+    read_items(decoder, determinant, |decoder, len| {
+        for _ in 0..len {
+            elements.push(per_decode_value(decoder, &mode)?);
+        }
+        Ok(())
+    })?;
+    Ok(elements)
+}
+
+/// Reads a normally small non-negative whole number (X.691 clause 10.6).
+fn read_normally_small_number(decoder: &mut PerDecoder<'_>) -> DecodeResult<u64> {
+    let is_large = decoder.reader.read_bit().map_err(DecodeError::Io)?;
+    if !is_large {
+        return decoder
+            .reader
+            .read_int(6, Alignment::None)
+            .map_err(DecodeError::Io);
+    }
+
+    let n = read_length_prefixed_integer(decoder, Some(&BigInt::from(0)))?;
+    match u64::try_from(n) {
+        Ok(n) => Ok(n),
+        Err(_) => decoder_error(&decoder.reader, "normally small number is too large"),
+    }
+}
+
+/// Reads a normally small length (X.691 clause 11.9.3.4).
+fn read_normally_small_length(decoder: &mut PerDecoder<'_>) -> DecodeResult<u64> {
+    let is_large = decoder.reader.read_bit().map_err(DecodeError::Io)?;
+    if !is_large {
+        let n = decoder
+            .reader
+            .read_int(6, Alignment::None)
+            .map_err(DecodeError::Io)?;
+        return Ok(n + 1);
+    }
+
+    match read_per_vlq(&mut decoder.reader)? {
+        (n, LengthDeterminantKind::VariableSize) => Ok(n),
+        _ => decoder_error(&decoder.reader, "normally small length is too large"),
+    }
+}
+
+/// Reads an open type field (X.691 clause 10.2), and decodes its contents as a complete encoding with `decode`.
+fn read_open_type<'a, T>(
+    decoder: &mut PerDecoder<'a>,
+    decode: impl FnOnce(&mut PerDecoder<'_>) -> DecodeResult<T>,
+) -> DecodeResult<T> {
+    let determinant = read_per_vlq(&mut decoder.reader)?;
+    let start = decoder.reader.byte_pos();
+    let mut buf = Vec::with_capacity(determinant.0 as usize);
+    read_items(decoder, determinant, |decoder, len| {
+        decoder
+            .reader
+            .read_bytes_to(len as usize, &mut buf)
+            .map_err(DecodeError::Io)
+    })?;
+
+    let mut open_type_decoder = PerDecoder {
+        context: decoder.context,
+        kind: decoder.kind,
+        reader: BitReader::with_offset(decoder.kind.is_aligned(), &buf, start),
+    };
+    decode(&mut open_type_decoder)
+}
+
+/// Decodes the chosen alternative of the CHOICE.
+/// Like in BER, the `DecodedValue` of a CHOICE is the value of its alternative, with the metadata of the CHOICE.
+fn per_decode_choice(
+    decoder: &mut PerDecoder<'_>,
+    choice: &Choice,
+    source_ident: &Option<QualifiedIdentifier>,
+    component_name: &Option<String>,
+) -> DecodeResult<DecodedValue> {
+    let context = decoder.context;
+    let (root, extensions) = get_alternative_order(context, choice).map_err(DecodeError::Parser)?;
+
+    let is_extension = choice.extensible && decoder.reader.read_bit().map_err(DecodeError::Io)?;
+    let index = if is_extension {
+        let extension_index = read_normally_small_number(decoder)?;
+        match extensions.get(extension_index as usize) {
+            Some(index) => *index,
+            None => {
+                return decoder_error(
+                    &decoder.reader,
+                    format!(
+                        "CHOICE extension alternative {} is not defined",
+                        extension_index
+                    ),
+                )
+            }
+        }
+    } else {
+        let root_index = if root.len() > 1 {
+            read_constrained_integer(decoder, &BigInt::from(0), &BigInt::from(root.len() - 1))?
+        } else {
+            BigInt::from(0)
+        };
+        match usize::try_from(&root_index)
+            .ok()
+            .and_then(|index| root.get(index))
+        {
+            Some(index) => *index,
+            None => {
+                return decoder_error(
+                    &decoder.reader,
+                    format!("CHOICE alternative {} is not defined", root_index),
+                )
+            }
+        }
+    };
+
+    let alternative = &choice.alternatives[index];
+    let mode = DecodeMode::SpecificType {
+        source_ident: source_ident.clone(),
+        component_name: component_name.clone(),
+        resolved: Box::new(
+            alternative
+                .alternative_type
+                .resolve(context)
+                .map_err(DecodeError::Parser)?,
+        ),
+    };
+    if is_extension {
+        read_open_type(decoder, |decoder| per_decode_value(decoder, &mode))
+    } else {
+        per_decode_value(decoder, &mode)
+    }
+}
+
+/// Reads a length-prefixed field containing the contents octets of a BER encoding,
+/// and decodes them as a value of the given universal type.
+fn read_ber_contents(
+    decoder: &mut PerDecoder<'_>,
+    tag_type: TagType,
+) -> DecodeResult<DecodedValueKind> {
+    let buf = read_length_prefixed_bytes(decoder)?;
+    ber::ber_decode_universal(BasicEncodingKind::Basic, &buf, tag_type).map_err(DecodeError::Io)
+}
+
+fn per_decode_character_string(
+    decoder: &mut PerDecoder<'_>,
+    resolved_type: &ResolvedType,
+    tag_type: TagType,
+) -> DecodeResult<DecodedValueKind> {
+    let context = decoder.context;
+    let alphabet = match KnownMultiplierAlphabet::new(
+        context,
+        resolved_type,
+        tag_type,
+        decoder.kind.is_aligned(),
+    )
+    .map_err(DecodeError::Parser)?
+    {
+        Some(alphabet) => alphabet,
+        None => return read_ber_contents(decoder, tag_type),
+    };
+
+    let (len, determinant_kind) = read_size_determinant(decoder, resolved_type)?;
+    let is_octet_aligned = match determinant_kind {
+        LengthDeterminantKind::VariableSize | LengthDeterminantKind::Fragmented => true,
+        LengthDeterminantKind::Const | LengthDeterminantKind::FixedSize => alphabet
+            .is_octet_aligned(
+                get_size_upper_bound(context, resolved_type).map_err(DecodeError::Parser)?,
+            ),
+    };
+    if is_octet_aligned && len > 0 {
+        decoder.reader.align();
+    }
+
+    let mut str = String::with_capacity(len as usize);
+    read_items(decoder, (len, determinant_kind), |decoder, len| {
+        for _ in 0..len {
+            let value = decoder
+                .reader
+                .read_int(alphabet.char_bits, Alignment::None)
+                .map_err(DecodeError::Io)?;
+            match alphabet.decode_char(value) {
+                Some(ch) => str.push(ch),
+                None => {
+                    return decoder_error(
+                        &decoder.reader,
+                        format!(
+                            "{} contains a character outside of its permitted alphabet",
+                            tag_type
+                        ),
+                    )
+                }
+            }
+        }
+        Ok(())
+    })?;
+    Ok(DecodedValueKind::CharacterString(tag_type, str))
+}
+
+fn per_decode_enumerated(
+    decoder: &mut PerDecoder<'_>,
+    enumerated: &EnumeratedType,
+) -> DecodeResult<i64> {
+    let (root, extensions) =
+        get_enumeration_order(decoder.context, enumerated).map_err(DecodeError::Parser)?;
+
+    let is_extension =
+        enumerated.extensible && decoder.reader.read_bit().map_err(DecodeError::Io)?;
+    let value = if is_extension {
+        let index = read_normally_small_number(decoder)?;
+        extensions.get(index as usize)
+    } else {
+        let index = if root.len() > 1 {
+            read_constrained_integer(decoder, &BigInt::from(0), &BigInt::from(root.len() - 1))?
+        } else {
+            BigInt::from(0)
+        };
+        usize::try_from(&index)
+            .ok()
+            .and_then(|index| root.get(index))
+    };
+    match value.and_then(|value| i64::try_from(value).ok()) {
+        Some(value) => Ok(value),
+        None => decoder_error(&decoder.reader, "ENUMERATED item is not defined"),
+    }
+}
+
+/// Decodes a single PER value of the type specified by the `DecodeMode`.
+/// PER encodings do not contain tags or lengths, so the `DecodedValue` has the tag of the type,
+/// and the positions of its tag and length are empty.
+pub fn per_decode_value(
+    decoder: &mut PerDecoder<'_>,
+    mode: &DecodeMode,
+) -> DecodeResult<DecodedValue> {
+    let (source_ident, component_name, resolved) = match mode {
+        DecodeMode::Contextless => {
+            return decoder_error(&decoder.reader, "PER can only be decoded with a known type")
+        }
+        DecodeMode::SpecificType {
+            source_ident,
+            component_name,
+            resolved,
+        } => (source_ident, component_name, resolved),
+    };
+
+    if let BuiltinType::Choice(choice) = &resolved.ty {
+        return per_decode_choice(decoder, choice, source_ident, component_name);
+    }
+
+    let start = decoder.reader.byte_pos();
+    let form = match &resolved.ty {
+        BuiltinType::Boolean => DecodedValueForm::Primitive(DecodedValueKind::Boolean(
+            decoder.reader.read_bit().map_err(DecodeError::Io)?,
+        )),
+        BuiltinType::Integer(_) => DecodedValueForm::Primitive(DecodedValueKind::Integer(
+            per_decode_integer(decoder, resolved)?,
+        )),
+        BuiltinType::BitString(_) => DecodedValueForm::Primitive(DecodedValueKind::BitString(
+            per_decode_bit_string(decoder, resolved)?,
+        )),
+        BuiltinType::OctetString => DecodedValueForm::Primitive(DecodedValueKind::OctetString(
+            per_decode_octet_string(decoder, resolved)?,
+        )),
+        BuiltinType::Null => DecodedValueForm::Primitive(DecodedValueKind::Null),
+        BuiltinType::CharacterString(tag_type) => {
+            DecodedValueForm::Primitive(per_decode_character_string(decoder, resolved, *tag_type)?)
+        }
+        BuiltinType::ObjectIdentifier => {
+            DecodedValueForm::Primitive(read_ber_contents(decoder, TagType::ObjectIdentifier)?)
+        }
+        BuiltinType::RelativeOid => {
+            DecodedValueForm::Primitive(read_ber_contents(decoder, TagType::RelativeOid)?)
+        }
+        ty if ber::is_real_type(ty) => {
+            DecodedValueForm::Primitive(read_ber_contents(decoder, TagType::Real)?)
+        }
+        BuiltinType::Enumerated(enumerated) => DecodedValueForm::Primitive(
+            DecodedValueKind::Enumerated(per_decode_enumerated(decoder, enumerated)?),
+        ),
+        BuiltinType::Structure(structure) => {
+            DecodedValueForm::Constructed(per_decode_structure(decoder, structure)?)
+        }
+        BuiltinType::StructureOf(of) => DecodedValueForm::Constructed(per_decode_structure_of(
+            decoder,
+            resolved,
+            &of.component_type,
+        )?),
+        other => {
+            return decoder_error(
+                &decoder.reader,
+                format!("PER decoding is not supported for {}", other),
+            )
+        }
+    };
+    let end = decoder.reader.end_byte_pos();
+
+    let tag = match &resolved.tag {
+        Some(tag) => TlvTag {
+            class: tag.class,
+            form: match form {
+                DecodedValueForm::Primitive(_) => TypeForm::Primitive,
+                DecodedValueForm::Constructed(_) => TypeForm::Constructed,
+            },
+            num: tag.num,
+        },
+        None => {
+            return decoder_error(
+                &decoder.reader,
+                format!("PER decoding is not supported for {}", resolved.ty),
+            )
+        }
+    };
+
+    Ok(DecodedValue {
+        tag: TlvElement::new(tag, TlvPos::new(start, start)),
+        len: TlvElement::new((end - start) as u32, TlvPos::new(start, start)),
+        value_pos: TlvPos::new(start, end),
+        form,
+        metadata: Some(DecodedValueMetadata {
+            type_ident: source_ident.clone(),
+            component_name: component_name.clone(),
+        }),
+    })
+}
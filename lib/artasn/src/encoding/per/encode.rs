@@ -1,14 +1,24 @@
-use std::io::Write;
+use std::{
+    io::{Cursor, Write},
+    ops::Range,
+};
 
 use num::BigInt;
 
 use crate::{
-    compiler::{parser::Result, Context},
+    compiler::{
+        parser::{AstElement, Error, ErrorKind, Loc, Result},
+        Context,
+    },
+    encoding::{ber, encode_contained_value, SetOrdering, TransferSyntax},
     types::*,
     values::*,
 };
 
-use super::{Alignment, BitWriter, PerEncoder};
+use super::{
+    get_alternative_order, get_component_order, get_enumeration_order, get_size_upper_bound,
+    Alignment, BitWriter, KnownMultiplierAlphabet, PerEncoder,
+};
 
 fn write_int<W: Write>(
     writer: &mut BitWriter<W>,
@@ -24,29 +34,47 @@ fn write_int<W: Write>(
     writer.write_int(int, bit_count, alignment);
 }
 
-fn write_per_vlq<W: Write>(writer: &mut BitWriter<W>, n: u64, max: Option<u64>) {
+/// The number of items in a length determinant fragment unit (X.691 clause 11.9.3.8).
+const FRAGMENT_SIZE: u64 = 0x4000;
+
+fn write_per_vlq<W: Write>(writer: &mut BitWriter<W>, n: u64) {
     writer.align();
 
-    let max = max.unwrap_or(n);
-    if max < 0x80 {
+    if n < 0x80 {
         writer.write_byte(n as u8);
-    } else if max < 0x4000 {
+    } else if n < FRAGMENT_SIZE {
         let msb = ((n >> 8) & 0xff) as u8;
         let lsb = (n & 0xff) as u8;
         writer.write_byte(0x80 | msb);
         writer.write_byte(lsb);
     } else {
-        todo!("encode fragmented values");
+        unreachable!("write_per_vlq on a length that requires fragmentation");
     }
 }
 
-enum LengthDeterminantKind {
+pub(crate) enum LengthDeterminantKind {
     /// No length determinant is encoded, because it is constrained to one value.
     Const,
     /// The length determinant is encoded as a fixed number of bits, because it is constrained to a range of values.
     FixedSize,
     /// The length determinant is encoded as a variable number of bits, because it is unbounded.
     VariableSize,
+    /// The length is at least 16K, so the items are split into fragments,
+    /// each of which is preceded by its own length determinant (X.691 clause 11.9.3.8).
+    Fragmented,
+}
+
+fn write_unbounded_length_determinant<W: Write>(
+    writer: &mut BitWriter<W>,
+    length: u64,
+) -> LengthDeterminantKind {
+    if length < FRAGMENT_SIZE {
+        write_per_vlq(writer, length);
+        LengthDeterminantKind::VariableSize
+    } else {
+        // the fragment headers are written along with the items by `write_items`
+        LengthDeterminantKind::Fragmented
+    }
 }
 
 fn write_length_determinant<W: Write>(
@@ -57,18 +85,12 @@ fn write_length_determinant<W: Write>(
     let (length_min, length_max) = length_range;
     match (length_min, length_max) {
         (Some(min), Some(max)) if min == max => LengthDeterminantKind::Const,
-        (_, None) => {
-            write_per_vlq(writer, length, None);
-            LengthDeterminantKind::VariableSize
-        }
+        (_, None) => write_unbounded_length_determinant(writer, length),
         (low, Some(high @ ..65536)) => {
             write_int(writer, length, low.unwrap_or(0), high, Alignment::None);
             LengthDeterminantKind::FixedSize
         }
-        (_, Some(length_max @ 65536..)) => {
-            write_per_vlq(writer, length, Some(length_max));
-            LengthDeterminantKind::VariableSize
-        }
+        (_, Some(65536..)) => write_unbounded_length_determinant(writer, length),
     }
 }
 
@@ -77,6 +99,7 @@ fn write_size_determinant<W: Write>(
     context: &Context,
     size: u64,
     resolved_type: &ResolvedType,
+    loc: Loc,
 ) -> Result<LengthDeterminantKind> {
     let (mut size_bounds, inclusion, is_extensible) = match &resolved_type.constraint {
         Some(constraint) => {
@@ -127,7 +150,13 @@ fn write_size_determinant<W: Write>(
                 }
             }
             IntegerInclusion::NotIncluded => {
-                panic!("per_encode_value on {} with illegal size", resolved_type.ty)
+                return Err(Error {
+                    kind: ErrorKind::Ast(format!(
+                        "size {} of {} value violates the size constraint of the type",
+                        size, resolved_type.ty
+                    )),
+                    loc,
+                });
             }
         }
     }
@@ -135,6 +164,59 @@ fn write_size_determinant<W: Write>(
     Ok(write_length_determinant(writer, size, size_bounds))
 }
 
+/// Writes `len` items with `write_items`, which is called with consecutive ranges of item indices.
+///
+/// If the length determinant is fragmented, the items are split into fragments of 16K, 32K, 48K, or 64K items,
+/// each preceded by a fragment header, and followed by a length determinant for the remaining items,
+/// which is zero if `len` is a multiple of 16K (X.691 clause 11.9.3.8).
+fn write_items<'a, W: Write>(
+    encoder: &mut PerEncoder<'a, W>,
+    len: u64,
+    determinant_kind: &LengthDeterminantKind,
+    mut write_items: impl FnMut(&mut PerEncoder<'a, W>, Range<u64>) -> Result<()>,
+) -> Result<()> {
+    let mut start = 0;
+    if let LengthDeterminantKind::Fragmented = determinant_kind {
+        loop {
+            let remaining = len - start;
+            if remaining < FRAGMENT_SIZE {
+                write_per_vlq(&mut encoder.writer, remaining);
+                break;
+            }
+
+            let multiplier = (remaining / FRAGMENT_SIZE).min(4);
+            encoder.writer.align();
+            encoder.writer.write_byte(0xc0 | multiplier as u8);
+
+            let end = start + multiplier * FRAGMENT_SIZE;
+            write_items(encoder, start..end)?;
+            start = end;
+        }
+    }
+
+    write_items(encoder, start..len)
+}
+
+/// Writes `bytes` prefixed by their unbounded length in octets.
+fn write_length_prefixed_bytes<W: Write>(encoder: &mut PerEncoder<'_, W>, bytes: &[u8]) {
+    let len = bytes.len() as u64;
+    let determinant_kind = write_unbounded_length_determinant(&mut encoder.writer, len);
+    write_items(encoder, len, &determinant_kind, |encoder, range| {
+        encoder
+            .writer
+            .write_bytes(&bytes[range.start as usize..range.end as usize]);
+        Ok(())
+    })
+    .expect("writing bytes is infallible");
+}
+
+/// Writes the contents of `tmp_buf` prefixed by their length in octets.
+fn write_length_prefixed_tmp_buf<W: Write>(encoder: &mut PerEncoder<'_, W>) {
+    let tmp_buf = std::mem::take(&mut encoder.tmp_buf);
+    write_length_prefixed_bytes(encoder, &tmp_buf);
+    encoder.tmp_buf = tmp_buf;
+}
+
 fn write_length_prefixed_integer<W: Write>(
     encoder: &mut PerEncoder<'_, W>,
     value: &BigInt,
@@ -147,8 +229,7 @@ fn write_length_prefixed_integer<W: Write>(
         None => value.to_signed_bytes_be(),
     });
 
-    write_per_vlq(&mut encoder.writer, tmp_buf.len() as u64, None);
-    encoder.writer.write_bytes(tmp_buf);
+    write_length_prefixed_tmp_buf(encoder);
 }
 
 fn write_constrained_integer<W: Write>(
@@ -178,6 +259,7 @@ fn per_encode_integer<W: Write>(
     encoder: &mut PerEncoder<'_, W>,
     resolved_type: &ResolvedType,
     value: &BigInt,
+    loc: Loc,
 ) -> Result<()> {
     let writer = &mut encoder.writer;
     let (mut size_bounds, inclusion, is_extensible) = match &resolved_type.constraint {
@@ -222,7 +304,13 @@ fn per_encode_integer<W: Write>(
                 }
             }
             IntegerInclusion::NotIncluded => {
-                panic!("per_encode_integer on INTEGER with illegal value")
+                return Err(Error {
+                    kind: ErrorKind::Ast(format!(
+                        "INTEGER value {} violates the value constraint of the type",
+                        value
+                    )),
+                    loc,
+                });
             }
         }
     }
@@ -236,25 +324,457 @@ fn per_encode_integer<W: Write>(
     Ok(())
 }
 
+/// Returns whether the component is omitted from the encoding because its value is equal to its DEFAULT value.
+/// CANONICAL-PER never encodes these components (X.691 clause 19.5).
+fn is_omitted_default_value<W: Write>(
+    encoder: &PerEncoder<'_, W>,
+    component: &StructureValueComponent,
+) -> Result<bool> {
+    if !encoder.kind.is_canonical() && encoder.options.encode_default_values {
+        return Ok(false);
+    }
+    if component.is_default {
+        return Ok(true);
+    }
+    match &component.default_value {
+        Some(default_value) => component.value.try_eq(encoder.context, default_value),
+        None => Ok(false),
+    }
+}
+
+/// Writes the preamble of the OPTIONAL and DEFAULT components (X.691 clause 19.2),
+/// followed by the values of the components that are present.
+fn write_components<W: Write>(
+    encoder: &mut PerEncoder<'_, W>,
+    components: &[(usize, &StructureComponent, Option<&StructureValueComponent>)],
+) -> Result<()> {
+    for (_, component, component_value) in components {
+        if component.optional || component.default_value.is_some() {
+            encoder.writer.write_bit(component_value.is_some());
+        }
+    }
+
+    for (_, _, component_value) in components {
+        if let Some(component_value) = component_value {
+            let loc = component_value.value.loc;
+            let component_value = component_value.value.resolve(encoder.context)?;
+            per_encode_value(encoder, &component_value, loc)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn per_encode_structure<W: Write>(
+    encoder: &mut PerEncoder<'_, W>,
+    structure_type: &Structure,
+    value: &StructureValue,
+) -> Result<()> {
+    let context = encoder.context;
+
+    let mut get_components = |order: Vec<usize>| {
+        let mut components = Vec::with_capacity(order.len());
+        for index in order {
+            let component = &structure_type.components[index];
+            let component_value = match value
+                .components
+                .iter()
+                .find(|value| value.name.element == component.name.element)
+            {
+                Some(component_value) if !is_omitted_default_value(encoder, component_value)? => {
+                    Some(component_value)
+                }
+                _ => None,
+            };
+            components.push((index, component, component_value));
+        }
+        Ok::<_, Error>(components)
+    };
+
+    let (root, additions) = get_component_order(context, structure_type)?;
+    let components = get_components(root)?;
+    let additions = additions
+        .into_iter()
+        .map(&mut get_components)
+        .collect::<Result<Vec<_>>>()?;
+
+    if structure_type.ty == TagType::Set && encoder.options.set_ordering == SetOrdering::Verify {
+        let present = components
+            .iter()
+            .filter_map(|(index, _, value)| value.map(|value| (*index, value)))
+            .collect::<Vec<_>>();
+        if let Some(window) = present.windows(2).find(|window| window[0].0 > window[1].0) {
+            return Err(Error {
+                kind: ErrorKind::Ast(format!(
+                    "SET component '{}' must be placed before component '{}' in canonical order",
+                    window[1].1.name.element, window[0].1.name.element,
+                )),
+                loc: window[1].1.name.loc,
+            });
+        }
+    }
+
+    // X.691 clause 19.1: the extension bit indicates whether any extension additions are present
+    let mut is_present = additions
+        .iter()
+        .map(|addition| addition.iter().any(|(_, _, value)| value.is_some()))
+        .collect::<Vec<_>>();
+    if encoder.kind.is_canonical() {
+        // CANONICAL-PER uses the minimal bit-map, which ends at the last extension addition that is present
+        let len = is_present
+            .iter()
+            .rposition(|is_present| *is_present)
+            .map_or(0, |index| index + 1);
+        is_present.truncate(len);
+    }
+    let has_additions = is_present.contains(&true);
+    if structure_type.extensible {
+        encoder.writer.write_bit(has_additions);
+    }
+
+    write_components(encoder, &components)?;
+
+    if has_additions {
+        // X.691 clause 19.8: a bit-map of the extension additions that are present, prefixed by its length
+        write_normally_small_length(encoder, is_present.len() as u64);
+        for is_present in &is_present {
+            encoder.writer.write_bit(*is_present);
+        }
+
+        // X.691 clause 19.9: each extension addition is encoded as an open type,
+        // where an extension addition group is encoded as a SEQUENCE of its components
+        for addition in additions {
+            let Some((_, component, component_value)) = addition.first() else {
+                continue;
+            };
+            let is_group = component
+                .extension_addition
+                .is_some_and(|addition| addition.is_group);
+            if is_group {
+                if addition.iter().any(|(_, _, value)| value.is_some()) {
+                    write_open_type(encoder, |encoder| write_components(encoder, &addition))?;
+                }
+            } else if let Some(component_value) = component_value {
+                let loc = component_value.value.loc;
+                let component_value = component_value.value.resolve(context)?;
+                write_open_type(encoder, |encoder| {
+                    per_encode_value(encoder, &component_value, loc)
+                })?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes a normally small length (X.691 clause 11.9.3.4), which is always at least one.
+fn write_normally_small_length<W: Write>(encoder: &mut PerEncoder<'_, W>, n: u64) {
+    if n <= 64 {
+        encoder.writer.write_bit(false);
+        encoder.writer.write_int(n - 1, 6, Alignment::None);
+    } else {
+        encoder.writer.write_bit(true);
+        write_per_vlq(&mut encoder.writer, n);
+    }
+}
+
+/// Writes a normally small non-negative whole number (X.691 clause 10.6).
+fn write_normally_small_number<W: Write>(encoder: &mut PerEncoder<'_, W>, n: u64) {
+    if n < 64 {
+        encoder.writer.write_bit(false);
+        encoder.writer.write_int(n, 6, Alignment::None);
+    } else {
+        encoder.writer.write_bit(true);
+        write_length_prefixed_integer(encoder, &BigInt::from(n), Some(&BigInt::from(0)));
+    }
+}
+
+/// Writes the complete encoding written by `encode` as an open type field (X.691 clause 10.2).
+fn write_open_type<W: Write>(
+    encoder: &mut PerEncoder<'_, W>,
+    encode: impl FnOnce(&mut PerEncoder<'_, Cursor<&mut Vec<u8>>>) -> Result<()>,
+) -> Result<()> {
+    let mut buf = Vec::new();
+    {
+        let mut open_type_encoder = PerEncoder {
+            context: encoder.context,
+            kind: encoder.kind,
+            options: encoder.options,
+            writer: BitWriter::new(encoder.kind.is_aligned(), Cursor::new(&mut buf)),
+            tmp_buf: Vec::new(),
+        };
+        encode(&mut open_type_encoder)?;
+        open_type_encoder.writer.force_align();
+    }
+    // a complete encoding is never empty
+    if buf.is_empty() {
+        buf.push(0x00);
+    }
+
+    write_length_prefixed_bytes(encoder, &buf);
+    Ok(())
+}
+
+fn per_encode_choice<W: Write>(
+    encoder: &mut PerEncoder<'_, W>,
+    choice_type: &Choice,
+    value: &ChoiceValue,
+) -> Result<()> {
+    let context = encoder.context;
+    let (root, extensions) = get_alternative_order(context, choice_type)?;
+    let index = choice_type
+        .alternatives
+        .iter()
+        .position(|alternative| alternative.name.element == value.alternative.element)
+        .expect("CHOICE value has an alternative that is not in the CHOICE type");
+    let alternative_value = value.value.resolve(context)?;
+
+    match extensions.iter().position(|extension| *extension == index) {
+        Some(extension_index) => {
+            // extension additions are encoded as an open type
+            encoder.writer.write_bit(true);
+            write_normally_small_number(encoder, extension_index as u64);
+            write_open_type(encoder, |encoder| {
+                per_encode_value(encoder, &alternative_value, value.value.loc)
+            })
+        }
+        None => {
+            if choice_type.extensible {
+                encoder.writer.write_bit(false);
+            }
+            if root.len() > 1 {
+                let root_index = root
+                    .iter()
+                    .position(|root| *root == index)
+                    .expect("root alternative index");
+                write_constrained_integer(
+                    encoder,
+                    &BigInt::from(root_index),
+                    &BigInt::from(0),
+                    &BigInt::from(root.len() - 1),
+                );
+            }
+            per_encode_value(encoder, &alternative_value, value.value.loc)
+        }
+    }
+}
+
+fn per_encode_enumerated<W: Write>(
+    encoder: &mut PerEncoder<'_, W>,
+    enumerated_type: &EnumeratedType,
+    value: &AstElement<TypedValue>,
+) -> Result<()> {
+    let context = encoder.context;
+    let num = match value.resolve(context)?.value {
+        BuiltinValue::Integer(num) => num,
+        other => {
+            return Err(Error {
+                kind: ErrorKind::Ast(format!(
+                    "ENUMERATED value must be of type INTEGER, but found {}",
+                    other.tag_type(context)?
+                )),
+                loc: value.loc,
+            })
+        }
+    };
+
+    let (root, extensions) = get_enumeration_order(context, enumerated_type)?;
+    if let Some(index) = root.iter().position(|root| root == &num) {
+        if enumerated_type.extensible {
+            encoder.writer.write_bit(false);
+        }
+        write_constrained_integer(
+            encoder,
+            &BigInt::from(index),
+            &BigInt::from(0),
+            &BigInt::from(root.len() - 1),
+        );
+    } else if let Some(index) = extensions.iter().position(|extension| extension == &num) {
+        encoder.writer.write_bit(true);
+        write_normally_small_number(encoder, index as u64);
+    } else {
+        return Err(Error {
+            kind: ErrorKind::Ast(format!(
+                "ENUMERATED value {} is not an item of the ENUMERATED type",
+                num
+            )),
+            loc: value.loc,
+        });
+    }
+
+    Ok(())
+}
+
+fn per_encode_character_string<W: Write>(
+    encoder: &mut PerEncoder<'_, W>,
+    resolved_type: &ResolvedType,
+    tag_type: TagType,
+    str: &str,
+    loc: Loc,
+) -> Result<()> {
+    let context = encoder.context;
+    let alphabet = match KnownMultiplierAlphabet::new(
+        context,
+        resolved_type,
+        tag_type,
+        encoder.kind.is_aligned(),
+    )? {
+        Some(alphabet) => alphabet,
+        None => {
+            // X.691 clause 30.6: other character string types are encoded as their BER contents octets
+            encoder.tmp_buf.clear();
+            ber::ber_encode_character_string(&mut encoder.tmp_buf, tag_type, str);
+            encoder.tmp_buf.reverse();
+            write_length_prefixed_tmp_buf(encoder);
+            return Ok(());
+        }
+    };
+
+    let chars = str.chars().collect::<Vec<_>>();
+    let len = chars.len() as u64;
+    let determinant_kind =
+        write_size_determinant(&mut encoder.writer, context, len, resolved_type, loc)?;
+    let is_octet_aligned = match determinant_kind {
+        LengthDeterminantKind::VariableSize | LengthDeterminantKind::Fragmented => true,
+        LengthDeterminantKind::Const | LengthDeterminantKind::FixedSize => {
+            alphabet.is_octet_aligned(get_size_upper_bound(context, resolved_type)?)
+        }
+    };
+    if is_octet_aligned && len > 0 {
+        encoder.writer.align();
+    }
+
+    write_items(encoder, len, &determinant_kind, |encoder, range| {
+        for &ch in &chars[range.start as usize..range.end as usize] {
+            let value = alphabet.encode_char(ch).ok_or_else(|| Error {
+                kind: ErrorKind::Ast(format!(
+                    "{} value contains the character '{}', which is outside of its permitted alphabet",
+                    tag_type, ch
+                )),
+                loc,
+            })?;
+            encoder
+                .writer
+                .write_int(value, alphabet.char_bits, Alignment::None);
+        }
+        Ok(())
+    })
+}
+
+/// Returns the elements of a SET OF value in ascending order of their encodings, as required by CANONICAL-PER (X.691 clause 22.1).
+fn sort_set_of<W: Write>(
+    encoder: &PerEncoder<'_, W>,
+    elements: &[AstElement<TypedValue>],
+) -> Result<Vec<AstElement<TypedValue>>> {
+    let mut encodings = Vec::with_capacity(elements.len());
+    for element in elements {
+        let resolved = element.resolve(encoder.context)?;
+        let mut buf = Vec::new();
+        {
+            let mut element_encoder = PerEncoder {
+                context: encoder.context,
+                kind: encoder.kind,
+                options: encoder.options,
+                writer: BitWriter::new(encoder.kind.is_aligned(), Cursor::new(&mut buf)),
+                tmp_buf: Vec::new(),
+            };
+            per_encode_value(&mut element_encoder, &resolved, element.loc)?;
+            element_encoder.writer.force_align();
+        }
+        encodings.push((element, buf));
+    }
+
+    match encoder.options.set_ordering {
+        // comparing the encodings as octet strings with the shorter encoding padded with trailing 0-octets
+        // is equivalent to a lexicographical comparison
+        SetOrdering::Sort => encodings.sort_by(|(_, a), (_, b)| a.cmp(b)),
+        SetOrdering::Verify => {
+            if let Some(window) = encodings
+                .windows(2)
+                .find(|window| window[0].1 > window[1].1)
+            {
+                return Err(Error {
+                    kind: ErrorKind::Ast(
+                        "SET OF element must be placed before the previous element in canonical order"
+                            .to_string(),
+                    ),
+                    loc: window[1].0.loc,
+                });
+            }
+        }
+    }
+
+    Ok(encodings
+        .into_iter()
+        .map(|(element, _)| element.clone())
+        .collect())
+}
+
+/// Writes a UTCTime or GeneralizedTime value, which is encoded as if its type were VisibleString
+/// without any PER-visible constraints (X.691 clause 32).
+fn per_encode_useful_time<W: Write>(
+    encoder: &mut PerEncoder<'_, W>,
+    typed_value: &ResolvedValue,
+    str: &str,
+    loc: Loc,
+) -> Result<()> {
+    let visible_string = ResolvedType {
+        tag: typed_value.ty.tag.clone(),
+        ty: BuiltinType::CharacterString(TagType::VisibleString),
+        constraint: None,
+        xer_instructions: Vec::new(),
+    };
+    per_encode_character_string(encoder, &visible_string, TagType::VisibleString, str, loc)
+}
+
+/// Writes the value, where `loc` is the location of the value that errors are reported at.
 pub fn per_encode_value<W: Write>(
     encoder: &mut PerEncoder<'_, W>,
     typed_value: &ResolvedValue,
+    loc: Loc,
 ) -> Result<()> {
     let context = encoder.context;
 
+    if ber::is_real_type(&typed_value.ty.ty) {
+        // X.691 clause 15: the contents octets of the CER encoding, prefixed by their length
+        encoder.tmp_buf.clear();
+        match &typed_value.value {
+            BuiltinValue::Integer(num) => {
+                ber::ber_encode_real(&mut encoder.tmp_buf, num.clone(), 10, BigInt::ZERO)
+            }
+            BuiltinValue::RealLiteral(lit) => ber::ber_encode_real(
+                &mut encoder.tmp_buf,
+                lit.mantissa.clone(),
+                10,
+                lit.exponent.clone(),
+            ),
+            BuiltinValue::Structure(_, structure) => {
+                ber::ber_encode_real_structure(&mut encoder.tmp_buf, context, structure)?
+            }
+            _ => unreachable!(),
+        }
+        encoder.tmp_buf.reverse();
+        write_length_prefixed_tmp_buf(encoder);
+        return Ok(());
+    }
+
     match &typed_value.value {
         BuiltinValue::Boolean(b) => {
             encoder.writer.write_bit(*b);
         }
         BuiltinValue::Integer(int) => {
-            per_encode_integer(encoder, &typed_value.ty, int)?;
+            per_encode_integer(encoder, &typed_value.ty, int, loc)?;
         }
         BuiltinValue::BitString(bs) => {
-            let mut total_bits = bs.data.len() as u64 * 8 - bs.unused_bits as u64;
+            let mut total_bits = bs.len();
 
             match &typed_value.ty.ty {
-                ty @ BuiltinType::BitString(bs) => {
-                    if bs.named_bits.is_some() {
+                ty @ BuiltinType::BitString(bs_type) => {
+                    if bs_type.named_bits.is_some() {
+                        if encoder.kind.is_canonical() {
+                            // X.691 clause 16.2: CANONICAL-PER removes the trailing 0 bits of BIT STRING with named bits
+                            total_bits = bs.len_without_trailing_zeros();
+                        }
                         if let Some(constraint) = &typed_value.ty.constraint {
                             if let Some(bounds) =
                                 constraint.resolve(encoder.context, ty)?.size_bounds()?
@@ -279,8 +799,13 @@ pub fn per_encode_value<W: Write>(
                 _ => unreachable!(),
             };
 
-            let determinant_kind =
-                write_size_determinant(&mut encoder.writer, context, total_bits, &typed_value.ty)?;
+            let determinant_kind = write_size_determinant(
+                &mut encoder.writer,
+                context,
+                total_bits,
+                &typed_value.ty,
+                loc,
+            )?;
             match determinant_kind {
                 LengthDeterminantKind::Const => (),
                 _ => {
@@ -289,17 +814,20 @@ pub fn per_encode_value<W: Write>(
                     }
                 }
             }
-            for bit_index in 0..total_bits {
-                let byte_index = (bit_index / 8) as usize;
-                if byte_index >= bs.data.len() {
-                    encoder.writer.write_bit(false);
-                } else {
-                    let byte = bs.data[byte_index];
-                    let bit_pos = 7 - (bit_index % 8) as usize;
-                    let bit = (byte >> bit_pos) & 1;
-                    encoder.writer.write_bit(bit == 1);
+            write_items(encoder, total_bits, &determinant_kind, |encoder, range| {
+                for bit_index in range {
+                    let byte_index = (bit_index / 8) as usize;
+                    if byte_index >= bs.data.len() {
+                        encoder.writer.write_bit(false);
+                    } else {
+                        let byte = bs.data[byte_index];
+                        let bit_pos = 7 - (bit_index % 8) as usize;
+                        let bit = (byte >> bit_pos) & 1;
+                        encoder.writer.write_bit(bit == 1);
+                    }
                 }
-            }
+                Ok(())
+            })?;
         }
         BuiltinValue::OctetString(bytes) => {
             let determinant_kind = write_size_determinant(
@@ -307,6 +835,7 @@ pub fn per_encode_value<W: Write>(
                 context,
                 bytes.len() as u64,
                 &typed_value.ty,
+                loc,
             )?;
             match determinant_kind {
                 LengthDeterminantKind::Const => (),
@@ -316,22 +845,131 @@ pub fn per_encode_value<W: Write>(
                     }
                 }
             }
-            encoder.writer.write_bytes(bytes);
+            write_items(
+                encoder,
+                bytes.len() as u64,
+                &determinant_kind,
+                |encoder, range| {
+                    encoder
+                        .writer
+                        .write_bytes(&bytes[range.start as usize..range.end as usize]);
+                    Ok(())
+                },
+            )?;
         }
         BuiltinValue::Null => (),
-        BuiltinValue::StructureOf(_, seq_of) => {
-            write_size_determinant(
+        BuiltinValue::CharacterString(tag_type, str) => {
+            per_encode_character_string(encoder, &typed_value.ty, *tag_type, str, loc)?;
+        }
+        BuiltinValue::ObjectIdentifier(oid) => {
+            // X.691 clause 24: the contents octets of the BER encoding, prefixed by their length
+            encoder.tmp_buf.clear();
+            ber::ber_encode_object_identifier(&mut encoder.tmp_buf, context, oid)?;
+            encoder.tmp_buf.reverse();
+            write_length_prefixed_tmp_buf(encoder);
+        }
+        BuiltinValue::RelativeOid(oid) => {
+            encoder.tmp_buf.clear();
+            ber::ber_encode_relative_oid(&mut encoder.tmp_buf, context, oid)?;
+            encoder.tmp_buf.reverse();
+            write_length_prefixed_tmp_buf(encoder);
+        }
+        BuiltinValue::Enumerated(enumerated) => match &typed_value.ty.ty {
+            BuiltinType::Enumerated(enumerated_type) => {
+                per_encode_enumerated(encoder, enumerated_type, enumerated)?;
+            }
+            _ => unreachable!(),
+        },
+        BuiltinValue::Structure(_, structure) => match &typed_value.ty.ty {
+            BuiltinType::Structure(structure_type) => {
+                per_encode_structure(encoder, structure_type, structure)?;
+            }
+            _ => unreachable!(),
+        },
+        BuiltinValue::Choice(choice) => match &typed_value.ty.ty {
+            BuiltinType::Choice(choice_type) => {
+                per_encode_choice(encoder, choice_type, choice)?;
+            }
+            _ => unreachable!(),
+        },
+        BuiltinValue::StructureOf(tag_type, seq_of) => {
+            let sorted;
+            let seq_of = if *tag_type == TagType::Set && encoder.kind.is_canonical() {
+                sorted = sort_set_of(encoder, seq_of)?;
+                &sorted
+            } else {
+                seq_of
+            };
+
+            let determinant_kind = write_size_determinant(
                 &mut encoder.writer,
                 context,
                 seq_of.len() as u64,
                 &typed_value.ty,
+                loc,
+            )?;
+            write_items(
+                encoder,
+                seq_of.len() as u64,
+                &determinant_kind,
+                |encoder, range| {
+                    for element in &seq_of[range.start as usize..range.end as usize] {
+                        let element_loc = element.loc;
+                        let element = element.resolve(context)?;
+                        per_encode_value(encoder, &element, element_loc)?;
+                    }
+                    Ok(())
+                },
+            )?;
+        }
+        BuiltinValue::UTCTime(utc) => {
+            per_encode_useful_time(encoder, typed_value, &utc.to_ber_string(), loc)?;
+        }
+        BuiltinValue::GeneralizedTime(gt) => {
+            per_encode_useful_time(encoder, typed_value, &gt.to_ber_string(), loc)?;
+        }
+        BuiltinValue::Containing(containing) => {
+            // X.691 clause 11.1: without ENCODED BY, the contained value is a complete encoding with the same encoding rules
+            let mut data = encode_contained_value(
+                context,
+                encoder.options,
+                &typed_value.ty,
+                containing,
+                &TransferSyntax::Packed(encoder.kind),
             )?;
-            for element in seq_of {
-                let element = element.resolve(context)?;
-                per_encode_value(encoder, &element)?;
+            if data.is_empty() {
+                data.push(0x00);
             }
+            let value = match containing.container_type {
+                TagType::BitString => BuiltinValue::BitString(BitStringValue {
+                    data,
+                    unused_bits: 0,
+                }),
+                _ => BuiltinValue::OctetString(data),
+            };
+            per_encode_value(
+                encoder,
+                &ResolvedValue {
+                    ty: typed_value.ty.clone(),
+                    value,
+                },
+                loc,
+            )?;
         }
-        _ => todo!("PER encode type: {}", typed_value.ty.ty),
+        BuiltinValue::Time(_)
+        | BuiltinValue::Date(_)
+        | BuiltinValue::TimeOfDay(_)
+        | BuiltinValue::DateTime(_)
+        | BuiltinValue::Duration(_) => {
+            return Err(Error {
+                kind: ErrorKind::Ast(format!(
+                    "PER encoding of {} values is not yet supported",
+                    typed_value.ty.ty
+                )),
+                loc,
+            });
+        }
+        BuiltinValue::RealLiteral(_) => unreachable!(),
     }
 
     Ok(())
@@ -339,7 +977,314 @@ pub fn per_encode_value<W: Write>(
 
 #[cfg(test)]
 mod test {
-    use crate::compiler::test::json_test;
+    use crate::{
+        compiler::{
+            parser::{AstElement, Loc},
+            test::{compile_module, encode_declared_value, json_test},
+            Context,
+        },
+        encoding::*,
+        module::{ModuleIdentifier, QualifiedIdentifier},
+        types::*,
+        values::*,
+    };
+
+    #[test]
+    fn test_per_encode_structure_options() {
+        let mut context = Context::new();
+        compile_module(
+            &mut context,
+            "PerStructureTest.asn",
+            include_str!("../../../test-data/encode/per/PerStructureTest.asn"),
+        );
+
+        let options = EncodeOptions {
+            set_ordering: SetOrdering::Verify,
+            ..Default::default()
+        };
+        for (name, ordered) in [("set-1", false), ("set-2", false), ("nested-1", true)] {
+            let result = encode_declared_value(
+                &context,
+                "PerStructureTest",
+                name,
+                &TransferSyntax::Packed(PackedEncodingKind::BasicAligned),
+                &options,
+            );
+            assert_eq!(result.is_ok(), ordered, "value = {}", name);
+        }
+
+        let options = EncodeOptions {
+            encode_default_values: true,
+            ..Default::default()
+        };
+        for (name, kind, expected) in [
+            ("defaults-1", PackedEncodingKind::BasicAligned, "DC"),
+            ("defaults-2", PackedEncodingKind::BasicUnaligned, "EC"),
+            ("defaults-1", PackedEncodingKind::CanonicalAligned, "00"),
+            ("defaults-2", PackedEncodingKind::CanonicalUnaligned, "A8"),
+        ] {
+            let encoded = encode_declared_value(
+                &context,
+                "PerStructureTest",
+                name,
+                &TransferSyntax::Packed(kind),
+                &options,
+            )
+            .expect("encode");
+            assert_eq!(hex::encode_upper(encoded), expected, "value = {}", name);
+        }
+    }
+
+    #[test]
+    fn test_per_encode_unsupported_time() {
+        let mut context = Context::new();
+        compile_module(
+            &mut context,
+            "PerTimeTest.asn",
+            include_str!("../../../test-data/encode/per/PerTimeTest.asn"),
+        );
+
+        for name in ["date", "time-of-day", "date-time", "duration"] {
+            let result = encode_declared_value(
+                &context,
+                "PerTimeTest",
+                name,
+                &TransferSyntax::Packed(PackedEncodingKind::BasicAligned),
+                &EncodeOptions::default(),
+            );
+            assert!(result.is_err(), "value = {}", name);
+        }
+    }
+
+    #[test]
+    fn test_per_encode_constraint_violations() {
+        let mut context = Context::new();
+        compile_module(
+            &mut context,
+            "PerViolationTest.asn",
+            r#"PerViolationTest DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                Letters ::= IA5String (FROM ("abc"))
+                Digits ::= NumericString
+                Small ::= INTEGER (0..7)
+                Pair ::= OCTET STRING (SIZE (2))
+            END"#,
+        );
+
+        for (name, value) in [
+            (
+                "Letters",
+                BuiltinValue::CharacterString(TagType::IA5String, "xyz".to_string()),
+            ),
+            (
+                "Digits",
+                BuiltinValue::CharacterString(TagType::NumericString, "ab".to_string()),
+            ),
+            ("Small", BuiltinValue::Integer(99.into())),
+            ("Pair", BuiltinValue::OctetString(vec![1, 2, 3])),
+        ] {
+            let ident = QualifiedIdentifier::new(
+                ModuleIdentifier::with_name("PerViolationTest".to_string()),
+                name.to_string(),
+            );
+            let value = ResolvedValue {
+                ty: context
+                    .lookup_type(&ident)
+                    .expect("lookup_type")
+                    .ty
+                    .resolve(&context)
+                    .expect("resolve"),
+                value,
+            };
+            for kind in [
+                PackedEncodingKind::BasicUnaligned,
+                PackedEncodingKind::CanonicalAligned,
+            ] {
+                let syntax = TransferSyntax::Packed(kind);
+                let encoder = syntax.get_codec().encoder.expect("encoder");
+                let result = encoder(
+                    &syntax,
+                    EncodeMode::Normal,
+                    &EncodeOptions::default(),
+                    &mut Vec::new(),
+                    &context,
+                    &value,
+                );
+                assert!(result.is_err(), "value of {} is encoded", name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_per_encode_fragmented() {
+        let mut context = Context::new();
+        compile_module(
+            &mut context,
+            "PerFragmentTest.asn",
+            r#"PerFragmentTest DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                Blob ::= OCTET STRING
+                Bits ::= BIT STRING
+                Text ::= IA5String
+                Flags ::= SEQUENCE OF BOOLEAN
+            END"#,
+        );
+
+        let lookup_type = |name: &str| {
+            let ident = QualifiedIdentifier::new(
+                ModuleIdentifier::with_name("PerFragmentTest".to_string()),
+                name.to_string(),
+            );
+            let declared = context.lookup_type(&ident).expect("lookup_type");
+            (ident, declared.ty.resolve(&context).expect("resolve"))
+        };
+
+        // (length, expected headers as (offset, bytes))
+        let cases: &[(usize, &[(usize, &str)])] = &[
+            (0x3fff, &[(0, "BFFF")]),
+            (0x4000, &[(0, "C1"), (0x4001, "00")]),
+            (0x10000, &[(0, "C4"), (0x10001, "00")]),
+            (70000, &[(0, "C4"), (0x10001, "9170")]),
+            (0x1c001, &[(0, "C4"), (0x10001, "C3"), (0x1c002, "01")]),
+        ];
+        let syntax = TransferSyntax::Packed(PackedEncodingKind::BasicAligned);
+        for (name, value_of) in [
+            (
+                "Blob",
+                (|len| BuiltinValue::OctetString((0..len).map(|i| i as u8).collect()))
+                    as fn(usize) -> BuiltinValue,
+            ),
+            ("Bits", |len| {
+                let mut data = (0..len.div_ceil(8)).map(|i| i as u8).collect::<Vec<_>>();
+                let unused_bits = ((8 - len % 8) % 8) as u8;
+                if let Some(last) = data.last_mut() {
+                    *last &= 0xff << unused_bits;
+                }
+                BuiltinValue::BitString(BitStringValue { data, unused_bits })
+            }),
+            ("Text", |len| {
+                BuiltinValue::CharacterString(
+                    TagType::IA5String,
+                    (0..len).map(|i| (b'a' + (i % 26) as u8) as char).collect(),
+                )
+            }),
+        ] {
+            let (ident, ty) = lookup_type(name);
+            for (len, headers) in cases {
+                let value = ResolvedValue {
+                    ty: ty.clone(),
+                    value: value_of(*len),
+                };
+
+                let encoder = syntax.get_codec().encoder.expect("encoder");
+                let mut buf = Vec::new();
+                encoder(
+                    &syntax,
+                    EncodeMode::Normal,
+                    &EncodeOptions::default(),
+                    &mut buf,
+                    &context,
+                    &value,
+                )
+                .expect("encode");
+
+                // BIT STRING fragments are counted in bits, so their headers are at different offsets
+                if name != "Bits" {
+                    for (offset, header) in *headers {
+                        let header = hex::decode(header).unwrap();
+                        assert_eq!(
+                            &buf[*offset..*offset + header.len()],
+                            header.as_slice(),
+                            "{} of length {} at offset {}",
+                            name,
+                            len,
+                            offset
+                        );
+                    }
+                }
+
+                let mode = DecodeMode::SpecificType {
+                    source_ident: Some(ident.clone()),
+                    component_name: None,
+                    resolved: Box::new(ty.clone()),
+                };
+                let decoder = syntax.get_codec().decoder.expect("decoder");
+                let decoded = decoder(&syntax, &mode, &buf, &context).expect("decode");
+                let decoded = match &decoded[0].form {
+                    DecodedValueForm::Primitive(kind) => kind,
+                    DecodedValueForm::Constructed(_) => panic!("decoded a constructed value"),
+                };
+                match (decoded, &value.value) {
+                    (DecodedValueKind::OctetString(lhs), BuiltinValue::OctetString(rhs)) => {
+                        assert_eq!(lhs, rhs)
+                    }
+                    (DecodedValueKind::BitString(lhs), BuiltinValue::BitString(rhs)) => {
+                        assert_eq!(lhs.data, rhs.data);
+                        assert_eq!(lhs.unused_bits, rhs.unused_bits);
+                    }
+                    (
+                        DecodedValueKind::CharacterString(_, lhs),
+                        BuiltinValue::CharacterString(_, rhs),
+                    ) => assert_eq!(lhs, rhs),
+                    (decoded, _) => panic!("{} decoded as {:?}", name, decoded),
+                }
+            }
+        }
+
+        // SEQUENCE OF elements are fragmented by count, not by octets
+        let (ident, ty) = lookup_type("Flags");
+        let element_type = match &ty.ty {
+            BuiltinType::StructureOf(structure_of) => structure_of
+                .component_type
+                .resolve(&context)
+                .expect("resolve"),
+            _ => unreachable!(),
+        };
+        let value = ResolvedValue {
+            ty: ty.clone(),
+            value: BuiltinValue::StructureOf(
+                TagType::Sequence,
+                (0..0x4001)
+                    .map(|i| {
+                        AstElement::new(
+                            TypedValue {
+                                resolved_type: element_type.clone(),
+                                value: ValueReference::BuiltinValue(BuiltinValue::Boolean(
+                                    i % 2 == 0,
+                                )),
+                            },
+                            Loc::default(),
+                        )
+                    })
+                    .collect(),
+            ),
+        };
+        let encoder = syntax.get_codec().encoder.expect("encoder");
+        let mut buf = Vec::new();
+        encoder(
+            &syntax,
+            EncodeMode::Normal,
+            &EncodeOptions::default(),
+            &mut buf,
+            &context,
+            &value,
+        )
+        .expect("encode");
+        // 16K booleans are 2K octets, followed by the remainder length and the last boolean
+        assert_eq!(buf.len(), 1 + 0x800 + 1 + 1);
+        assert_eq!(buf[0], 0xc1);
+        assert_eq!(&buf[0x801..], &[0x01, 0x80]);
+
+        let mode = DecodeMode::SpecificType {
+            source_ident: Some(ident),
+            component_name: None,
+            resolved: Box::new(ty),
+        };
+        let decoder = syntax.get_codec().decoder.expect("decoder");
+        let decoded = decoder(&syntax, &mode, &buf, &context).expect("decode");
+        match &decoded[0].form {
+            DecodedValueForm::Constructed(elements) => assert_eq!(elements.len(), 0x4001),
+            DecodedValueForm::Primitive(kind) => panic!("Flags decoded as {:?}", kind),
+        }
+    }
 
     json_test!(
         test_per_encode_boolean,
@@ -349,6 +1294,22 @@ mod test {
         test_per_encode_integer,
         "../../../test-data/encode/per/PerIntegerTest"
     );
+    json_test!(
+        test_per_encode_character_string,
+        "../../../test-data/encode/per/PerCharacterStringTest"
+    );
+    json_test!(
+        test_per_encode_enumerated,
+        "../../../test-data/encode/per/PerEnumeratedTest"
+    );
+    json_test!(
+        test_per_encode_object_identifier,
+        "../../../test-data/encode/per/PerObjectIdentifierTest"
+    );
+    json_test!(
+        test_per_encode_real,
+        "../../../test-data/encode/per/PerRealTest"
+    );
     json_test!(
         test_per_encode_bit_string,
         "../../../test-data/encode/per/PerBitStringTest"
@@ -361,4 +1322,20 @@ mod test {
         test_per_encode_null,
         "../../../test-data/encode/per/PerNullTest"
     );
+    json_test!(
+        test_per_encode_structure,
+        "../../../test-data/encode/per/PerStructureTest"
+    );
+    json_test!(
+        test_per_encode_choice,
+        "../../../test-data/encode/per/PerChoiceTest"
+    );
+    json_test!(
+        test_per_encode_time,
+        "../../../test-data/encode/per/PerTimeTest"
+    );
+    json_test!(
+        test_per_encode_contents_constraint,
+        "../../../test-data/encode/per/PerContentsConstraintTest"
+    );
 }
@@ -1,6 +1,6 @@
-use std::io::Write;
+use std::io::{self, Write};
 
-use num::BigInt;
+use num::{BigInt, BigUint};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Alignment {
@@ -127,13 +127,177 @@ impl<W: Write> BitWriter<W> {
     }
 }
 
+pub struct BitReader<'a> {
+    aligned: bool,
+    source: &'a [u8],
+    source_start: usize,
+    byte_cursor: usize,
+    bit_cursor: u8,
+}
+
+impl<'a> BitReader<'a> {
+    pub fn new(aligned: bool, source: &'a [u8]) -> BitReader<'a> {
+        BitReader::with_offset(aligned, source, 0)
+    }
+
+    /// Creates a reader for a source that starts at `source_start` bytes into the full encoding,
+    /// so that the positions it reports are relative to the full encoding.
+    pub fn with_offset(aligned: bool, source: &'a [u8], source_start: usize) -> BitReader<'a> {
+        BitReader {
+            aligned,
+            source,
+            source_start,
+            byte_cursor: 0,
+            bit_cursor: 0,
+        }
+    }
+
+    /// Returns the offset of the byte that contains the next bit to be read.
+    pub fn byte_pos(&self) -> usize {
+        self.source_start + self.byte_cursor
+    }
+
+    /// Returns the offset of the first byte after the bits that have been read.
+    pub fn end_byte_pos(&self) -> usize {
+        if self.bit_cursor == 0 {
+            self.byte_pos()
+        } else {
+            self.byte_pos() + 1
+        }
+    }
+
+    /// Returns `true` if every byte of the source has been read.
+    pub fn is_empty(&self) -> bool {
+        self.byte_cursor >= self.source.len()
+    }
+
+    pub fn read_int(&mut self, bits: u32, alignment: Alignment) -> io::Result<u64> {
+        if self.aligned && alignment == Alignment::End && !bits.is_multiple_of(8) {
+            let align_bits = 8 - (bits % 8);
+            for _ in 0..align_bits {
+                self.read_bit()?;
+            }
+        }
+
+        let mut int = 0;
+        for _ in 0..bits {
+            int = (int << 1) | self.read_bit()? as u64;
+        }
+
+        if self.aligned && alignment == Alignment::Start {
+            self.align();
+        }
+
+        Ok(int)
+    }
+
+    pub fn read_bigint(&mut self, bits: u64, alignment: Alignment) -> io::Result<BigInt> {
+        if self.aligned && alignment == Alignment::End && !bits.is_multiple_of(8) {
+            let align_bits = 8 - (bits % 8);
+            for _ in 0..align_bits {
+                self.read_bit()?;
+            }
+        }
+
+        let mut int = BigUint::default();
+        for _ in 0..bits {
+            int <<= 1;
+            if self.read_bit()? {
+                int.set_bit(0, true);
+            }
+        }
+
+        if self.aligned && alignment == Alignment::Start && !bits.is_multiple_of(8) {
+            let align_bits = 8 - (bits % 8);
+            for _ in 0..align_bits {
+                self.read_bit()?;
+            }
+        }
+
+        Ok(BigInt::from(int))
+    }
+
+    pub fn read_bytes(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        if self.bit_cursor != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("read_bytes: bit_cursor == {}", self.bit_cursor),
+            ));
+        }
+        let end = self.byte_cursor + len;
+        if end > self.source.len() {
+            return Err(unexpected_eof());
+        }
+        let bytes = &self.source[self.byte_cursor..end];
+        self.byte_cursor = end;
+        Ok(bytes)
+    }
+
+    pub fn read_bytes_to(&mut self, len: usize, buf: &mut Vec<u8>) -> io::Result<()> {
+        if self.bit_cursor == 0 {
+            buf.extend_from_slice(self.read_bytes(len)?);
+        } else {
+            for _ in 0..len {
+                buf.push(self.read_byte()?);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn read_byte(&mut self) -> io::Result<u8> {
+        let mut byte = 0;
+        for _ in 0..8 {
+            byte = (byte << 1) | self.read_bit()? as u8;
+        }
+        Ok(byte)
+    }
+
+    pub fn align(&mut self) {
+        if self.aligned {
+            self.force_align();
+        }
+    }
+
+    pub fn force_align(&mut self) {
+        if self.bit_cursor > 0 {
+            self.bit_cursor = 0;
+            self.byte_cursor += 1;
+        }
+    }
+
+    #[inline(always)]
+    pub fn read_bit(&mut self) -> io::Result<bool> {
+        let byte = match self.source.get(self.byte_cursor) {
+            Some(byte) => *byte,
+            None => return Err(unexpected_eof()),
+        };
+        let bit = (byte >> (7 - self.bit_cursor)) & 1;
+
+        if self.bit_cursor == 7 {
+            self.byte_cursor += 1;
+            self.bit_cursor = 0;
+        } else {
+            self.bit_cursor += 1;
+        }
+
+        Ok(bit == 1)
+    }
+}
+
+fn unexpected_eof() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::UnexpectedEof,
+        "unexpected end of PER encoding",
+    )
+}
+
 #[cfg(test)]
 mod test {
     use std::io::Cursor;
 
     use crate::encoding::per::Alignment;
 
-    use super::BitWriter;
+    use super::{BitReader, BitWriter};
 
     fn exec_test<F: Fn(BitWriter<Cursor<&mut Vec<u8>>>)>(f: F) -> (Vec<u8>, Vec<u8>) {
         let mut aligned_buf = Vec::new();
@@ -221,4 +385,26 @@ mod test {
         });
         assert_eq!(buf, (vec![0x07, 0xd0], vec![0xfa, 0x00]));
     }
+
+    #[test]
+    pub fn test_bit_reader_read_int() {
+        for aligned in [true, false] {
+            let mut buf = Vec::new();
+            let mut writer = BitWriter::new(aligned, Cursor::new(&mut buf));
+            writer.write_bit(true);
+            writer.write_int(6, 3, Alignment::End);
+            writer.write_int(2000, 11, Alignment::Start);
+            writer.write_int(5, 3, Alignment::None);
+            writer.force_align();
+
+            let mut reader = BitReader::new(aligned, &buf);
+            assert!(reader.read_bit().unwrap());
+            assert_eq!(reader.read_int(3, Alignment::End).unwrap(), 6);
+            assert_eq!(reader.read_int(11, Alignment::Start).unwrap(), 2000);
+            assert_eq!(reader.read_int(3, Alignment::None).unwrap(), 5);
+            reader.force_align();
+            assert!(reader.is_empty());
+            assert!(reader.read_bit().is_err());
+        }
+    }
 }
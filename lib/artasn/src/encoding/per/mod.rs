@@ -7,15 +7,268 @@ pub use io::*;
 mod encode;
 pub use encode::*;
 
-use crate::compiler::Context;
+mod decode;
+pub use decode::*;
+
+use num::BigInt;
+
+use crate::{
+    compiler::{
+        parser::{Error, ErrorKind, Result},
+        Context,
+    },
+    encoding::{EncodeOptions, PackedEncodingKind},
+    types::*,
+    values::{BuiltinValue, ValueResolve},
+};
 
 pub(crate) struct PerEncoder<'a, W: Write> {
     pub context: &'a Context,
+    pub kind: PackedEncodingKind,
+    pub options: &'a EncodeOptions,
     pub writer: BitWriter<W>,
     pub tmp_buf: Vec<u8>,
 }
 
+pub(crate) struct PerDecoder<'a> {
+    pub context: &'a Context,
+    pub kind: PackedEncodingKind,
+    pub reader: BitReader<'a>,
+}
+
 #[cfg(target_arch = "wasm32")]
 pub(crate) const TMP_BUF_CAPACITY: usize = 1024;
 #[cfg(not(target_arch = "wasm32"))]
 pub(crate) const TMP_BUF_CAPACITY: usize = 4096;
+
+/// Returns the tag that orders the type in canonical order (X.680 clause 8.6),
+/// where an untagged CHOICE has the smallest tag of its alternatives.
+pub(crate) fn get_canonical_tag(context: &Context, tagged_type: &TaggedType) -> Result<(Class, u16)> {
+    let resolved_type = tagged_type.resolve(context)?;
+    Ok(match &resolved_type.tag {
+        Some(tag) => (tag.class, tag.num),
+        None => resolved_type
+            .get_possible_tags(context)?
+            .into_iter()
+            .map(|(tag, _)| (tag.class, tag.num))
+            .min()
+            .expect("CHOICE has no alternatives"),
+    })
+}
+
+/// Returns the indexes of the root components of a SEQUENCE or SET type in the order that they are encoded,
+/// and the indexes of the components of each extension addition in the order that they are defined.
+/// The root components of a SET are encoded in the canonical order of their tags (X.691 clause 20).
+pub(crate) fn get_component_order(
+    context: &Context,
+    structure: &Structure,
+) -> Result<(Vec<usize>, Vec<Vec<usize>>)> {
+    let mut root = Vec::with_capacity(structure.components.len());
+    let mut additions: Vec<Vec<usize>> = Vec::new();
+    for (index, component) in structure.components.iter().enumerate() {
+        match component.extension_addition {
+            Some(addition) => {
+                if addition.index == additions.len() {
+                    additions.push(Vec::new());
+                }
+                additions[addition.index].push(index);
+            }
+            None => root.push(index),
+        }
+    }
+    if structure.ty == TagType::Set {
+        let mut tags = Vec::with_capacity(structure.components.len());
+        for component in &structure.components {
+            tags.push(get_canonical_tag(context, &component.component_type)?);
+        }
+        root.sort_by_key(|index| tags[*index]);
+    }
+    Ok((root, additions))
+}
+
+/// Returns the indexes of the root alternatives of a CHOICE type in the canonical order of their tags,
+/// and the indexes of the extension addition alternatives in the order that they are defined (X.691 clause 23).
+pub(crate) fn get_alternative_order(
+    context: &Context,
+    choice: &Choice,
+) -> Result<(Vec<usize>, Vec<usize>)> {
+    let mut root = Vec::with_capacity(choice.alternatives.len());
+    let mut extensions = Vec::new();
+    for (index, alternative) in choice.alternatives.iter().enumerate() {
+        if alternative.is_extension {
+            extensions.push(index);
+        } else {
+            root.push((
+                get_canonical_tag(context, &alternative.alternative_type)?,
+                index,
+            ));
+        }
+    }
+    root.sort_by_key(|(tag, _)| *tag);
+    Ok((
+        root.into_iter().map(|(_, index)| index).collect(),
+        extensions,
+    ))
+}
+
+fn get_enumeration_item_value(context: &Context, item: &EnumerationItem) -> Result<BigInt> {
+    match &item.value {
+        EnumerationItemValue::Implied(implied) => Ok(BigInt::from(*implied)),
+        EnumerationItemValue::Specified(specified) => match specified.resolve(context)?.value {
+            BuiltinValue::Integer(value) => Ok(value),
+            other => Err(Error {
+                kind: ErrorKind::Ast(format!(
+                    "ENUMERATED item must be of type INTEGER, but found {}",
+                    other.tag_type(context)?
+                )),
+                loc: specified.loc,
+            }),
+        },
+    }
+}
+
+/// Returns the values of the root items of an ENUMERATED type in ascending order,
+/// and the values of its extension addition items in ascending order (X.691 clause 14).
+/// The index of a value in its list is the index that is encoded.
+pub(crate) fn get_enumeration_order(
+    context: &Context,
+    enumerated: &EnumeratedType,
+) -> Result<(Vec<BigInt>, Vec<BigInt>)> {
+    let mut root = Vec::with_capacity(enumerated.items.len());
+    let mut extensions = Vec::new();
+    for item in &enumerated.items {
+        let value = get_enumeration_item_value(context, item)?;
+        if item.is_extension {
+            extensions.push(value);
+        } else {
+            root.push(value);
+        }
+    }
+    root.sort();
+    extensions.sort();
+    Ok((root, extensions))
+}
+
+/// Returns the upper bound of the root of the SIZE constraint of the type.
+/// If the size has no upper bound, `None` is returned.
+pub(crate) fn get_size_upper_bound(
+    context: &Context,
+    resolved_type: &ResolvedType,
+) -> Result<Option<u64>> {
+    let constraint = match &resolved_type.constraint {
+        Some(constraint) => constraint.resolve(context, &resolved_type.ty)?,
+        None => return Ok(None),
+    };
+    Ok(match constraint.size_bounds()? {
+        Some(ConstraintBounds {
+            upper_bound: Some(Bound::Integer(upper_bound)),
+            ..
+        }) => upper_bound.try_into().ok(),
+        _ => None,
+    })
+}
+
+/// How the characters of a known-multiplier character string type are encoded (X.691 clause 30.5).
+pub(crate) struct KnownMultiplierAlphabet {
+    /// The effective permitted alphabet of the type.
+    alphabet: PermittedAlphabet,
+    /// The number of bits that encode each character.
+    pub char_bits: u32,
+    /// `true` if characters are encoded as their index in the alphabet instead of their code point.
+    is_indexed: bool,
+}
+
+impl KnownMultiplierAlphabet {
+    /// Returns the encoding of the characters of the character string type,
+    /// or `None` if the type is not a known-multiplier character string type.
+    pub fn new(
+        context: &Context,
+        resolved_type: &ResolvedType,
+        tag_type: TagType,
+        aligned: bool,
+    ) -> Result<Option<KnownMultiplierAlphabet>> {
+        let alphabet = PermittedAlphabet::from_ranges(match tag_type {
+            TagType::NumericString => vec![(0x20, 0x20), (0x30, 0x39)],
+            TagType::PrintableString => vec![
+                (0x20, 0x20),
+                (0x27, 0x29),
+                (0x2b, 0x3a),
+                (0x3d, 0x3d),
+                (0x3f, 0x3f),
+                (0x41, 0x5a),
+                (0x61, 0x7a),
+            ],
+            TagType::VisibleString => vec![(0x20, 0x7e)],
+            TagType::IA5String => vec![(0x00, 0x7f)],
+            TagType::BMPString => vec![(0x0000, 0xffff)],
+            TagType::UniversalString => vec![(0x0000_0000, 0xffff_ffff)],
+            _ => return Ok(None),
+        });
+
+        // extensible permitted alphabet constraints are not PER-visible
+        let alphabet = match &resolved_type.constraint {
+            Some(constraint) => {
+                let resolved_constraint = constraint.resolve(context, &resolved_type.ty)?;
+                match resolved_constraint.permitted_alphabet() {
+                    Some(permitted)
+                        if !permitted.is_extensible && !resolved_constraint.is_extensible =>
+                    {
+                        let alphabet = alphabet.intersection(&permitted);
+                        if alphabet.is_empty() {
+                            return Err(Error {
+                                kind: ErrorKind::Ast(format!(
+                                    "the effective permitted alphabet of {} is empty",
+                                    tag_type
+                                )),
+                                loc: constraint.loc,
+                            });
+                        }
+                        alphabet
+                    }
+                    _ => alphabet,
+                }
+            }
+            None => alphabet,
+        };
+
+        let bits = u64::BITS - (alphabet.len() - 1).leading_zeros();
+        let char_bits = if aligned && bits > 0 {
+            bits.next_power_of_two()
+        } else {
+            bits
+        };
+        let is_indexed = alphabet.max().expect("alphabet is empty") as u64 >= 1 << char_bits;
+        Ok(Some(KnownMultiplierAlphabet {
+            alphabet,
+            char_bits,
+            is_indexed,
+        }))
+    }
+
+    /// Returns the value that encodes the character, or `None` if it is not in the alphabet.
+    pub fn encode_char(&self, ch: char) -> Option<u64> {
+        let index = self.alphabet.index_of(ch as u32)?;
+        Some(if self.is_indexed { index } else { ch as u64 })
+    }
+
+    /// Returns the character encoded by the value, or `None` if it is not in the alphabet.
+    pub fn decode_char(&self, value: u64) -> Option<char> {
+        let ch = if self.is_indexed {
+            self.alphabet.nth(value)?
+        } else {
+            u32::try_from(value)
+                .ok()
+                .filter(|ch| self.alphabet.contains(*ch))?
+        };
+        char::from_u32(ch)
+    }
+
+    /// Returns `true` if the characters are octet-aligned in the ALIGNED variant, based on the
+    /// upper bound of the size of the string (X.691 clause 30.5.7).
+    pub fn is_octet_aligned(&self, upper_bound: Option<u64>) -> bool {
+        match upper_bound {
+            Some(upper_bound) => upper_bound * self.char_bits as u64 > 16,
+            None => true,
+        }
+    }
+}
@@ -0,0 +1,741 @@
+use num::BigInt;
+
+use super::*;
+use crate::{
+    compiler::{
+        ast::values::{
+            is_valid_character, MINUS_INFINITY_IDENT, NOT_A_NUMBER_IDENT, PLUS_INFINITY_IDENT,
+        },
+        parser::{AstElement, Loc},
+        Context,
+    },
+    types::*,
+    values::*,
+};
+
+fn resolve_error<T>(pos: TlvPos, message: impl Into<String>) -> DecodeResult<T> {
+    Err(DecodeError::Decoder {
+        message: message.into(),
+        pos,
+    })
+}
+
+fn typed_value(resolved_type: ResolvedType, value: BuiltinValue) -> AstElement<TypedValue> {
+    AstElement::new(
+        TypedValue {
+            resolved_type,
+            value: ValueReference::BuiltinValue(value),
+        },
+        Loc::default(),
+    )
+}
+
+fn tag_matches(
+    context: &Context,
+    resolved_type: &ResolvedType,
+    tag: &TlvTag,
+) -> DecodeResult<bool> {
+    Ok(resolved_type
+        .get_possible_tags(context)
+        .map_err(DecodeError::Parser)?
+        .iter()
+        .any(|(possible, _)| possible.class == tag.class && possible.num == tag.num))
+}
+
+/// Returns the value inside the EXPLICIT tag of the type, if the value was decoded from BER with its EXPLICIT tag as a separate TLV.
+/// The other encodings do not encode tags, so their values are returned as-is.
+fn strip_explicit_tag<'a>(
+    context: &Context,
+    value: &'a DecodedValue,
+    resolved_type: &ResolvedType,
+) -> DecodeResult<&'a DecodedValue> {
+    let inner = match (&resolved_type.tag, &value.form) {
+        (
+            Some(Tag {
+                kind: TagKind::Explicit(inner_tag),
+                ..
+            }),
+            DecodedValueForm::Constructed(elements),
+        ) if elements.len() == 1 => {
+            let inner = &elements[0];
+            let inner_tag = inner_tag.or(resolved_type
+                .ty
+                .tag_type()
+                .map(|tag_type| (Class::Universal, tag_type as u16)));
+            let is_inner_tag = match inner_tag {
+                Some((class, num)) => {
+                    inner.tag.element.class == class && inner.tag.element.num == num
+                }
+                // the EXPLICIT tag of a CHOICE encloses the tag of its alternative
+                None => match &resolved_type.ty {
+                    BuiltinType::Choice(_) => {
+                        let untagged = ResolvedType {
+                            tag: None,
+                            ..resolved_type.clone()
+                        };
+                        tag_matches(context, &untagged, &inner.tag.element)?
+                    }
+                    _ => false,
+                },
+            };
+            is_inner_tag.then_some(inner)
+        }
+        _ => None,
+    };
+    Ok(inner.unwrap_or(value))
+}
+
+fn get_integer(context: &Context, value: &AstElement<TypedValue>) -> DecodeResult<BigInt> {
+    match value.resolve(context).map_err(DecodeError::Parser)?.value {
+        BuiltinValue::Integer(num) => Ok(num),
+        _ => unreachable!("ENUMERATED item value is not an INTEGER"),
+    }
+}
+
+/// Returns the value of the decoded REAL value.
+/// Nonzero values with base 2 are `{ mantissa, base 2, exponent }` values of the `Real` structure,
+/// other finite values, including minus zero, are REAL literals,
+/// and the special values are the values of the same names defined in the `Real` module.
+fn resolve_real(
+    context: &Context,
+    structure: &Structure,
+    real: &RealValue,
+) -> DecodeResult<BuiltinValue> {
+    let special = match real {
+        RealValue::Finite {
+            mantissa,
+            base: 2,
+            exponent,
+        } if *mantissa != BigInt::ZERO => {
+            // binary values keep their base, so that they are also encoded in binary (X.690 clause 11.3.1)
+            let components = structure
+                .components
+                .iter()
+                .zip([mantissa.clone(), BigInt::from(2), exponent.clone()])
+                .map(|(component, value)| {
+                    let component_type = component
+                        .component_type
+                        .resolve(context)
+                        .map_err(DecodeError::Parser)?;
+                    Ok(StructureValueComponent {
+                        name: component.name.clone(),
+                        value: typed_value(component_type, BuiltinValue::Integer(value)),
+                        is_default: false,
+                        default_value: None,
+                    })
+                })
+                .collect::<DecodeResult<Vec<_>>>()?;
+            return Ok(BuiltinValue::Structure(
+                structure.ty,
+                StructureValue { components },
+            ));
+        }
+        RealValue::Finite {
+            mantissa, exponent, ..
+        } => {
+            return Ok(BuiltinValue::RealLiteral(RealLiteral {
+                mantissa: mantissa.clone(),
+                exponent: exponent.clone(),
+            }))
+        }
+        // minus zero cannot be represented by a REAL literal, so it is treated like zero
+        RealValue::MinusZero => {
+            return Ok(BuiltinValue::RealLiteral(RealLiteral {
+                mantissa: BigInt::ZERO,
+                exponent: BigInt::ZERO,
+            }))
+        }
+        RealValue::PlusInfinity => &*PLUS_INFINITY_IDENT,
+        RealValue::MinusInfinity => &*MINUS_INFINITY_IDENT,
+        RealValue::NotANumber => &*NOT_A_NUMBER_IDENT,
+    };
+    let declared = context
+        .lookup_value(special)
+        .unwrap_or_else(|| panic!("missing special REAL value '{}'", special));
+    Ok(declared
+        .value
+        .resolve(context)
+        .map_err(DecodeError::Parser)?
+        .value)
+}
+
+/// Returns the ENUMERATED value of the item with the number, which must be defined by the type.
+fn resolve_enumerated(
+    context: &Context,
+    enumerated: &EnumeratedType,
+    num: i64,
+    pos: TlvPos,
+) -> DecodeResult<BuiltinValue> {
+    for item in &enumerated.items {
+        let item_num = match &item.value {
+            EnumerationItemValue::Implied(implied) => BigInt::from(*implied),
+            EnumerationItemValue::Specified(specified) => get_integer(context, specified)?,
+        };
+        if item_num == BigInt::from(num) {
+            return Ok(BuiltinValue::Enumerated(Box::new(typed_value(
+                ResolvedType::universal(TagType::Integer),
+                BuiltinValue::Integer(item_num),
+            ))));
+        }
+    }
+    resolve_error(
+        pos,
+        format!("ENUMERATED item with value {} is not defined", num),
+    )
+}
+
+fn resolve_oid(tag_type: TagType, oid: &Oid) -> ObjectIdentifier {
+    ObjectIdentifier {
+        ty: tag_type,
+        components: oid
+            .0
+            .iter()
+            .map(|node| ObjectIdentifierComponent::IntegerLiteral {
+                name: None,
+                int: AstElement::new(*node, Loc::default()),
+            })
+            .collect(),
+    }
+}
+
+/// Returns the value of a type that is not constructed.
+fn resolve_primitive(
+    context: &Context,
+    kind: &DecodedValueKind,
+    resolved_type: &ResolvedType,
+    pos: TlvPos,
+) -> DecodeResult<BuiltinValue> {
+    // values that a contextless BER decoder could not interpret, such as values with context-specific tags
+    if let DecodedValueKind::Raw(bytes) = kind {
+        let tag_type = match resolved_type.ty.tag_type() {
+            Some(tag_type) => tag_type,
+            None => {
+                return resolve_error(pos, format!("cannot convert {} value", resolved_type.ty))
+            }
+        };
+        let kind = ber::ber_decode_universal(BasicEncodingKind::Basic, bytes, tag_type)
+            .map_err(DecodeError::Io)?;
+        return resolve_primitive(context, &kind, resolved_type, pos);
+    }
+
+    Ok(match (&resolved_type.ty, kind) {
+        (BuiltinType::Structure(structure), DecodedValueKind::Real(real))
+            if ber::is_real_type(&resolved_type.ty) =>
+        {
+            resolve_real(context, structure, real)?
+        }
+        (BuiltinType::Boolean, DecodedValueKind::Boolean(b)) => BuiltinValue::Boolean(*b),
+        (BuiltinType::Integer(_), DecodedValueKind::Integer(num)) => {
+            BuiltinValue::Integer(num.clone())
+        }
+        (BuiltinType::Enumerated(enumerated), DecodedValueKind::Enumerated(num)) => {
+            resolve_enumerated(context, enumerated, *num, pos)?
+        }
+        (BuiltinType::BitString(_), DecodedValueKind::BitString(bs)) => {
+            BuiltinValue::BitString(bs.clone())
+        }
+        (BuiltinType::OctetString, DecodedValueKind::OctetString(bytes)) => {
+            BuiltinValue::OctetString(bytes.clone())
+        }
+        (BuiltinType::Null, DecodedValueKind::Null) => BuiltinValue::Null,
+        (BuiltinType::ObjectIdentifier, DecodedValueKind::ObjectIdentifier(oid)) => {
+            BuiltinValue::ObjectIdentifier(resolve_oid(TagType::ObjectIdentifier, oid))
+        }
+        (BuiltinType::RelativeOid, DecodedValueKind::RelativeOid(oid)) => {
+            BuiltinValue::RelativeOid(resolve_oid(TagType::RelativeOid, oid))
+        }
+        (BuiltinType::CharacterString(tag_type), DecodedValueKind::CharacterString(_, str)) => {
+            if !str.chars().all(|ch| is_valid_character(*tag_type, ch)) {
+                return resolve_error(
+                    pos,
+                    format!(
+                        "{} value contains characters that are not permitted",
+                        tag_type
+                    ),
+                );
+            }
+            BuiltinValue::CharacterString(*tag_type, str.clone())
+        }
+        (BuiltinType::UTCTime, DecodedValueKind::UTCTime(time)) => {
+            BuiltinValue::UTCTime(time.clone())
+        }
+        (BuiltinType::GeneralizedTime, DecodedValueKind::GeneralizedTime(time)) => {
+            BuiltinValue::GeneralizedTime(time.clone())
+        }
+        (BuiltinType::Date, DecodedValueKind::Date(date)) => BuiltinValue::Date(date.clone()),
+        (BuiltinType::TimeOfDay, DecodedValueKind::TimeOfDay(time)) => {
+            BuiltinValue::TimeOfDay(time.clone())
+        }
+        (BuiltinType::DateTime, DecodedValueKind::DateTime(date_time)) => {
+            BuiltinValue::DateTime(date_time.clone())
+        }
+        (BuiltinType::Duration, DecodedValueKind::Duration(duration)) => {
+            BuiltinValue::Duration(duration.clone())
+        }
+        (BuiltinType::Time, DecodedValueKind::Time(time)) => BuiltinValue::Time(time.clone()),
+        (ty, kind) => {
+            return resolve_error(pos, format!("expecting {} value, but found {:?}", ty, kind))
+        }
+    })
+}
+
+/// Returns the name of the component that the decoded value is a value of.
+/// Type-directed decoders name each component, while the components of a value that BER decoded without its type
+/// are matched by their tags, in the order of the type.
+fn find_component<'a>(
+    context: &Context,
+    structure: &'a Structure,
+    value: &DecodedValue,
+    next_index: usize,
+) -> DecodeResult<Option<(usize, &'a StructureComponent)>> {
+    let name = value
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.component_name.as_ref());
+    for (index, component) in structure.components.iter().enumerate() {
+        let is_match = match name {
+            Some(name) => &component.name.element == name,
+            None => {
+                index >= next_index
+                    && tag_matches(
+                        context,
+                        &component
+                            .component_type
+                            .resolve(context)
+                            .map_err(DecodeError::Parser)?,
+                        &value.tag.element,
+                    )?
+            }
+        };
+        if is_match {
+            return Ok(Some((index, component)));
+        }
+    }
+    Ok(None)
+}
+
+/// Returns the DEFAULT value of the component, which is parsed when the module is compiled.
+fn get_default_value(
+    component: &StructureComponent,
+) -> DecodeResult<Option<AstElement<TypedValue>>> {
+    match component
+        .default_value
+        .as_ref()
+        .and_then(|default_value| default_value.get())
+    {
+        Some(default_value) => default_value.map(Some).map_err(DecodeError::Parser),
+        None => Ok(None),
+    }
+}
+
+fn resolve_structure(
+    context: &Context,
+    elements: &[DecodedValue],
+    structure: &Structure,
+    pos: TlvPos,
+) -> DecodeResult<StructureValue> {
+    let mut components = Vec::with_capacity(elements.len());
+    let mut is_present = vec![false; structure.components.len()];
+    let mut next_index = 0;
+    for element in elements {
+        let (index, component) = match find_component(context, structure, element, next_index)? {
+            Some(component) => component,
+            // unknown extension additions cannot be represented in the value
+            None if structure.extensible => continue,
+            None => return resolve_error(element.tag.pos, "value is not a component of the type"),
+        };
+        if is_present[index] {
+            return resolve_error(
+                element.tag.pos,
+                format!(
+                    "component '{}' is present more than once",
+                    component.name.element
+                ),
+            );
+        }
+        is_present[index] = true;
+        next_index = index + 1;
+
+        let component_type = component
+            .component_type
+            .resolve(context)
+            .map_err(DecodeError::Parser)?;
+        let value = element.to_resolved_value(context, &component_type)?;
+        components.push((
+            index,
+            StructureValueComponent {
+                name: component.name.clone(),
+                value: typed_value(value.ty, value.value),
+                is_default: false,
+                default_value: get_default_value(component)?,
+            },
+        ));
+    }
+
+    for (index, (component, is_present)) in structure.components.iter().zip(&is_present).enumerate()
+    {
+        if *is_present {
+            continue;
+        }
+        // an absent component with a DEFAULT value has its DEFAULT value, like in value notation
+        if let Some(default_value) = get_default_value(component)? {
+            components.push((
+                index,
+                StructureValueComponent {
+                    name: component.name.clone(),
+                    value: default_value.clone(),
+                    is_default: true,
+                    default_value: Some(default_value),
+                },
+            ));
+            continue;
+        }
+        let is_required = !component.optional
+            && component.default_value.is_none()
+            && component.extension_addition.is_none();
+        if is_required {
+            return resolve_error(
+                pos,
+                format!("missing component '{}'", component.name.element),
+            );
+        }
+    }
+
+    // the components of a SET can be decoded in any order, but values keep the order of the type
+    components.sort_by_key(|(index, _)| *index);
+    Ok(StructureValue {
+        components: components
+            .into_iter()
+            .map(|(_, component)| component)
+            .collect(),
+    })
+}
+
+fn resolve_choice(
+    context: &Context,
+    value: &DecodedValue,
+    choice: &Choice,
+) -> DecodeResult<ChoiceValue> {
+    for alternative in &choice.alternatives {
+        let alternative_type = alternative
+            .alternative_type
+            .resolve(context)
+            .map_err(DecodeError::Parser)?;
+        if tag_matches(context, &alternative_type, &value.tag.element)? {
+            let alternative_value = value.to_resolved_value(context, &alternative_type)?;
+            return Ok(ChoiceValue {
+                alternative: alternative.name.clone(),
+                alternative_type,
+                value: Box::new(typed_value(alternative_value.ty, alternative_value.value)),
+            });
+        }
+    }
+    resolve_error(
+        value.tag.pos,
+        format!(
+            "tag {} does not match any alternative of the CHOICE",
+            value.tag.element
+        ),
+    )
+}
+
+impl DecodedValue {
+    /// Converts a value that was decoded as a value of the type into a `ResolvedValue`,
+    /// which can be encoded in any transfer syntax, compared to other values and edited.
+    ///
+    /// The components of SEQUENCE and SET values are matched by their names,
+    /// and the alternatives of CHOICE values are matched by their tags.
+    /// Components that were absent from the encoding have their DEFAULT value if the type defines one,
+    /// and are marked as default values, while unknown extension additions are ignored.
+    /// The value must satisfy the constraints of its type.
+    pub fn to_resolved_value(
+        &self,
+        context: &Context,
+        resolved_type: &ResolvedType,
+    ) -> DecodeResult<ResolvedValue> {
+        let value = strip_explicit_tag(context, self, resolved_type)?;
+        let pos = value.value_pos;
+        let builtin = match (&resolved_type.ty, &value.form) {
+            (BuiltinType::Choice(choice), _) => {
+                BuiltinValue::Choice(resolve_choice(context, value, choice)?)
+            }
+            (BuiltinType::Structure(structure), DecodedValueForm::Constructed(elements))
+                if !ber::is_real_type(&resolved_type.ty) =>
+            {
+                BuiltinValue::Structure(
+                    structure.ty,
+                    resolve_structure(context, elements, structure, pos)?,
+                )
+            }
+            (BuiltinType::StructureOf(of), DecodedValueForm::Constructed(elements)) => {
+                let component_type = of
+                    .component_type
+                    .resolve(context)
+                    .map_err(DecodeError::Parser)?;
+                BuiltinValue::StructureOf(
+                    of.ty,
+                    elements
+                        .iter()
+                        .map(|element| {
+                            let value = element.to_resolved_value(context, &component_type)?;
+                            Ok(typed_value(value.ty, value.value))
+                        })
+                        .collect::<DecodeResult<Vec<_>>>()?,
+                )
+            }
+            (_, DecodedValueForm::Primitive(kind)) => {
+                resolve_primitive(context, kind, resolved_type, pos)?
+            }
+            (ty, DecodedValueForm::Constructed(_)) => {
+                return resolve_error(
+                    pos,
+                    format!("expecting {} value, but found constructed value", ty),
+                )
+            }
+        };
+
+        // the decoders do not check the constraints of the type, so values are checked like the values of the module
+        let value = typed_value(resolved_type.clone(), builtin);
+        resolved_type
+            .ty
+            .ensure_satisfied_by_value(context, &value, resolved_type.constraint.as_ref())
+            .map_err(|err| DecodeError::Decoder {
+                message: err.kind.message(),
+                pos,
+            })?;
+        Ok(ResolvedValue {
+            ty: resolved_type.clone(),
+            value: match value.element.value {
+                ValueReference::BuiltinValue(builtin) => builtin,
+                _ => unreachable!(),
+            },
+        })
+    }
+
+    /// Converts a value that was decoded with `DecodeMode::SpecificType` into a `ResolvedValue` of the type of the mode.
+    /// See [`DecodedValue::to_resolved_value`].
+    pub fn resolve_with_mode(
+        &self,
+        context: &Context,
+        mode: &DecodeMode,
+    ) -> DecodeResult<ResolvedValue> {
+        match mode {
+            DecodeMode::Contextless => resolve_error(
+                self.tag.pos,
+                "a value decoded without a type cannot be converted",
+            ),
+            DecodeMode::SpecificType { resolved, .. } => self.to_resolved_value(context, resolved),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        compiler::{test::compile_module, Context},
+        encoding::*,
+        module::{ModuleIdentifier, QualifiedIdentifier},
+        values::ResolvedValue,
+    };
+
+    const MODULE: &str = r#"ResolveTest DEFINITIONS EXPLICIT TAGS ::= BEGIN
+
+Record ::= SEQUENCE {
+    id [0] INTEGER,
+    name [1] UTF8String OPTIONAL,
+    kind [2] Kind,
+    flags [3] IMPLICIT BIT STRING { a(0), b(1) } DEFAULT {},
+    body Body,
+    oids SEQUENCE OF OBJECT IDENTIFIER,
+    ratio REAL
+}
+
+Kind ::= ENUMERATED { small, large(10) }
+
+Ratio ::= REAL
+
+Body ::= CHOICE {
+    point [0] SEQUENCE { x INTEGER, y INTEGER },
+    text [1] IA5String
+}
+
+Letters ::= IA5String (FROM ("abc"))
+
+Digits ::= NumericString
+
+Small ::= INTEGER (0..7)
+
+Pair ::= OCTET STRING (SIZE (2))
+
+END
+"#;
+
+    fn ident(name: &str) -> QualifiedIdentifier {
+        QualifiedIdentifier::new(
+            ModuleIdentifier::with_name("ResolveTest".to_string()),
+            name.to_string(),
+        )
+    }
+
+    fn decode(context: &Context, syntax: &TransferSyntax, encoding: &[u8]) -> ResolvedValue {
+        let mode = DecodeMode::SpecificType {
+            source_ident: Some(ident("Record")),
+            component_name: None,
+            resolved: Box::new(
+                context
+                    .lookup_type(&ident("Record"))
+                    .expect("lookup_type")
+                    .ty
+                    .resolve(context)
+                    .expect("resolve"),
+            ),
+        };
+        let decoder = syntax.get_codec().decoder.expect("decoder");
+        let value = decoder(syntax, &mode, encoding, context)
+            .expect("decode")
+            .remove(0);
+        value
+            .resolve_with_mode(context, &mode)
+            .unwrap_or_else(|err| panic!("{} convert: {}", syntax, err))
+    }
+
+    fn encode(context: &Context, syntax: &TransferSyntax, value: &ResolvedValue) -> Vec<u8> {
+        let mut buf = Vec::new();
+        let encoder = syntax.get_codec().encoder.expect("encoder");
+        encoder(
+            syntax,
+            EncodeMode::Normal,
+            &EncodeOptions {
+                type_name: Some("Record".to_string()),
+                ..Default::default()
+            },
+            &mut buf,
+            context,
+            value,
+        )
+        .unwrap_or_else(|err| panic!("{} encode: {}", syntax, err.kind));
+        buf
+    }
+
+    #[test]
+    fn test_resolve_round_trip() {
+        let mut context = Context::new();
+        compile_module(&mut context, "ResolveTest.asn", MODULE);
+
+        let der = TransferSyntax::Basic(BasicEncodingKind::Distinguished);
+        let gser = TransferSyntax::String(StringEncodingKind::Generic);
+        let value = decode(
+            &context,
+            &gser,
+            br#"{ id 5, name "test", kind large, flags { a, b }, body point:{ x 1, y -2 }, oids { 1.2.840, 2.5.4.3 }, ratio 25E-2 }"#,
+        );
+        let encoding = encode(&context, &der, &value);
+
+        // every syntax, including BER with its EXPLICIT tags, must decode its own encoding into the same value
+        for syntax in TransferSyntax::syntaxes() {
+            let codec = syntax.get_codec();
+            if codec.encoder.is_none() || codec.decoder.is_none() {
+                continue;
+            }
+            let encoded = encode(&context, syntax, &value);
+            let decoded = decode(&context, syntax, &encoded);
+            assert_eq!(encode(&context, &der, &decoded), encoding, "{}", syntax);
+        }
+    }
+
+    #[test]
+    fn test_resolve_real() {
+        let mut context = Context::new();
+        compile_module(&mut context, "ResolveTest.asn", MODULE);
+
+        let resolved = context
+            .lookup_type(&ident("Ratio"))
+            .expect("lookup_type")
+            .ty
+            .resolve(&context)
+            .expect("resolve");
+        let ber = TransferSyntax::Basic(BasicEncodingKind::Basic);
+        let der = TransferSyntax::Basic(BasicEncodingKind::Distinguished);
+        let decoder = ber.get_codec().decoder.expect("decoder");
+        for (hex, expected) in [
+            // a decimal value with more digits than an f64 can hold
+            (
+                "091F03313233343536373839303132333435363738393031323334352E452D3230",
+                "091F03313233343536373839303132333435363738393031323334352E452D3230",
+            ),
+            // 3 * 16^1 and -3 * 8^1 are converted to base 2
+            ("0903A00103", "0903800403"),
+            ("0903D00103", "0903C00303"),
+        ] {
+            let encoding = hex::decode(hex).expect("hex");
+            let values =
+                decoder(&ber, &DecodeMode::Contextless, &encoding, &context).expect("decode");
+            let value = values[0]
+                .to_resolved_value(&context, &resolved)
+                .expect("to_resolved_value");
+            assert_eq!(hex::encode_upper(encode(&context, &der, &value)), expected);
+        }
+    }
+
+    #[test]
+    fn test_resolve_invalid() {
+        let mut context = Context::new();
+        compile_module(&mut context, "ResolveTest.asn", MODULE);
+
+        let resolved = context
+            .lookup_type(&ident("Record"))
+            .expect("lookup_type")
+            .ty
+            .resolve(&context)
+            .expect("resolve");
+        let ber = TransferSyntax::Basic(BasicEncodingKind::Basic);
+        let decoder = ber.get_codec().decoder.expect("decoder");
+        for hex in [
+            // missing 'kind'
+            "3017A003020105A0083006020101020101300406022A030900",
+            // 'kind' is not defined
+            "301CA003020105A2030A0107A0083006020101020101300406022A030900",
+        ] {
+            let encoding = hex::decode(hex).expect("hex");
+            let values =
+                decoder(&ber, &DecodeMode::Contextless, &encoding, &context).expect("decode");
+            assert!(values[0].to_resolved_value(&context, &resolved).is_err());
+        }
+    }
+
+    #[test]
+    fn test_resolve_constraint_violations() {
+        let mut context = Context::new();
+        compile_module(&mut context, "ResolveTest.asn", MODULE);
+
+        let jer = TransferSyntax::Json(JsonEncodingKind::Basic);
+        let decoder = jer.get_codec().decoder.expect("decoder");
+        for (name, valid, invalid) in [
+            ("Letters", r#""cab""#, r#""xyz""#),
+            ("Digits", r#""12 34""#, r#""ab""#),
+            ("Small", "7", "99"),
+            ("Pair", r#""0102""#, r#""010203""#),
+        ] {
+            let mode = DecodeMode::SpecificType {
+                source_ident: Some(ident(name)),
+                component_name: None,
+                resolved: Box::new(
+                    context
+                        .lookup_type(&ident(name))
+                        .expect("lookup_type")
+                        .ty
+                        .resolve(&context)
+                        .expect("resolve"),
+                ),
+            };
+            let resolve = |encoding: &str| {
+                decoder(&jer, &mode, encoding.as_bytes(), &context)
+                    .expect("decode")
+                    .remove(0)
+                    .resolve_with_mode(&context, &mode)
+            };
+            assert!(resolve(valid).is_ok(), "{}", name);
+            assert!(resolve(invalid).is_err(), "{}", name);
+        }
+    }
+}
@@ -2,8 +2,12 @@ use std::io::Cursor;
 
 use super::*;
 use crate::{
-    compiler::{parser::Result, Context},
-    values::{Oid, ResolvedValue},
+    compiler::{
+        parser::{Error, ErrorKind, Loc, Result},
+        Context,
+    },
+    types::{ContentsConstraint, ResolvedType, UntaggedType},
+    values::{BuiltinValue, ContainingValue, Oid, ResolvedValue, ValueResolve},
 };
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -21,6 +25,16 @@ pub enum PackedEncodingKind {
     CanonicalUnaligned,
 }
 
+impl PackedEncodingKind {
+    pub fn is_aligned(&self) -> bool {
+        matches!(self, Self::BasicAligned | Self::CanonicalAligned)
+    }
+
+    pub fn is_canonical(&self) -> bool {
+        matches!(self, Self::CanonicalAligned | Self::CanonicalUnaligned)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum XmlEncodingKind {
     Basic,
@@ -34,12 +48,40 @@ pub enum OctetEncodingKind {
     Canonical,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum JsonEncodingKind {
+    Basic,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum StringEncodingKind {
+    Generic,
+}
+
+impl XmlEncodingKind {
+    pub fn is_canonical(&self) -> bool {
+        matches!(self, Self::Canonical)
+    }
+
+    pub fn is_extended(&self) -> bool {
+        matches!(self, Self::Extended)
+    }
+}
+
+impl OctetEncodingKind {
+    pub fn is_canonical(&self) -> bool {
+        matches!(self, Self::Canonical)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum TransferSyntax {
     Basic(BasicEncodingKind),
     Packed(PackedEncodingKind),
     Xml(XmlEncodingKind),
     Octet(OctetEncodingKind),
+    Json(JsonEncodingKind),
+    String(StringEncodingKind),
 }
 
 impl Display for TransferSyntax {
@@ -51,6 +93,7 @@ impl Display for TransferSyntax {
 type EncodeFunc = fn(
     syntax: &TransferSyntax,
     mode: EncodeMode,
+    options: &EncodeOptions,
     buf: &mut Vec<u8>,
     context: &Context,
     typed_value: &ResolvedValue,
@@ -73,6 +116,28 @@ pub enum EncodeMode {
     ContentsConstraint,
 }
 
+/// How canonical encodings handle SET components and SET OF elements that are not in canonical order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SetOrdering {
+    /// Sort the components or elements into canonical order.
+    #[default]
+    Sort,
+    /// Fail to encode the value if its components or elements are not already in canonical order.
+    Verify,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EncodeOptions {
+    pub set_ordering: SetOrdering,
+    /// Whether the components of a SEQUENCE or SET value that are equal to their DEFAULT value are encoded.
+    /// Only BER, PER, UPER, OER, XER, E-XER, JER and GSER encode these components;
+    /// CER, DER, CPER, CUPER, COER and CXER never encode them (X.690 clause 11.5).
+    pub encode_default_values: bool,
+    /// The name of the type of the encoded value, which XER uses as the name of the root element.
+    /// If it is `None`, the XML name of the built-in type is used instead, for example `SEQUENCE` or `BIT_STRING`.
+    pub type_name: Option<String>,
+}
+
 impl TransferSyntaxCodec {
     pub fn new(encoder: EncodeFunc, decoder: DecodeFunc) -> TransferSyntaxCodec {
         TransferSyntaxCodec {
@@ -91,7 +156,8 @@ impl TransferSyntaxCodec {
 
 struct TransferSyntaxData {
     pub syntax: TransferSyntax,
-    pub oid: Oid,
+    /// The OID of the transfer syntax, or `None` if it is not defined by an ASN.1 standard.
+    pub oid: Option<Oid>,
     pub name: &'static str,
     pub codec: TransferSyntaxCodec,
 }
@@ -100,156 +166,156 @@ lazy_static::lazy_static! {
     static ref TRANFER_SYNTAXES: Vec<TransferSyntaxData> = vec![
         TransferSyntaxData {
             syntax: TransferSyntax::Basic(BasicEncodingKind::Basic),
-            oid: Oid(vec![
+            oid: Some(Oid(vec![
                 2, // joint-iso-itu-t
                 1, // asn1
                 1, // basic-encoding
-            ]),
+            ])),
             name: "BER",
-            codec: TransferSyntaxCodec {
-                encoder: Some(ber_encode_value),
-                decoder: None,
-            },
+            codec: TransferSyntaxCodec::new(ber_encode_value, ber_decode_value),
         },
         TransferSyntaxData {
             syntax: TransferSyntax::Basic(BasicEncodingKind::Canonical),
-            oid: Oid(vec![
+            oid: Some(Oid(vec![
                 2, // joint-iso-itu-t
                 1, // asn1
                 2, // ber-derived
                 0, // canonical-encoding
-            ]),
+            ])),
             name: "CER",
-            codec: TransferSyntaxCodec {
-                encoder: Some(ber_encode_value),
-                decoder: None,
-            },
+            codec: TransferSyntaxCodec::new(ber_encode_value, ber_decode_value),
         },
         TransferSyntaxData {
             syntax: TransferSyntax::Basic(BasicEncodingKind::Distinguished),
-            oid: Oid(vec![
+            oid: Some(Oid(vec![
                 2, // joint-iso-itu-t
                 1, // asn1
                 2, // ber-derived
                 1, // distinguished-encoding
-            ]),
+            ])),
             name: "DER",
             codec: TransferSyntaxCodec::new(ber_encode_value, ber_decode_value),
         },
         TransferSyntaxData {
             syntax: TransferSyntax::Packed(PackedEncodingKind::BasicAligned),
-            oid: Oid(vec![
+            oid: Some(Oid(vec![
                 2, // joint-iso-itu-t
                 1, // asn1
                 3, // packed-encoding
                 0, // basic
                 0, // aligned
-            ]),
+            ])),
             name: "PER",
-            codec: TransferSyntaxCodec {
-                encoder: Some(per_encode_value),
-                decoder: None,
-            },
+            codec: TransferSyntaxCodec::new(per_encode_value, per_decode_value),
         },
         TransferSyntaxData {
             syntax: TransferSyntax::Packed(PackedEncodingKind::BasicUnaligned),
-            oid: Oid(vec![
+            oid: Some(Oid(vec![
                 2, // joint-iso-itu-t
                 1, // asn1
                 3, // packed-encoding
                 0, // basic
                 1, // unaligned
-            ]),
+            ])),
             name: "UPER",
-            codec: TransferSyntaxCodec {
-                encoder: Some(per_encode_value),
-                decoder: None,
-            },
+            codec: TransferSyntaxCodec::new(per_encode_value, per_decode_value),
         },
         TransferSyntaxData {
             syntax: TransferSyntax::Packed(PackedEncodingKind::CanonicalAligned),
-            oid: Oid(vec![
+            oid: Some(Oid(vec![
                 2, // joint-iso-itu-t
                 1, // asn1
                 3, // packed-encoding
                 1, // canonical
                 0, // aligned
-            ]),
+            ])),
             name: "CPER",
-            codec: TransferSyntaxCodec {
-                encoder: Some(per_encode_value),
-                decoder: None,
-            },
+            codec: TransferSyntaxCodec::new(per_encode_value, per_decode_value),
         },
         TransferSyntaxData {
             syntax: TransferSyntax::Packed(PackedEncodingKind::CanonicalUnaligned),
-            oid: Oid(vec![
+            oid: Some(Oid(vec![
                 2, // joint-iso-itu-t
                 1, // asn1
                 3, // packed-encoding
                 1, // canonical
                 1, // unaligned
-            ]),
+            ])),
             name: "CUPER",
-            codec: TransferSyntaxCodec {
-                encoder: Some(per_encode_value),
-                decoder: None,
-            },
+            codec: TransferSyntaxCodec::new(per_encode_value, per_decode_value),
         },
         TransferSyntaxData {
             syntax: TransferSyntax::Xml(XmlEncodingKind::Basic),
-            oid: Oid(vec![
+            oid: Some(Oid(vec![
                 2, // joint-iso-itu-t
                 1, // asn1
                 5, // xer-encoding
                 0, // basic
-            ]),
+            ])),
             name: "XER",
-            codec: TransferSyntaxCodec::unsupported(),
+            codec: TransferSyntaxCodec::new(xer_encode_value, xer_decode_value),
         },
         TransferSyntaxData {
             syntax: TransferSyntax::Xml(XmlEncodingKind::Canonical),
-            oid: Oid(vec![
+            oid: Some(Oid(vec![
                 2, // joint-iso-itu-t
                 1, // asn1
                 5, // xer-encoding
                 1, // canonical
-            ]),
+            ])),
             name: "CXER",
-            codec: TransferSyntaxCodec::unsupported(),
+            codec: TransferSyntaxCodec::new(xer_encode_value, xer_decode_value),
         },
         TransferSyntaxData {
             syntax: TransferSyntax::Xml(XmlEncodingKind::Extended),
-            oid: Oid(vec![
+            oid: Some(Oid(vec![
                 2, // joint-iso-itu-t
                 1, // asn1
                 5, // xer-encoding
                 2, // extended
-            ]),
+            ])),
             name: "E-XER",
-            codec: TransferSyntaxCodec::unsupported(),
+            codec: TransferSyntaxCodec::new(xer_encode_value, xer_decode_value),
         },
         TransferSyntaxData {
             syntax: TransferSyntax::Octet(OctetEncodingKind::Basic),
-            oid: Oid(vec![
+            oid: Some(Oid(vec![
                 2, // joint-iso-itu-t
                 1, // asn1
                 6, // oer-encoding
                 0, // basic
-            ]),
+            ])),
             name: "OER",
-            codec: TransferSyntaxCodec::unsupported(),
+            codec: TransferSyntaxCodec::new(oer_encode_value, oer_decode_value),
         },
         TransferSyntaxData {
             syntax: TransferSyntax::Octet(OctetEncodingKind::Canonical),
-            oid: Oid(vec![
+            oid: Some(Oid(vec![
                 2, // joint-iso-itu-t
                 1, // asn1
                 6, // oer-encoding
                 1, // canonical
-            ]),
+            ])),
             name: "COER",
-            codec: TransferSyntaxCodec::unsupported(),
+            codec: TransferSyntaxCodec::new(oer_encode_value, oer_decode_value),
+        },
+        TransferSyntaxData {
+            syntax: TransferSyntax::Json(JsonEncodingKind::Basic),
+            oid: Some(Oid(vec![
+                2, // joint-iso-itu-t
+                1, // asn1
+                7, // jer-encoding
+                0, // basic
+            ])),
+            name: "JER",
+            codec: TransferSyntaxCodec::new(jer_encode_value, jer_decode_value),
+        },
+        TransferSyntaxData {
+            // GSER is defined by RFC 3641 rather than by an ASN.1 standard, so it does not have an OID
+            syntax: TransferSyntax::String(StringEncodingKind::Generic),
+            oid: None,
+            name: "GSER",
+            codec: TransferSyntaxCodec::new(gser_encode_value, gser_decode_value),
         },
     ];
 }
@@ -277,7 +343,7 @@ impl TransferSyntax {
 
     pub fn get_by_oid<'a>(oid: &Oid) -> Option<&'a TransferSyntax> {
         for data in TRANFER_SYNTAXES.iter() {
-            if oid == &data.oid {
+            if data.oid.as_ref() == Some(oid) {
                 return Some(&data.syntax);
             }
         }
@@ -295,8 +361,14 @@ impl TransferSyntax {
         None
     }
 
-    pub fn get_oid<'a>(&self) -> &'a Oid {
-        find_data_field!(self, oid);
+    pub fn get_oid<'a>(&self) -> Option<&'a Oid> {
+        for data in TRANFER_SYNTAXES.iter() {
+            if self == &data.syntax {
+                return data.oid.as_ref();
+            }
+        }
+
+        unreachable!()
     }
 
     pub fn get_name(&self) -> &'static str {
@@ -308,20 +380,109 @@ impl TransferSyntax {
     }
 }
 
+fn get_contents_constraint(resolved_type: &ResolvedType) -> &ContentsConstraint {
+    resolved_type
+        .constraint
+        .as_ref()
+        .map(|constraint| constraint.get_contents_constraint())
+        .expect("CONTAINING value for type without constraint")
+        .expect("CONTAINING value for type without contents constraint")
+}
+
+/// Returns the transfer syntax named by the ENCODED BY of the contents constraint of the type,
+/// or `None` if the contents constraint does not have an ENCODED BY.
+pub(crate) fn get_contents_transfer_syntax<'a>(
+    context: &Context,
+    resolved_type: &ResolvedType,
+) -> Result<Option<&'a TransferSyntax>> {
+    let Some(encoded_by) = &get_contents_constraint(resolved_type).encoded_by else {
+        return Ok(None);
+    };
+
+    let resolved = encoded_by.resolve(context)?;
+    match &resolved.value {
+        BuiltinValue::ObjectIdentifier(oid) => {
+            let oid = oid.resolve_oid(context)?;
+            match TransferSyntax::get_by_oid(&oid) {
+                Some(ts) => {
+                    if ts.get_codec().encoder.is_some() {
+                        Ok(Some(ts))
+                    } else {
+                        Err(Error {
+                            kind: ErrorKind::Ast(format!(
+                                "encoding with the {} transfer syntax is not yet implemented",
+                                ts.get_name()
+                            )),
+                            loc: encoded_by.loc,
+                        })
+                    }
+                }
+                None => Err(Error {
+                    kind: ErrorKind::Ast(format!(
+                        "the provided OBJECT IDENTIFIER ({}) does not represent a registered transfer syntax",
+                        oid
+                    )),
+                    loc: encoded_by.loc,
+                }),
+            }
+        }
+        other => Err(Error {
+            kind: ErrorKind::Ast(format!(
+                "expecting OBJECT IDENTIFIER for the transfer  found {}",
+                other.tag_type(context)?
+            )),
+            loc: encoded_by.loc,
+        }),
+    }
+}
+
+/// Returns the complete encoding of the value of a BIT STRING or OCTET STRING type with a contents constraint,
+/// with the transfer syntax named by ENCODED BY, or `default_syntax` if the contents constraint does not have an ENCODED BY.
+pub(crate) fn encode_contained_value(
+    context: &Context,
+    options: &EncodeOptions,
+    resolved_type: &ResolvedType,
+    containing: &ContainingValue,
+    default_syntax: &TransferSyntax,
+) -> Result<Vec<u8>> {
+    let ts = get_contents_transfer_syntax(context, resolved_type)?.unwrap_or(default_syntax);
+    let encoder = ts.get_codec().encoder.ok_or_else(|| Error {
+        kind: ErrorKind::Ast(format!(
+            "encoding with the {} transfer syntax is not yet implemented",
+            ts.get_name()
+        )),
+        loc: containing.value.loc,
+    })?;
+
+    // the XML encodings name the root element of the contained value after its type
+    let options = EncodeOptions {
+        type_name: match &get_contents_constraint(resolved_type).content_type.ty {
+            UntaggedType::Reference(typeref) => Some(typeref.element.name.clone()),
+            _ => None,
+        },
+        ..options.clone()
+    };
+    let value = containing.value.resolve(context)?;
+    let mut buf = Vec::new();
+    encoder(ts, EncodeMode::Normal, &options, &mut buf, context, &value)?;
+    Ok(buf)
+}
+
 fn ber_encode_value(
     syntax: &TransferSyntax,
     mode: EncodeMode,
+    options: &EncodeOptions,
     buf: &mut Vec<u8>,
     context: &Context,
     typed_value: &ResolvedValue,
 ) -> Result<()> {
-    match syntax {
-        TransferSyntax::Basic(_) => (),
+    let kind = match syntax {
+        TransferSyntax::Basic(kind) => *kind,
         other => panic!("illegal TransferSyntax (expecting Basic): {:?}", other),
     };
 
     let buf_start = buf.len();
-    ber::ber_encode_value(mode, buf, context, typed_value)?;
+    ber::ber_encode_value(kind, mode, options, buf, context, typed_value)?;
     if mode == EncodeMode::Normal {
         buf[buf_start..].reverse();
     }
@@ -346,26 +507,249 @@ fn ber_decode_value(
 fn per_encode_value(
     syntax: &TransferSyntax,
     _mode: EncodeMode,
+    options: &EncodeOptions,
     buf: &mut Vec<u8>,
     context: &Context,
     typed_value: &ResolvedValue,
 ) -> Result<()> {
-    let aligned = match syntax {
-        TransferSyntax::Packed(kind) => match kind {
-            PackedEncodingKind::BasicAligned | PackedEncodingKind::CanonicalAligned => true,
-            PackedEncodingKind::BasicUnaligned | PackedEncodingKind::CanonicalUnaligned => false,
-        },
+    let kind = match syntax {
+        TransferSyntax::Packed(kind) => *kind,
         other => panic!("illegal TransferSyntax (expecting Packed): {:?}", other),
     };
 
-    let writer = per::BitWriter::new(aligned, Cursor::new(buf));
+    let writer = per::BitWriter::new(kind.is_aligned(), Cursor::new(buf));
     let mut encoder = per::PerEncoder {
         context,
+        kind,
+        options,
         writer,
         tmp_buf: Vec::with_capacity(per::TMP_BUF_CAPACITY),
     };
-    per::per_encode_value(&mut encoder, typed_value)?;
+    per::per_encode_value(&mut encoder, typed_value, Loc::default())?;
     encoder.writer.force_align();
 
     Ok(())
 }
+
+fn per_decode_value(
+    syntax: &TransferSyntax,
+    mode: &DecodeMode,
+    buf: &[u8],
+    context: &Context,
+) -> DecodeResult<Vec<DecodedValue>> {
+    let kind = match syntax {
+        TransferSyntax::Packed(kind) => *kind,
+        other => panic!("illegal TransferSyntax (expecting Packed): {:?}", other),
+    };
+
+    let mut decoder = per::PerDecoder {
+        context,
+        kind,
+        reader: per::BitReader::new(kind.is_aligned(), buf),
+    };
+    let value = per::per_decode_value(&mut decoder, mode)?;
+    decoder.reader.force_align();
+    if !decoder.reader.is_empty() {
+        let pos = decoder.reader.byte_pos();
+        return Err(DecodeError::Decoder {
+            message: format!("{} bytes of trailing data", buf.len() - pos),
+            pos: TlvPos::new(pos, buf.len()),
+        });
+    }
+
+    Ok(vec![value])
+}
+
+fn oer_encode_value(
+    syntax: &TransferSyntax,
+    _mode: EncodeMode,
+    options: &EncodeOptions,
+    buf: &mut Vec<u8>,
+    context: &Context,
+    typed_value: &ResolvedValue,
+) -> Result<()> {
+    let kind = match syntax {
+        TransferSyntax::Octet(kind) => *kind,
+        other => panic!("illegal TransferSyntax (expecting Octet): {:?}", other),
+    };
+
+    let mut encoder = oer::OerEncoder {
+        context,
+        kind,
+        options,
+        buf,
+    };
+    oer::oer_encode_value(&mut encoder, typed_value)
+}
+
+fn xer_encode_value(
+    syntax: &TransferSyntax,
+    _mode: EncodeMode,
+    options: &EncodeOptions,
+    buf: &mut Vec<u8>,
+    context: &Context,
+    typed_value: &ResolvedValue,
+) -> Result<()> {
+    let kind = match syntax {
+        TransferSyntax::Xml(kind) => *kind,
+        other => panic!("illegal TransferSyntax (expecting Xml): {:?}", other),
+    };
+
+    let mut encoder = xer::XerEncoder {
+        context,
+        kind,
+        options,
+        buf,
+        depth: 0,
+    };
+    xer::xer_encode_value(&mut encoder, typed_value)
+}
+
+fn oer_decode_value(
+    syntax: &TransferSyntax,
+    mode: &DecodeMode,
+    buf: &[u8],
+    context: &Context,
+) -> DecodeResult<Vec<DecodedValue>> {
+    let kind = match syntax {
+        TransferSyntax::Octet(kind) => *kind,
+        other => panic!("illegal TransferSyntax (expecting Octet): {:?}", other),
+    };
+
+    let mut decoder = oer::OerDecoder {
+        context,
+        kind,
+        buf,
+        pos: 0,
+    };
+    let value = oer::oer_decode_value(&mut decoder, mode)?;
+    if decoder.pos != buf.len() {
+        return Err(DecodeError::Decoder {
+            message: format!("{} bytes of trailing data", buf.len() - decoder.pos),
+            pos: TlvPos::new(decoder.pos, buf.len()),
+        });
+    }
+
+    Ok(vec![value])
+}
+
+fn xer_decode_value(
+    syntax: &TransferSyntax,
+    mode: &DecodeMode,
+    buf: &[u8],
+    context: &Context,
+) -> DecodeResult<Vec<DecodedValue>> {
+    let kind = match syntax {
+        TransferSyntax::Xml(kind) => *kind,
+        other => panic!("illegal TransferSyntax (expecting Xml): {:?}", other),
+    };
+
+    let mut decoder = xer::XerDecoder {
+        context,
+        kind,
+        buf,
+        pos: 0,
+    };
+    let value = xer::xer_decode_value(&mut decoder, mode)?;
+    if decoder.pos != buf.len() {
+        return Err(DecodeError::Decoder {
+            message: format!("{} bytes of trailing data", buf.len() - decoder.pos),
+            pos: TlvPos::new(decoder.pos, buf.len()),
+        });
+    }
+
+    Ok(vec![value])
+}
+
+fn jer_encode_value(
+    syntax: &TransferSyntax,
+    _mode: EncodeMode,
+    options: &EncodeOptions,
+    buf: &mut Vec<u8>,
+    context: &Context,
+    typed_value: &ResolvedValue,
+) -> Result<()> {
+    if !matches!(syntax, TransferSyntax::Json(_)) {
+        panic!("illegal TransferSyntax (expecting Json): {:?}", syntax);
+    }
+
+    let mut encoder = jer::JerEncoder {
+        context,
+        options,
+        buf,
+        depth: 0,
+    };
+    jer::jer_encode_value(&mut encoder, typed_value)
+}
+
+fn jer_decode_value(
+    syntax: &TransferSyntax,
+    mode: &DecodeMode,
+    buf: &[u8],
+    context: &Context,
+) -> DecodeResult<Vec<DecodedValue>> {
+    if !matches!(syntax, TransferSyntax::Json(_)) {
+        panic!("illegal TransferSyntax (expecting Json): {:?}", syntax);
+    }
+
+    let mut decoder = jer::JerDecoder {
+        context,
+        buf,
+        pos: 0,
+    };
+    let value = jer::jer_decode_value(&mut decoder, mode)?;
+    if decoder.pos != buf.len() {
+        return Err(DecodeError::Decoder {
+            message: format!("{} bytes of trailing data", buf.len() - decoder.pos),
+            pos: TlvPos::new(decoder.pos, buf.len()),
+        });
+    }
+
+    Ok(vec![value])
+}
+
+fn gser_encode_value(
+    syntax: &TransferSyntax,
+    _mode: EncodeMode,
+    options: &EncodeOptions,
+    buf: &mut Vec<u8>,
+    context: &Context,
+    typed_value: &ResolvedValue,
+) -> Result<()> {
+    if !matches!(syntax, TransferSyntax::String(_)) {
+        panic!("illegal TransferSyntax (expecting String): {:?}", syntax);
+    }
+
+    let mut encoder = gser::GserEncoder {
+        context,
+        options,
+        buf,
+    };
+    gser::gser_encode_value(&mut encoder, typed_value)
+}
+
+fn gser_decode_value(
+    syntax: &TransferSyntax,
+    mode: &DecodeMode,
+    buf: &[u8],
+    context: &Context,
+) -> DecodeResult<Vec<DecodedValue>> {
+    if !matches!(syntax, TransferSyntax::String(_)) {
+        panic!("illegal TransferSyntax (expecting String): {:?}", syntax);
+    }
+
+    let mut decoder = gser::GserDecoder {
+        context,
+        buf,
+        pos: 0,
+    };
+    let value = gser::gser_decode_value(&mut decoder, mode)?;
+    if decoder.pos != buf.len() {
+        return Err(DecodeError::Decoder {
+            message: format!("{} bytes of trailing data", buf.len() - decoder.pos),
+            pos: TlvPos::new(decoder.pos, buf.len()),
+        });
+    }
+
+    Ok(vec![value])
+}
@@ -0,0 +1,1932 @@
+use std::str::FromStr;
+
+use base64::Engine;
+use num::BigInt;
+
+use crate::{
+    compiler::{
+        parser::{AstElement, Error, ErrorKind},
+        Context,
+    },
+    encoding::*,
+    module::QualifiedIdentifier,
+    types::*,
+    values::*,
+};
+
+use super::{
+    escape_attribute_value, get_builtin_type_name, get_instructions, get_type_name,
+    is_value_list_type, QualifiedName, XerDecoder,
+};
+
+fn decoder_error<T>(start: usize, end: usize, message: impl Into<String>) -> DecodeResult<T> {
+    Err(DecodeError::Decoder {
+        message: message.into(),
+        pos: TlvPos::new(start, end),
+    })
+}
+
+fn parser_error(err: crate::compiler::parser::Error) -> DecodeError {
+    DecodeError::Parser(err)
+}
+
+/// An attribute of a start tag.
+struct Attribute {
+    /// The namespace prefix of the name of the attribute, such as `xsi` in `xsi:type`.
+    prefix: Option<String>,
+    name: String,
+    value: String,
+    pos: TlvPos,
+    /// The position of the value, not including its quotes.
+    value_pos: TlvPos,
+}
+
+impl Attribute {
+    fn is_xsi(&self, name: &str) -> bool {
+        self.prefix.as_deref() == Some("xsi") && self.name == name
+    }
+
+    fn qualified_name(&self) -> String {
+        match &self.prefix {
+            Some(prefix) => format!("{}:{}", prefix, self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+/// The start tag of an element.
+struct StartTag {
+    /// The name of the element, which does not include its namespace prefix in EXTENDED-XER.
+    name: String,
+    /// The name of the element as it is written, which is also the name of its end tag.
+    qualified_name: String,
+    /// The attributes of the element, not including namespace declarations.
+    attributes: Vec<Attribute>,
+    pos: TlvPos,
+    /// Whether the tag is an empty-element tag (`<name/>`), which has no contents or end tag.
+    is_empty: bool,
+}
+
+/// An item of the contents of an element that does not have child elements.
+enum ContentItem {
+    Text(String, TlvPos),
+    /// An empty element, such as `<true/>` or the name of a control character in a character string.
+    Element(String, TlvPos),
+}
+
+fn remaining<'a>(decoder: &XerDecoder<'a>) -> &'a [u8] {
+    &decoder.buf[decoder.pos..]
+}
+
+/// Skips the characters up to and including `terminator`.
+fn skip_past(decoder: &mut XerDecoder<'_>, terminator: &[u8]) -> DecodeResult<()> {
+    let start = decoder.pos;
+    match remaining(decoder)
+        .windows(terminator.len())
+        .position(|window| window == terminator)
+    {
+        Some(offset) => {
+            decoder.pos += offset + terminator.len();
+            Ok(())
+        }
+        None => decoder_error(
+            start,
+            decoder.buf.len(),
+            format!(
+                "missing '{}' in XML document",
+                String::from_utf8_lossy(terminator)
+            ),
+        ),
+    }
+}
+
+fn skip_whitespace(decoder: &mut XerDecoder<'_>) {
+    while decoder
+        .buf
+        .get(decoder.pos)
+        .is_some_and(|ch| ch.is_ascii_whitespace())
+    {
+        decoder.pos += 1;
+    }
+}
+
+/// Skips the whitespace, comments, processing instructions and XML declarations before the next tag.
+fn skip_misc(decoder: &mut XerDecoder<'_>) -> DecodeResult<()> {
+    loop {
+        let rest = remaining(decoder);
+        if rest.first().is_some_and(|ch| ch.is_ascii_whitespace()) {
+            decoder.pos += 1;
+        } else if rest.starts_with(b"<!--") {
+            skip_past(decoder, b"-->")?;
+        } else if rest.starts_with(b"<?") {
+            skip_past(decoder, b"?>")?;
+        } else {
+            return Ok(());
+        }
+    }
+}
+
+fn is_name_end(ch: u8) -> bool {
+    ch.is_ascii_whitespace() || ch == b'/' || ch == b'>' || ch == b'='
+}
+
+fn read_name(decoder: &mut XerDecoder<'_>) -> DecodeResult<String> {
+    let start = decoder.pos;
+    let len = remaining(decoder)
+        .iter()
+        .position(|ch| is_name_end(*ch))
+        .unwrap_or(decoder.buf.len() - start);
+    decoder.pos += len;
+    if len == 0 {
+        return decoder_error(start, decoder.pos, "missing XML element name");
+    }
+    match std::str::from_utf8(&decoder.buf[start..decoder.pos]) {
+        Ok(name) => Ok(name.to_string()),
+        Err(_) => decoder_error(start, decoder.pos, "XML element name is not UTF-8"),
+    }
+}
+
+/// Returns the name without its namespace prefix.
+fn strip_namespace_prefix(name: &str) -> &str {
+    match name.split_once(':') {
+        Some((_, name)) => name,
+        None => name,
+    }
+}
+
+/// Reads an attribute of a start tag, or returns `None` if the attribute is a namespace declaration.
+fn read_attribute(decoder: &mut XerDecoder<'_>) -> DecodeResult<Option<Attribute>> {
+    let start = decoder.pos;
+    let qualified_name = read_name(decoder)?;
+    skip_whitespace(decoder);
+    if decoder.buf.get(decoder.pos) != Some(&b'=') {
+        return decoder_error(
+            start,
+            decoder.pos,
+            format!("XML attribute '{}' does not have a value", qualified_name),
+        );
+    }
+    decoder.pos += 1;
+    skip_whitespace(decoder);
+    let quote = match decoder.buf.get(decoder.pos) {
+        Some(quote @ (b'"' | b'\'')) => *quote,
+        _ => {
+            return decoder_error(
+                start,
+                decoder.pos,
+                format!("value of XML attribute '{}' is not quoted", qualified_name),
+            )
+        }
+    };
+    decoder.pos += 1;
+    let value_start = decoder.pos;
+    let len = match remaining(decoder).iter().position(|ch| *ch == quote) {
+        Some(len) => len,
+        None => {
+            return decoder_error(
+                start,
+                decoder.buf.len(),
+                format!("value of XML attribute '{}' is not closed", qualified_name),
+            )
+        }
+    };
+    decoder.pos += len + 1;
+    let value_pos = TlvPos::new(value_start, value_start + len);
+    let value = match std::str::from_utf8(&decoder.buf[value_start..value_start + len]) {
+        Ok(value) => unescape_character_data(value, value_pos)?,
+        Err(_) => {
+            return decoder_error(
+                value_pos.start,
+                value_pos.end,
+                "XML attribute value is not UTF-8",
+            )
+        }
+    };
+
+    if qualified_name == "xmlns" || qualified_name.starts_with("xmlns:") {
+        return Ok(None);
+    }
+    let (prefix, name) = match qualified_name.split_once(':') {
+        Some((prefix, name)) => (Some(prefix.to_string()), name.to_string()),
+        None => (None, qualified_name),
+    };
+    Ok(Some(Attribute {
+        prefix,
+        name,
+        value,
+        pos: TlvPos::new(start, decoder.pos),
+        value_pos,
+    }))
+}
+
+/// Reads the next start tag and its attributes.
+fn read_start_tag(decoder: &mut XerDecoder<'_>) -> DecodeResult<StartTag> {
+    skip_misc(decoder)?;
+    let start = decoder.pos;
+    let rest = remaining(decoder);
+    if !rest.starts_with(b"<") || rest.starts_with(b"</") {
+        return decoder_error(start, start, "expecting an XML start tag");
+    }
+    decoder.pos += 1;
+    let qualified_name = read_name(decoder)?;
+
+    let mut attributes = Vec::new();
+    let is_empty = loop {
+        skip_whitespace(decoder);
+        let rest = remaining(decoder);
+        if rest.starts_with(b">") {
+            decoder.pos += 1;
+            break false;
+        } else if rest.starts_with(b"/>") {
+            decoder.pos += 2;
+            break true;
+        } else if rest.is_empty() {
+            return decoder_error(start, decoder.pos, "XML start tag is not closed");
+        } else if let Some(attribute) = read_attribute(decoder)? {
+            attributes.push(attribute);
+        }
+    };
+
+    let name = match decoder.kind {
+        XmlEncodingKind::Extended => strip_namespace_prefix(&qualified_name).to_string(),
+        XmlEncodingKind::Basic | XmlEncodingKind::Canonical => qualified_name.clone(),
+    };
+    Ok(StartTag {
+        name,
+        qualified_name,
+        attributes,
+        pos: TlvPos::new(start, decoder.pos),
+        is_empty,
+    })
+}
+
+/// Reads the end tag of the element, returning its position.
+fn read_end_tag(decoder: &mut XerDecoder<'_>, start_tag: &StartTag) -> DecodeResult<TlvPos> {
+    if start_tag.is_empty {
+        return Ok(TlvPos::new(start_tag.pos.end, start_tag.pos.end));
+    }
+
+    skip_misc(decoder)?;
+    let start = decoder.pos;
+    if !remaining(decoder).starts_with(b"</") {
+        return decoder_error(
+            start,
+            start,
+            format!(
+                "expecting end tag of XML element '{}'",
+                start_tag.qualified_name
+            ),
+        );
+    }
+    decoder.pos += 2;
+    let name = read_name(decoder)?;
+    skip_whitespace(decoder);
+    if decoder.buf.get(decoder.pos) != Some(&b'>') {
+        return decoder_error(start, decoder.pos, "XML end tag is not closed");
+    }
+    decoder.pos += 1;
+    if name != start_tag.qualified_name {
+        return decoder_error(
+            start,
+            decoder.pos,
+            format!(
+                "end tag '{}' does not match start tag '{}'",
+                name, start_tag.qualified_name
+            ),
+        );
+    }
+    Ok(TlvPos::new(start, decoder.pos))
+}
+
+/// Returns whether the next tag is an end tag, ignoring whitespace and comments.
+fn is_at_end_tag(decoder: &mut XerDecoder<'_>) -> DecodeResult<bool> {
+    skip_misc(decoder)?;
+    Ok(remaining(decoder).starts_with(b"</"))
+}
+
+/// Skips the contents of the element with the start tag, followed by its end tag.
+/// Returns the position of the end tag.
+fn skip_element(decoder: &mut XerDecoder<'_>, start_tag: &StartTag) -> DecodeResult<TlvPos> {
+    if !start_tag.is_empty {
+        loop {
+            let rest = remaining(decoder);
+            if rest.starts_with(b"</") {
+                break;
+            } else if rest.starts_with(b"<!--") {
+                skip_past(decoder, b"-->")?;
+            } else if rest.starts_with(b"<?") {
+                skip_past(decoder, b"?>")?;
+            } else if rest.starts_with(b"<") {
+                let tag = read_start_tag(decoder)?;
+                skip_element(decoder, &tag)?;
+            } else if rest.is_empty() {
+                return decoder_error(
+                    start_tag.pos.start,
+                    decoder.pos,
+                    format!("XML element '{}' is not closed", start_tag.qualified_name),
+                );
+            } else {
+                decoder.pos += rest.iter().position(|ch| *ch == b'<').unwrap_or(rest.len());
+            }
+        }
+    }
+    read_end_tag(decoder, start_tag)
+}
+
+/// Replaces the entity and character references in XML character data with their characters.
+fn unescape_character_data(str: &str, pos: TlvPos) -> DecodeResult<String> {
+    let mut unescaped = String::with_capacity(str.len());
+    let mut rest = str;
+    while let Some(index) = rest.find('&') {
+        unescaped.push_str(&rest[..index]);
+        rest = &rest[index..];
+        let end = match rest.find(';') {
+            Some(end) => end,
+            None => return decoder_error(pos.start, pos.end, "XML reference is not terminated"),
+        };
+        let ch = match &rest[1..end] {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            reference => match reference.strip_prefix("#x") {
+                Some(hex) => u32::from_str_radix(hex, 16).ok().and_then(char::from_u32),
+                None => reference
+                    .strip_prefix('#')
+                    .and_then(|dec| dec.parse().ok())
+                    .and_then(char::from_u32),
+            },
+        };
+        match ch {
+            Some(ch) => unescaped.push(ch),
+            None => {
+                return decoder_error(
+                    pos.start,
+                    pos.end,
+                    format!("unknown XML reference '{}'", &rest[..=end]),
+                )
+            }
+        }
+        rest = &rest[end + 1..];
+    }
+    unescaped.push_str(rest);
+    Ok(unescaped)
+}
+
+/// Reads the character data and empty elements of an element that does not have child elements,
+/// followed by its end tag.
+fn read_content_items(
+    decoder: &mut XerDecoder<'_>,
+    start_tag: &StartTag,
+) -> DecodeResult<(Vec<ContentItem>, TlvPos)> {
+    let mut items = Vec::new();
+    if !start_tag.is_empty {
+        loop {
+            let rest = remaining(decoder);
+            if rest.starts_with(b"</") {
+                break;
+            } else if rest.starts_with(b"<!--") {
+                skip_past(decoder, b"-->")?;
+            } else if rest.starts_with(b"<?") {
+                skip_past(decoder, b"?>")?;
+            } else if rest.starts_with(b"<") {
+                let tag = read_start_tag(decoder)?;
+                if !tag.is_empty {
+                    return decoder_error(
+                        tag.pos.start,
+                        tag.pos.end,
+                        format!("unexpected child element '{}'", tag.name),
+                    );
+                }
+                items.push(ContentItem::Element(tag.name, tag.pos));
+            } else if rest.is_empty() {
+                return decoder_error(
+                    start_tag.pos.start,
+                    decoder.pos,
+                    format!("XML element '{}' is not closed", start_tag.qualified_name),
+                );
+            } else {
+                let start = decoder.pos;
+                let len = rest.iter().position(|ch| *ch == b'<').unwrap_or(rest.len());
+                decoder.pos += len;
+                let pos = TlvPos::new(start, decoder.pos);
+                let text = match std::str::from_utf8(&rest[..len]) {
+                    Ok(text) => text,
+                    Err(_) => return decoder_error(pos.start, pos.end, "XML text is not UTF-8"),
+                };
+                items.push(ContentItem::Text(unescape_character_data(text, pos)?, pos));
+            }
+        }
+    }
+    let end_pos = read_end_tag(decoder, start_tag)?;
+    Ok((items, end_pos))
+}
+
+/// Returns the text of the contents, ignoring leading and trailing whitespace,
+/// or an error if the contents contain an empty element.
+fn get_text(items: &[ContentItem], start_tag: &StartTag) -> DecodeResult<String> {
+    let mut text = String::new();
+    for item in items {
+        match item {
+            ContentItem::Text(str, _) => text.push_str(str),
+            ContentItem::Element(name, pos) => {
+                return decoder_error(
+                    pos.start,
+                    pos.end,
+                    format!(
+                        "unexpected element '{}' in the contents of '{}'",
+                        name, start_tag.name
+                    ),
+                )
+            }
+        }
+    }
+    Ok(text.trim().to_string())
+}
+
+/// Returns the names of the empty elements of the contents,
+/// or an error if the contents contain both elements and text that is not whitespace.
+fn get_element_names(items: &[ContentItem]) -> DecodeResult<Vec<(&str, TlvPos)>> {
+    let mut names = Vec::new();
+    if !items
+        .iter()
+        .any(|item| matches!(item, ContentItem::Element(..)))
+    {
+        return Ok(names);
+    }
+    for item in items {
+        match item {
+            ContentItem::Text(str, pos) => {
+                if !str.trim().is_empty() {
+                    return decoder_error(pos.start, pos.end, "unexpected XML text");
+                }
+            }
+            ContentItem::Element(name, pos) => names.push((name.as_str(), *pos)),
+        }
+    }
+    Ok(names)
+}
+
+/// Returns the name of the single empty element of the contents.
+fn get_single_element_name<'a>(
+    items: &'a [ContentItem],
+    start_tag: &StartTag,
+) -> DecodeResult<Option<(&'a str, TlvPos)>> {
+    let names = get_element_names(items)?;
+    match names.len() {
+        0 => Ok(None),
+        1 => Ok(Some(names[0])),
+        _ => decoder_error(
+            start_tag.pos.start,
+            names[1].1.end,
+            format!("'{}' must contain a single element", start_tag.name),
+        ),
+    }
+}
+
+fn get_integer(context: &Context, value: &AstElement<TypedValue>) -> DecodeResult<BigInt> {
+    match value.resolve(context).map_err(parser_error)?.value {
+        BuiltinValue::Integer(num) => Ok(num),
+        _ => unreachable!("named number value is not an INTEGER"),
+    }
+}
+
+fn find_named_number(
+    context: &Context,
+    named_numbers: Option<&Vec<NamedNumber>>,
+    name: &str,
+) -> DecodeResult<Option<BigInt>> {
+    for named_number in named_numbers.into_iter().flatten() {
+        if named_number.name.element == name {
+            return Ok(Some(get_integer(context, &named_number.value)?));
+        }
+    }
+    Ok(None)
+}
+
+fn parse_number<T: FromStr>(text: &str, pos: TlvPos, type_name: &str) -> DecodeResult<T> {
+    match text.parse() {
+        Ok(num) => Ok(num),
+        Err(_) => decoder_error(
+            pos.start,
+            pos.end,
+            format!("'{}' is not a valid {} value", text, type_name),
+        ),
+    }
+}
+
+/// Returns the mode of a value of a known type, which is the only mode that XER can decode.
+fn get_specific_type(
+    mode: &DecodeMode,
+) -> (&Option<QualifiedIdentifier>, &Option<String>, &ResolvedType) {
+    match mode {
+        DecodeMode::SpecificType {
+            source_ident,
+            component_name,
+            resolved,
+        } => (source_ident, component_name, resolved),
+        DecodeMode::Contextless => unreachable!(),
+    }
+}
+
+/// Returns whether the values of the type are written as child elements, rather than as character data.
+fn is_constructed_type(ty: &BuiltinType) -> bool {
+    match ty {
+        BuiltinType::Structure(_) => !ber::is_real_type(ty),
+        BuiltinType::StructureOf(_) | BuiltinType::Choice(_) => true,
+        _ => false,
+    }
+}
+
+fn xer_decode_boolean(items: &[ContentItem], start_tag: &StartTag) -> DecodeResult<bool> {
+    let pos = start_tag.pos;
+    match get_single_element_name(items, start_tag)? {
+        Some(("true", _)) => Ok(true),
+        Some(("false", _)) => Ok(false),
+        Some((name, pos)) => decoder_error(
+            pos.start,
+            pos.end,
+            format!("'{}' is not a valid BOOLEAN value", name),
+        ),
+        None => match get_text(items, start_tag)?.as_str() {
+            "true" | "1" => Ok(true),
+            "false" | "0" => Ok(false),
+            text => decoder_error(
+                pos.start,
+                pos.end,
+                format!("'{}' is not a valid BOOLEAN value", text),
+            ),
+        },
+    }
+}
+
+fn xer_decode_integer(
+    decoder: &XerDecoder<'_>,
+    integer: &IntegerType,
+    items: &[ContentItem],
+    start_tag: &StartTag,
+) -> DecodeResult<BigInt> {
+    if let Some((name, pos)) = get_single_element_name(items, start_tag)? {
+        return match find_named_number(decoder.context, integer.named_values.as_ref(), name)? {
+            Some(num) => Ok(num),
+            None => decoder_error(
+                pos.start,
+                pos.end,
+                format!("INTEGER does not have a named number '{}'", name),
+            ),
+        };
+    }
+    parse_number(&get_text(items, start_tag)?, start_tag.pos, "INTEGER")
+}
+
+fn get_enumeration_item_value(
+    decoder: &XerDecoder<'_>,
+    item: &EnumerationItem,
+    pos: TlvPos,
+) -> DecodeResult<i64> {
+    match &item.value {
+        EnumerationItemValue::Implied(num) => Ok(*num),
+        EnumerationItemValue::Specified(value) => {
+            match i64::try_from(get_integer(decoder.context, value)?) {
+                Ok(num) => Ok(num),
+                Err(_) => decoder_error(
+                    pos.start,
+                    pos.end,
+                    "ENUMERATED value out of bounds for signed 64-bit integer",
+                ),
+            }
+        }
+    }
+}
+
+/// Decodes an ENUMERATED value, which is an empty element named after the item.
+/// In EXTENDED-XER, the value can also be the identifier of the item as text,
+/// or the number of the item if the type has the `USE-NUMBER` encoding instruction.
+fn xer_decode_enumerated(
+    decoder: &XerDecoder<'_>,
+    enumerated: &EnumeratedType,
+    instructions: &[XerEncodingInstruction],
+    items: &[ContentItem],
+    start_tag: &StartTag,
+) -> DecodeResult<i64> {
+    let (name, pos) = match get_single_element_name(items, start_tag)? {
+        Some((name, pos)) => (name.to_string(), pos),
+        None if decoder.kind.is_extended() => {
+            let text = get_text(items, start_tag)?;
+            if instructions.contains(&XerEncodingInstruction::UseNumber) {
+                let num: i64 = parse_number(&text, start_tag.pos, "ENUMERATED")?;
+                for item in &enumerated.items {
+                    if get_enumeration_item_value(decoder, item, start_tag.pos)? == num {
+                        return Ok(num);
+                    }
+                }
+                return decoder_error(
+                    start_tag.pos.start,
+                    start_tag.pos.end,
+                    format!("ENUMERATED item with value {} is not defined", num),
+                );
+            }
+            (text, start_tag.pos)
+        }
+        None => {
+            return decoder_error(
+                start_tag.pos.start,
+                start_tag.pos.end,
+                "ENUMERATED value must be an empty element",
+            )
+        }
+    };
+    let item = match enumerated
+        .items
+        .iter()
+        .find(|item| item.name.element == name)
+    {
+        Some(item) => item,
+        None => {
+            return decoder_error(
+                pos.start,
+                pos.end,
+                format!("ENUMERATED item '{}' is not defined", name),
+            )
+        }
+    };
+    get_enumeration_item_value(decoder, item, pos)
+}
+
+fn xer_decode_real(
+    decoder: &XerDecoder<'_>,
+    items: &[ContentItem],
+    start_tag: &StartTag,
+) -> DecodeResult<RealValue> {
+    if let Some((name, pos)) = get_single_element_name(items, start_tag)? {
+        return match name {
+            "PLUS-INFINITY" => Ok(RealValue::PlusInfinity),
+            "MINUS-INFINITY" => Ok(RealValue::MinusInfinity),
+            "NOT-A-NUMBER" => Ok(RealValue::NotANumber),
+            _ => decoder_error(
+                pos.start,
+                pos.end,
+                format!("'{}' is not a valid REAL value", name),
+            ),
+        };
+    }
+
+    let text = get_text(items, start_tag)?;
+    if decoder.kind.is_extended() {
+        // the special values of EXTENDED-XER attributes and list items
+        match text.as_str() {
+            "INF" => return Ok(RealValue::PlusInfinity),
+            "-INF" => return Ok(RealValue::MinusInfinity),
+            "NaN" => return Ok(RealValue::NotANumber),
+            _ => (),
+        }
+    }
+    // only decimal numbers are permitted, which excludes the forms of infinity and NaN that Rust parses
+    if !text
+        .chars()
+        .all(|ch| ch.is_ascii_digit() || matches!(ch, '.' | 'e' | 'E' | '+' | '-'))
+    {
+        return decoder_error(
+            start_tag.pos.start,
+            start_tag.pos.end,
+            format!("'{}' is not a valid REAL value", text),
+        );
+    }
+    parse_number(&text, start_tag.pos, "REAL")
+}
+
+fn xer_decode_bit_string(
+    decoder: &XerDecoder<'_>,
+    bit_string: &BitStringType,
+    items: &[ContentItem],
+    start_tag: &StartTag,
+) -> DecodeResult<BitStringValue> {
+    let mut bits = Vec::new();
+    let names = get_element_names(items)?;
+    if !names.is_empty() {
+        for (name, pos) in names {
+            let bit =
+                match find_named_number(decoder.context, bit_string.named_bits.as_ref(), name)? {
+                    Some(bit) => bit,
+                    None => {
+                        return decoder_error(
+                            pos.start,
+                            pos.end,
+                            format!("BIT STRING does not have a named bit '{}'", name),
+                        )
+                    }
+                };
+            let bit = usize::try_from(bit).expect("named bit is out of bounds");
+            if bits.len() <= bit {
+                bits.resize(bit + 1, false);
+            }
+            bits[bit] = true;
+        }
+    } else {
+        for ch in get_text(items, start_tag)?.chars() {
+            match ch {
+                '0' => bits.push(false),
+                '1' => bits.push(true),
+                ch if ch.is_whitespace() => (),
+                ch => {
+                    return decoder_error(
+                        start_tag.pos.start,
+                        start_tag.pos.end,
+                        format!("'{}' is not a valid BIT STRING character", ch),
+                    )
+                }
+            }
+        }
+    }
+
+    let mut data = vec![0; bits.len().div_ceil(8)];
+    for (i, bit) in bits.iter().enumerate() {
+        if *bit {
+            data[i / 8] |= 0x80 >> (i % 8);
+        }
+    }
+    Ok(BitStringValue {
+        data,
+        unused_bits: ((8 - bits.len() % 8) % 8) as u8,
+    })
+}
+
+/// Decodes an OCTET STRING value, which is written in hexadecimal,
+/// or in Base64 if the type has the `BASE64` encoding instruction.
+fn xer_decode_octet_string(
+    instructions: &[XerEncodingInstruction],
+    items: &[ContentItem],
+    start_tag: &StartTag,
+) -> DecodeResult<Vec<u8>> {
+    let text = get_text(items, start_tag)?
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+        .collect::<String>();
+    let bytes = if instructions.contains(&XerEncodingInstruction::Base64) {
+        base64::engine::general_purpose::STANDARD.decode(&text).ok()
+    } else {
+        hex::decode(&text).ok()
+    };
+    match bytes {
+        Some(bytes) => Ok(bytes),
+        None => decoder_error(
+            start_tag.pos.start,
+            start_tag.pos.end,
+            format!("'{}' is not a valid OCTET STRING value", text),
+        ),
+    }
+}
+
+fn xer_decode_oid(items: &[ContentItem], start_tag: &StartTag) -> DecodeResult<Oid> {
+    let text = get_text(items, start_tag)?;
+    match Oid::parse_string(&text) {
+        Ok(oid) => Ok(oid),
+        Err(_) => decoder_error(
+            start_tag.pos.start,
+            start_tag.pos.end,
+            format!("'{}' is not a valid OBJECT IDENTIFIER value", text),
+        ),
+    }
+}
+
+/// Returns the string of the contents, where the empty elements are the names of control characters.
+/// Unlike the values of other types, leading and trailing whitespace is significant,
+/// unless the type has the `WHITESPACE` encoding instruction.
+fn xer_decode_character_string(
+    instructions: &[XerEncodingInstruction],
+    items: &[ContentItem],
+) -> DecodeResult<String> {
+    let mut str = String::new();
+    for item in items {
+        match item {
+            ContentItem::Text(text, _) => str.push_str(text),
+            ContentItem::Element(name, pos) => {
+                match super::CONTROL_CHARACTER_NAMES
+                    .iter()
+                    .position(|control| *control == Some(name.as_str()))
+                {
+                    Some(ch) => str.push(ch as u8 as char),
+                    None => {
+                        return decoder_error(
+                            pos.start,
+                            pos.end,
+                            format!("'{}' is not the name of a control character", name),
+                        )
+                    }
+                }
+            }
+        }
+    }
+    Ok(
+        match instructions
+            .iter()
+            .find_map(|instruction| match instruction {
+                XerEncodingInstruction::Whitespace(whitespace) => Some(whitespace),
+                _ => None,
+            }) {
+            Some(XerWhitespace::Replace) => str.replace(['\t', '\n', '\r'], " "),
+            Some(XerWhitespace::Collapse) => str.split_whitespace().collect::<Vec<_>>().join(" "),
+            None => str,
+        },
+    )
+}
+
+/// Decodes the contents of an element of a type that is not constructed.
+fn xer_decode_primitive(
+    decoder: &XerDecoder<'_>,
+    resolved: &ResolvedType,
+    items: &[ContentItem],
+    start_tag: &StartTag,
+    end_pos: TlvPos,
+) -> DecodeResult<DecodedValueKind> {
+    let instructions = get_instructions(decoder.kind, &resolved.xer_instructions);
+    // an empty element has the value of the DEFAULT-FOR-EMPTY encoding instruction
+    let default_items;
+    let items = match instructions
+        .iter()
+        .find_map(|instruction| match instruction {
+            XerEncodingInstruction::DefaultForEmpty(default) => Some(default),
+            _ => None,
+        }) {
+        Some(default) if items.is_empty() => {
+            default_items = [ContentItem::Text(default.clone(), start_tag.pos)];
+            &default_items[..]
+        }
+        _ => items,
+    };
+
+    Ok(match &resolved.ty {
+        BuiltinType::Boolean => DecodedValueKind::Boolean(xer_decode_boolean(items, start_tag)?),
+        BuiltinType::Integer(integer) => {
+            DecodedValueKind::Integer(xer_decode_integer(decoder, integer, items, start_tag)?)
+        }
+        BuiltinType::Enumerated(enumerated) => DecodedValueKind::Enumerated(xer_decode_enumerated(
+            decoder,
+            enumerated,
+            instructions,
+            items,
+            start_tag,
+        )?),
+        ty if ber::is_real_type(ty) => {
+            DecodedValueKind::Real(xer_decode_real(decoder, items, start_tag)?)
+        }
+        BuiltinType::BitString(bit_string) => DecodedValueKind::BitString(xer_decode_bit_string(
+            decoder, bit_string, items, start_tag,
+        )?),
+        BuiltinType::OctetString => {
+            DecodedValueKind::OctetString(xer_decode_octet_string(instructions, items, start_tag)?)
+        }
+        BuiltinType::Null => {
+            if !get_text(items, start_tag)?.is_empty() {
+                return decoder_error(start_tag.pos.start, end_pos.end, "NULL cannot have a value");
+            }
+            DecodedValueKind::Null
+        }
+        BuiltinType::ObjectIdentifier => {
+            DecodedValueKind::ObjectIdentifier(xer_decode_oid(items, start_tag)?)
+        }
+        BuiltinType::RelativeOid => {
+            DecodedValueKind::RelativeOid(xer_decode_oid(items, start_tag)?)
+        }
+        BuiltinType::CharacterString(tag_type) => DecodedValueKind::CharacterString(
+            *tag_type,
+            xer_decode_character_string(instructions, items)?,
+        ),
+        ty @ (BuiltinType::UTCTime
+        | BuiltinType::GeneralizedTime
+        | BuiltinType::Date
+        | BuiltinType::TimeOfDay
+        | BuiltinType::DateTime
+        | BuiltinType::Duration
+        | BuiltinType::Time) => {
+            // the contents are the same strings as the contents octets of BER
+            let text = get_text(items, start_tag)?;
+            ber::ber_decode_universal(
+                BasicEncodingKind::Basic,
+                text.as_bytes(),
+                ty.tag_type().expect("time type has no tag type"),
+            )
+            .map_err(DecodeError::Io)?
+        }
+        other => {
+            return decoder_error(
+                start_tag.pos.start,
+                start_tag.pos.end,
+                format!("XER decoding is not supported for {}", other),
+            )
+        }
+    })
+}
+
+/// Decodes a value of a type that is not constructed from text,
+/// which is the value of an EXTENDED-XER attribute or list item.
+fn xer_decode_text(
+    decoder: &XerDecoder<'_>,
+    mode: &DecodeMode,
+    name: &str,
+    text: &str,
+    pos: TlvPos,
+    value_pos: TlvPos,
+) -> DecodeResult<DecodedValue> {
+    let (source_ident, component_name, resolved) = get_specific_type(mode);
+    if is_constructed_type(&resolved.ty) {
+        return decoder_error(
+            pos.start,
+            pos.end,
+            format!(
+                "{} value of '{}' cannot be decoded from text",
+                resolved.ty, name
+            ),
+        );
+    }
+    let start_tag = StartTag {
+        name: name.to_string(),
+        qualified_name: name.to_string(),
+        attributes: Vec::new(),
+        pos,
+        is_empty: false,
+    };
+    let items = [ContentItem::Text(text.to_string(), value_pos)];
+    let kind = xer_decode_primitive(decoder, resolved, &items, &start_tag, pos)?;
+    Ok(new_decoded_value(
+        resolved,
+        source_ident,
+        component_name,
+        DecodedValueForm::Primitive(kind),
+        pos,
+        value_pos,
+        pos,
+    ))
+}
+
+/// Returns the mode of a component, with the type reference of the component type if it has one.
+fn get_component_mode(
+    context: &Context,
+    component_name: Option<String>,
+    component_type: &TaggedType,
+) -> DecodeResult<DecodeMode> {
+    Ok(DecodeMode::SpecificType {
+        source_ident: match &component_type.ty {
+            UntaggedType::BuiltinType(_) => None,
+            UntaggedType::Reference(typeref) => Some(typeref.element.clone()),
+            UntaggedType::ObjectClassField(ocf) => {
+                return Err(DecodeError::Parser(Error {
+                    kind: ErrorKind::Ast(format!(
+                        "decoding a component of the type {} is not yet supported",
+                        ocf
+                    )),
+                    loc: ocf.field.loc,
+                }))
+            }
+        },
+        component_name,
+        resolved: Box::new(component_type.resolve(context).map_err(parser_error)?),
+    })
+}
+
+/// The elements of the items of a SEQUENCE OF or SET OF value.
+struct ItemElements {
+    mode: DecodeMode,
+    name: String,
+    /// Whether the items are not enclosed by an element of their type.
+    is_value_list: bool,
+}
+
+impl ItemElements {
+    fn new(decoder: &XerDecoder<'_>, component_type: &TaggedType) -> DecodeResult<ItemElements> {
+        let context = decoder.context;
+        let mode = get_component_mode(context, None, component_type)?;
+        let (_, _, resolved) = get_specific_type(&mode);
+        let is_untagged_choice = matches!(resolved.ty, BuiltinType::Choice(_))
+            && get_instructions(decoder.kind, &resolved.xer_instructions)
+                .contains(&XerEncodingInstruction::Untagged);
+        let is_value_list = is_value_list_type(decoder.kind, resolved) || is_untagged_choice;
+        let name = QualifiedName::new(
+            &get_type_name(context, component_type).map_err(parser_error)?,
+            get_instructions(decoder.kind, &component_type.xer_instructions),
+        )
+        .name;
+        Ok(ItemElements {
+            mode,
+            name,
+            is_value_list,
+        })
+    }
+
+    /// Returns whether the element is the element of an item.
+    fn is_item(&self, decoder: &XerDecoder<'_>, tag: &StartTag) -> bool {
+        let (_, _, resolved) = get_specific_type(&self.mode);
+        match (&resolved.ty, self.is_value_list) {
+            (BuiltinType::Choice(choice), true) => {
+                find_alternative(decoder, choice, &tag.name).is_some()
+            }
+            (_, true) => true,
+            (_, false) => tag.name == self.name,
+        }
+    }
+
+    fn decode(&self, decoder: &mut XerDecoder<'_>, tag: &StartTag) -> DecodeResult<DecodedValue> {
+        if self.is_value_list {
+            // the value of each element is not enclosed by an element
+            xer_decode_value_list_item(decoder, &self.mode, tag)
+        } else {
+            xer_decode_contents(decoder, &self.mode, tag)
+        }
+    }
+}
+
+/// Returns the component of the structure whose contents are not enclosed by an element that can contain the element,
+/// which is either an `UNTAGGED` or `USE-NIL` component of a CHOICE or SEQUENCE OF type.
+fn find_untagged_component(
+    decoder: &XerDecoder<'_>,
+    structure: &Structure,
+    modes: &[DecodeMode],
+    untagged: &[usize],
+    tag: &StartTag,
+) -> DecodeResult<Option<usize>> {
+    for index in untagged {
+        let (_, _, resolved) = get_specific_type(&modes[*index]);
+        let is_match = match &resolved.ty {
+            BuiltinType::Choice(choice) => find_alternative(decoder, choice, &tag.name).is_some(),
+            BuiltinType::StructureOf(of) => {
+                ItemElements::new(decoder, &of.component_type)?.is_item(decoder, tag)
+            }
+            _ => {
+                return decoder_error(
+                    tag.pos.start,
+                    tag.pos.end,
+                    format!(
+                        "UNTAGGED component '{}' of type {} is not supported",
+                        structure.components[*index].name.element, resolved.ty
+                    ),
+                )
+            }
+        };
+        if is_match {
+            return Ok(Some(*index));
+        }
+    }
+    Ok(None)
+}
+
+/// Reads the contents of a SEQUENCE or SET value, followed by its end tag.
+/// The components are child elements named after the components, and in EXTENDED-XER,
+/// they can also be attributes or contents that are not enclosed by an element.
+/// The components of a SET can be in any order, and the components that are not OPTIONAL and do not have a DEFAULT value must be present.
+fn xer_decode_structure(
+    decoder: &mut XerDecoder<'_>,
+    structure: &Structure,
+    resolved: &ResolvedType,
+    start_tag: &StartTag,
+) -> DecodeResult<(Vec<DecodedValue>, TlvPos)> {
+    let context = decoder.context;
+    let kind = decoder.kind;
+
+    let mut modes = Vec::with_capacity(structure.components.len());
+    let mut names = Vec::with_capacity(structure.components.len());
+    for component in &structure.components {
+        modes.push(get_component_mode(
+            context,
+            Some(component.name.element.clone()),
+            &component.component_type,
+        )?);
+        names.push(
+            QualifiedName::new(
+                &component.name.element,
+                get_instructions(kind, &component.component_type.xer_instructions),
+            )
+            .name,
+        );
+    }
+    let component_instructions = |index: usize| {
+        let (_, _, resolved) = get_specific_type(&modes[index]);
+        get_instructions(kind, &resolved.xer_instructions)
+    };
+
+    // the last component of a USE-NIL type is the content of the element,
+    // and it is absent when the element is nil
+    let nil_index = match get_instructions(kind, &resolved.xer_instructions)
+        .contains(&XerEncodingInstruction::UseNil)
+    {
+        true => structure
+            .components
+            .len()
+            .checked_sub(1)
+            .filter(|index| structure.components[*index].optional),
+        false => None,
+    };
+    let is_nil = start_tag.attributes.iter().any(|attribute| {
+        attribute.is_xsi("nil") && matches!(attribute.value.as_str(), "true" | "1")
+    });
+
+    let mut components = Vec::with_capacity(structure.components.len());
+    let mut is_present = vec![false; structure.components.len()];
+
+    // the components that are attributes
+    let mut attribute_names = Vec::new();
+    let mut any_attributes_index = None;
+    for (index, mode) in modes.iter().enumerate() {
+        let instructions = component_instructions(index);
+        if instructions.contains(&XerEncodingInstruction::Attribute) {
+            attribute_names.push(names[index].as_str());
+            if let Some(attribute) = start_tag.attributes.iter().find(|attribute| {
+                attribute.prefix.as_deref() != Some("xsi") && attribute.name == names[index]
+            }) {
+                components.push((
+                    index,
+                    xer_decode_text(
+                        decoder,
+                        mode,
+                        &names[index],
+                        &attribute.value,
+                        attribute.pos,
+                        attribute.value_pos,
+                    )?,
+                ));
+                is_present[index] = true;
+            }
+        } else if instructions.contains(&XerEncodingInstruction::AnyAttributes) {
+            any_attributes_index = Some(index);
+        }
+    }
+    if let Some(index) = any_attributes_index {
+        let attributes = start_tag
+            .attributes
+            .iter()
+            .filter(|attribute| {
+                attribute.prefix.as_deref() != Some("xsi")
+                    && !attribute_names.contains(&attribute.name.as_str())
+            })
+            .collect::<Vec<_>>();
+        components.push((
+            index,
+            xer_decode_any_attributes(decoder, &modes[index], &attributes, start_tag)?,
+        ));
+        is_present[index] = true;
+    }
+
+    let untagged = (0..structure.components.len())
+        .filter(|index| {
+            Some(*index) == nil_index
+                || component_instructions(*index).contains(&XerEncodingInstruction::Untagged)
+        })
+        .collect::<Vec<_>>();
+    let text_index = untagged.iter().copied().find(|index| {
+        let (_, _, resolved) = get_specific_type(&modes[*index]);
+        !is_constructed_type(&resolved.ty)
+    });
+    let any_element_index = (0..structure.components.len())
+        .find(|index| component_instructions(*index).contains(&XerEncodingInstruction::AnyElement));
+
+    let end_pos = match text_index {
+        // the contents of the element are the character data of the component
+        Some(index) => {
+            let (items, end_pos) = read_content_items(decoder, start_tag)?;
+            if is_nil && Some(index) == nil_index {
+                if !get_text(&items, start_tag)?.is_empty() {
+                    return decoder_error(
+                        start_tag.pos.start,
+                        end_pos.end,
+                        format!("nil element '{}' must be empty", start_tag.name),
+                    );
+                }
+            } else {
+                let (source_ident, component_name, resolved) = get_specific_type(&modes[index]);
+                let kind = xer_decode_primitive(decoder, resolved, &items, start_tag, end_pos)?;
+                let start = start_tag.pos.end;
+                components.push((
+                    index,
+                    new_decoded_value(
+                        resolved,
+                        source_ident,
+                        component_name,
+                        DecodedValueForm::Primitive(kind),
+                        TlvPos::new(start, start),
+                        TlvPos::new(start, end_pos.start.max(start)),
+                        end_pos,
+                    ),
+                ));
+                is_present[index] = true;
+            }
+            end_pos
+        }
+        None => {
+            let mut untagged_items: Vec<(usize, Vec<DecodedValue>)> = Vec::new();
+            let mut last_index = None;
+            if !start_tag.is_empty {
+                while !is_at_end_tag(decoder)? {
+                    let tag = read_start_tag(decoder)?;
+                    let index = names.iter().enumerate().position(|(index, name)| {
+                        *name == tag.name
+                            && !untagged.contains(&index)
+                            && !component_instructions(index).iter().any(|instruction| {
+                                matches!(
+                                    instruction,
+                                    XerEncodingInstruction::Attribute
+                                        | XerEncodingInstruction::AnyAttributes
+                                        | XerEncodingInstruction::AnyElement
+                                )
+                            })
+                    });
+                    let untagged_index = match index {
+                        Some(_) => None,
+                        None => {
+                            find_untagged_component(decoder, structure, &modes, &untagged, &tag)?
+                        }
+                    };
+                    let index = match index.or(untagged_index).or(any_element_index) {
+                        Some(index) => index,
+                        None => {
+                            return decoder_error(
+                                tag.pos.start,
+                                tag.pos.end,
+                                format!("'{}' is not a component of the type", tag.name),
+                            )
+                        }
+                    };
+
+                    let (_, _, component_type) = get_specific_type(&modes[index]);
+                    let is_item = untagged_index.is_some()
+                        && matches!(component_type.ty, BuiltinType::StructureOf(_));
+                    if is_present[index] && !is_item {
+                        return decoder_error(
+                            tag.pos.start,
+                            tag.pos.end,
+                            format!("component '{}' is present more than once", tag.name),
+                        );
+                    }
+                    if structure.ty == TagType::Sequence
+                        && last_index.is_some_and(|last_index| last_index > index)
+                    {
+                        return decoder_error(
+                            tag.pos.start,
+                            tag.pos.end,
+                            format!("component '{}' is out of order", tag.name),
+                        );
+                    }
+                    is_present[index] = true;
+                    last_index = Some(index);
+
+                    if is_item {
+                        let of = match &component_type.ty {
+                            BuiltinType::StructureOf(of) => of,
+                            _ => unreachable!(),
+                        };
+                        let item = ItemElements::new(decoder, &of.component_type)?
+                            .decode(decoder, &tag)?;
+                        match untagged_items.last_mut() {
+                            Some((last, items)) if *last == index => items.push(item),
+                            _ => untagged_items.push((index, vec![item])),
+                        }
+                    } else if untagged_index.is_some() {
+                        let (source_ident, component_name, _) = get_specific_type(&modes[index]);
+                        let choice = match &component_type.ty {
+                            BuiltinType::Choice(choice) => choice,
+                            _ => unreachable!(),
+                        };
+                        components.push((
+                            index,
+                            xer_decode_alternative(
+                                decoder,
+                                choice,
+                                source_ident,
+                                component_name,
+                                &tag,
+                            )?,
+                        ));
+                    } else if Some(index) == any_element_index {
+                        // the value is the XML of the element itself
+                        let end_pos = skip_element(decoder, &tag)?;
+                        let pos = TlvPos::new(tag.pos.start, end_pos.end);
+                        let xml = String::from_utf8_lossy(&decoder.buf[pos.start..pos.end]);
+                        components.push((
+                            index,
+                            xer_decode_text(decoder, &modes[index], &tag.name, &xml, pos, pos)?,
+                        ));
+                    } else {
+                        components
+                            .push((index, xer_decode_contents(decoder, &modes[index], &tag)?));
+                    }
+                }
+            }
+            let end_pos = read_end_tag(decoder, start_tag)?;
+
+            // the untagged SEQUENCE OF components are present even if they do not have any items
+            for index in &untagged {
+                let (_, _, resolved) = get_specific_type(&modes[*index]);
+                if !matches!(resolved.ty, BuiltinType::StructureOf(_))
+                    || Some(*index) == nil_index
+                    || is_present[*index]
+                    || structure.components[*index].optional
+                {
+                    continue;
+                }
+                untagged_items.push((*index, Vec::new()));
+                is_present[*index] = true;
+            }
+            for (index, items) in untagged_items {
+                let (source_ident, component_name, resolved) = get_specific_type(&modes[index]);
+                let start = items
+                    .first()
+                    .map(|item| item.tag.pos.start)
+                    .unwrap_or(end_pos.start);
+                let end = items
+                    .last()
+                    .map(|item| item.len.pos.end)
+                    .unwrap_or(end_pos.start);
+                components.push((
+                    index,
+                    new_decoded_value(
+                        resolved,
+                        source_ident,
+                        component_name,
+                        DecodedValueForm::Constructed(items),
+                        TlvPos::new(start, start),
+                        TlvPos::new(start, end),
+                        TlvPos::new(end, end),
+                    ),
+                ));
+            }
+            end_pos
+        }
+    };
+
+    if is_nil && nil_index.is_some_and(|index| is_present[index]) {
+        return decoder_error(
+            start_tag.pos.start,
+            start_tag.pos.end,
+            format!("nil element '{}' must be empty", start_tag.name),
+        );
+    }
+    for (component, is_present) in structure.components.iter().zip(&is_present) {
+        let is_required = !component.optional
+            && component.default_value.is_none()
+            && component.extension_addition.is_none();
+        if is_required && !is_present {
+            return decoder_error(
+                start_tag.pos.start,
+                start_tag.pos.end,
+                format!("missing component '{}'", component.name.element),
+            );
+        }
+    }
+
+    if structure.ty == TagType::Sequence {
+        components.sort_by_key(|(index, _)| *index);
+    }
+    Ok((
+        components.into_iter().map(|(_, value)| value).collect(),
+        end_pos,
+    ))
+}
+
+/// Decodes the attributes of an element that are the strings of an `ANY-ATTRIBUTES` component.
+fn xer_decode_any_attributes(
+    decoder: &XerDecoder<'_>,
+    mode: &DecodeMode,
+    attributes: &[&Attribute],
+    start_tag: &StartTag,
+) -> DecodeResult<DecodedValue> {
+    let (source_ident, component_name, resolved) = get_specific_type(mode);
+    let of = match &resolved.ty {
+        BuiltinType::StructureOf(of) => of,
+        other => {
+            return decoder_error(
+                start_tag.pos.start,
+                start_tag.pos.end,
+                format!("ANY-ATTRIBUTES cannot be decoded as {}", other),
+            )
+        }
+    };
+    let element_mode = get_component_mode(decoder.context, None, &of.component_type)?;
+    let mut elements = Vec::with_capacity(attributes.len());
+    for attribute in attributes {
+        let text = format!(
+            "{}=\"{}\"",
+            attribute.qualified_name(),
+            escape_attribute_value(&attribute.value)
+        );
+        elements.push(xer_decode_text(
+            decoder,
+            &element_mode,
+            &attribute.name,
+            &text,
+            attribute.pos,
+            attribute.pos,
+        )?);
+    }
+    Ok(new_decoded_value(
+        resolved,
+        source_ident,
+        component_name,
+        DecodedValueForm::Constructed(elements),
+        start_tag.pos,
+        start_tag.pos,
+        start_tag.pos,
+    ))
+}
+
+/// Reads the contents of a SEQUENCE OF or SET OF value, followed by its end tag.
+/// In EXTENDED-XER, the items of a type with the `LIST` encoding instruction are separated by whitespace.
+fn xer_decode_structure_of(
+    decoder: &mut XerDecoder<'_>,
+    of: &StructureOf,
+    resolved: &ResolvedType,
+    start_tag: &StartTag,
+) -> DecodeResult<(Vec<DecodedValue>, TlvPos)> {
+    let items = ItemElements::new(decoder, &of.component_type)?;
+
+    let mut elements = Vec::new();
+    if get_instructions(decoder.kind, &resolved.xer_instructions)
+        .contains(&XerEncodingInstruction::List)
+    {
+        let (content, end_pos) = read_content_items(decoder, start_tag)?;
+        let text = get_text(&content, start_tag)?;
+        let pos = TlvPos::new(start_tag.pos.end, end_pos.start.max(start_tag.pos.end));
+        for item in text.split_whitespace() {
+            elements.push(xer_decode_text(
+                decoder,
+                &items.mode,
+                &start_tag.name,
+                item,
+                pos,
+                pos,
+            )?);
+        }
+        return Ok((elements, end_pos));
+    }
+
+    if !start_tag.is_empty {
+        while !is_at_end_tag(decoder)? {
+            let tag = read_start_tag(decoder)?;
+            if !items.is_item(decoder, &tag) {
+                return decoder_error(
+                    tag.pos.start,
+                    tag.pos.end,
+                    format!(
+                        "expecting element '{}', but found '{}'",
+                        items.name, tag.name
+                    ),
+                );
+            }
+            elements.push(items.decode(decoder, &tag)?);
+        }
+    }
+    Ok((elements, read_end_tag(decoder, start_tag)?))
+}
+
+/// Returns the alternative of the CHOICE whose element has the name.
+fn find_alternative<'a>(
+    decoder: &XerDecoder<'_>,
+    choice: &'a Choice,
+    name: &str,
+) -> Option<&'a ChoiceAlternative> {
+    choice.alternatives.iter().find(|alternative| {
+        QualifiedName::new(
+            &alternative.name.element,
+            get_instructions(decoder.kind, &alternative.alternative_type.xer_instructions),
+        )
+        .name
+            == name
+    })
+}
+
+/// Decodes the element of a CHOICE alternative, which is named after the alternative.
+/// Like in BER, the `DecodedValue` of a CHOICE is the value of its alternative, with the metadata of the CHOICE.
+fn xer_decode_alternative(
+    decoder: &mut XerDecoder<'_>,
+    choice: &Choice,
+    source_ident: &Option<QualifiedIdentifier>,
+    component_name: &Option<String>,
+    tag: &StartTag,
+) -> DecodeResult<DecodedValue> {
+    let context = decoder.context;
+    let alternative = match find_alternative(decoder, choice, &tag.name) {
+        Some(alternative) => alternative,
+        None => {
+            return decoder_error(
+                tag.pos.start,
+                tag.pos.end,
+                format!("'{}' is not an alternative of the CHOICE", tag.name),
+            )
+        }
+    };
+
+    let mode = DecodeMode::SpecificType {
+        source_ident: source_ident.clone(),
+        component_name: component_name.clone(),
+        resolved: Box::new(
+            alternative
+                .alternative_type
+                .resolve(context)
+                .map_err(parser_error)?,
+        ),
+    };
+    xer_decode_contents(decoder, &mode, tag)
+}
+
+/// Decodes the element of a CHOICE value with the `USE-TYPE` or `USE-UNION` encoding instruction,
+/// whose contents are the contents of the alternative.
+/// The alternative is identified by the `xsi:type` attribute, and otherwise it is the first alternative for `USE-TYPE`,
+/// or the first alternative that can decode the contents for `USE-UNION`.
+fn xer_decode_union(
+    decoder: &mut XerDecoder<'_>,
+    choice: &Choice,
+    use_type: bool,
+    source_ident: &Option<QualifiedIdentifier>,
+    component_name: &Option<String>,
+    start_tag: &StartTag,
+) -> DecodeResult<DecodedValue> {
+    let context = decoder.context;
+    let alternatives = match start_tag
+        .attributes
+        .iter()
+        .find(|attribute| attribute.is_xsi("type"))
+    {
+        Some(attribute) => {
+            match find_alternative(decoder, choice, strip_namespace_prefix(&attribute.value)) {
+                Some(alternative) => vec![alternative],
+                None => {
+                    return decoder_error(
+                        attribute.value_pos.start,
+                        attribute.value_pos.end,
+                        format!("'{}' is not an alternative of the CHOICE", attribute.value),
+                    )
+                }
+            }
+        }
+        None if use_type => choice.alternatives.iter().take(1).collect(),
+        None => choice.alternatives.iter().collect(),
+    };
+
+    let start = decoder.pos;
+    let mut error = None;
+    for alternative in alternatives {
+        decoder.pos = start;
+        let mode = DecodeMode::SpecificType {
+            source_ident: source_ident.clone(),
+            component_name: component_name.clone(),
+            resolved: Box::new(
+                alternative
+                    .alternative_type
+                    .resolve(context)
+                    .map_err(parser_error)?,
+            ),
+        };
+        match xer_decode_contents(decoder, &mode, start_tag) {
+            Ok(value) => return Ok(value),
+            Err(err) => error = Some(err),
+        }
+    }
+    match error {
+        Some(err) => Err(err),
+        None => decoder_error(
+            start_tag.pos.start,
+            start_tag.pos.end,
+            "CHOICE does not have any alternatives",
+        ),
+    }
+}
+
+/// Decodes an element of a SEQUENCE OF or SET OF value whose elements are not enclosed by an element of their type.
+fn xer_decode_value_list_item(
+    decoder: &mut XerDecoder<'_>,
+    mode: &DecodeMode,
+    tag: &StartTag,
+) -> DecodeResult<DecodedValue> {
+    let (source_ident, component_name, resolved) = get_specific_type(mode);
+
+    let kind = match &resolved.ty {
+        BuiltinType::Choice(choice) => {
+            return xer_decode_alternative(decoder, choice, source_ident, component_name, tag)
+        }
+        _ if !tag.is_empty => {
+            return decoder_error(
+                tag.pos.start,
+                tag.pos.end,
+                format!("'{}' must be an empty element", tag.name),
+            )
+        }
+        BuiltinType::Boolean => DecodedValueKind::Boolean(xer_decode_boolean(
+            &[ContentItem::Element(tag.name.clone(), tag.pos)],
+            tag,
+        )?),
+        BuiltinType::Enumerated(enumerated) => DecodedValueKind::Enumerated(xer_decode_enumerated(
+            decoder,
+            enumerated,
+            &[],
+            &[ContentItem::Element(tag.name.clone(), tag.pos)],
+            tag,
+        )?),
+        _ => unreachable!(),
+    };
+    Ok(new_decoded_value(
+        resolved,
+        source_ident,
+        component_name,
+        DecodedValueForm::Primitive(kind),
+        tag.pos,
+        TlvPos::new(tag.pos.start, tag.pos.end),
+        tag.pos,
+    ))
+}
+
+fn new_decoded_value(
+    resolved: &ResolvedType,
+    source_ident: &Option<QualifiedIdentifier>,
+    component_name: &Option<String>,
+    form: DecodedValueForm,
+    tag_pos: TlvPos,
+    value_pos: TlvPos,
+    end_pos: TlvPos,
+) -> DecodedValue {
+    let tag = resolved.tag.as_ref().expect("non-CHOICE type has no tag");
+    DecodedValue {
+        tag: TlvElement::new(
+            TlvTag {
+                class: tag.class,
+                form: match form {
+                    DecodedValueForm::Primitive(_) => TypeForm::Primitive,
+                    DecodedValueForm::Constructed(_) => TypeForm::Constructed,
+                },
+                num: tag.num,
+            },
+            tag_pos,
+        ),
+        len: TlvElement::new((value_pos.end - value_pos.start) as u32, end_pos),
+        value_pos,
+        form,
+        metadata: Some(DecodedValueMetadata {
+            type_ident: source_ident.clone(),
+            component_name: component_name.clone(),
+        }),
+    }
+}
+
+/// Decodes the contents of the element with the start tag, followed by its end tag.
+/// The positions of the tag and length of the `DecodedValue` are the positions of the start tag and end tag,
+/// and its value position is the position of the contents.
+fn xer_decode_contents(
+    decoder: &mut XerDecoder<'_>,
+    mode: &DecodeMode,
+    start_tag: &StartTag,
+) -> DecodeResult<DecodedValue> {
+    let (source_ident, component_name, resolved) = match mode {
+        DecodeMode::Contextless => {
+            return decoder_error(
+                start_tag.pos.start,
+                start_tag.pos.end,
+                "XER can only be decoded with a known type",
+            )
+        }
+        DecodeMode::SpecificType {
+            source_ident,
+            component_name,
+            resolved,
+        } => (source_ident, component_name, resolved),
+    };
+
+    if let BuiltinType::Choice(choice) = &resolved.ty {
+        let instructions = get_instructions(decoder.kind, &resolved.xer_instructions);
+        let use_type = instructions.contains(&XerEncodingInstruction::UseType);
+        if use_type || instructions.contains(&XerEncodingInstruction::UseUnion) {
+            return xer_decode_union(
+                decoder,
+                choice,
+                use_type,
+                source_ident,
+                component_name,
+                start_tag,
+            );
+        }
+
+        if start_tag.is_empty || is_at_end_tag(decoder)? {
+            return decoder_error(
+                start_tag.pos.start,
+                start_tag.pos.end,
+                format!("'{}' must contain a CHOICE alternative", start_tag.name),
+            );
+        }
+        let tag = read_start_tag(decoder)?;
+        let mut value =
+            xer_decode_alternative(decoder, choice, source_ident, component_name, &tag)?;
+        let end_pos = read_end_tag(decoder, start_tag)?;
+        value.tag.pos = start_tag.pos;
+        value.len.pos = end_pos;
+        return Ok(value);
+    }
+
+    let start = start_tag.pos.end;
+    let (form, end_pos) = match &resolved.ty {
+        BuiltinType::Structure(structure) if !ber::is_real_type(&resolved.ty) => {
+            let (components, end_pos) =
+                xer_decode_structure(decoder, structure, resolved, start_tag)?;
+            (DecodedValueForm::Constructed(components), end_pos)
+        }
+        BuiltinType::StructureOf(of) => {
+            let (elements, end_pos) = xer_decode_structure_of(decoder, of, resolved, start_tag)?;
+            (DecodedValueForm::Constructed(elements), end_pos)
+        }
+        _ => {
+            let (items, end_pos) = read_content_items(decoder, start_tag)?;
+            let kind = xer_decode_primitive(decoder, resolved, &items, start_tag, end_pos)?;
+            (DecodedValueForm::Primitive(kind), end_pos)
+        }
+    };
+
+    Ok(new_decoded_value(
+        resolved,
+        source_ident,
+        component_name,
+        form,
+        start_tag.pos,
+        TlvPos::new(start, end_pos.start.max(start)),
+        end_pos,
+    ))
+}
+
+/// Decodes an XML document containing a single value of the type specified by the `DecodeMode`.
+/// The name of the root element must be the name of the type, or the name of its built-in type if it is not a type reference.
+pub fn xer_decode_value(
+    decoder: &mut XerDecoder<'_>,
+    mode: &DecodeMode,
+) -> DecodeResult<DecodedValue> {
+    let start_tag = read_start_tag(decoder)?;
+    if let DecodeMode::SpecificType {
+        source_ident,
+        resolved,
+        ..
+    } = mode
+    {
+        let expected_name = match source_ident {
+            Some(ident) => ident.name.clone(),
+            None => get_builtin_type_name(&resolved.ty),
+        };
+        let expected_name = QualifiedName::new(
+            &expected_name,
+            get_instructions(decoder.kind, &resolved.xer_instructions),
+        )
+        .name;
+        if start_tag.name != expected_name {
+            return decoder_error(
+                start_tag.pos.start,
+                start_tag.pos.end,
+                format!(
+                    "expecting root element '{}', but found '{}'",
+                    expected_name, start_tag.name
+                ),
+            );
+        }
+    }
+    let value = xer_decode_contents(decoder, mode, &start_tag)?;
+    skip_misc(decoder)?;
+    Ok(value)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        compiler::{test::compile_module, Context},
+        encoding::*,
+        module::{ModuleIdentifier, QualifiedIdentifier},
+    };
+
+    const MODULE: &str = r#"XerDecodeTest DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+
+Point ::= SEQUENCE {
+    x INTEGER,
+    y INTEGER,
+    label UTF8String OPTIONAL
+}
+
+Pair ::= SET {
+    a BOOLEAN,
+    b INTEGER
+}
+
+Message ::= CHOICE {
+    data OCTET STRING,
+    flag BOOLEAN
+}
+
+Text ::= UTF8String
+
+MESSAGE-ID ::= CLASS { &id INTEGER UNIQUE } WITH SYNTAX { ID &id }
+
+Identified ::= SEQUENCE {
+    id MESSAGE-ID.&id
+}
+
+END
+"#;
+
+    fn decode(context: &Context, type_name: &str, xml: &str) -> DecodeResult<DecodedValue> {
+        let ident = QualifiedIdentifier::new(
+            ModuleIdentifier::with_name("XerDecodeTest".to_string()),
+            type_name.to_string(),
+        );
+        let mode = DecodeMode::SpecificType {
+            source_ident: Some(ident.clone()),
+            component_name: None,
+            resolved: Box::new(
+                context
+                    .lookup_type(&ident)
+                    .expect("lookup_type")
+                    .ty
+                    .resolve(context)
+                    .expect("resolve"),
+            ),
+        };
+
+        let syntax = TransferSyntax::Xml(XmlEncodingKind::Basic);
+        let decoder = syntax.get_codec().decoder.expect("decoder");
+        decoder(&syntax, &mode, xml.as_bytes(), context).map(|mut values| values.remove(0))
+    }
+
+    #[test]
+    fn test_xer_decode_document() {
+        let mut context = Context::new();
+        compile_module(&mut context, "XerDecodeTest.asn", MODULE);
+
+        for (type_name, xml) in [
+            (
+                "Point",
+                "<?xml version=\"1.0\"?>\n<!-- point -->\n<Point>\n  <x> 1 </x>\n  <y>2</y>\n</Point>\n",
+            ),
+            ("Pair", "<Pair><b>5</b><a><true/></a></Pair>"),
+            ("Message", "<Message><flag>false</flag></Message>"),
+            ("Text", "<Text> a&lt;b&#x26;<bel/>c </Text>"),
+        ] {
+            decode(&context, type_name, xml)
+                .unwrap_or_else(|err| panic!("XER {} {}: {}", type_name, xml, err));
+        }
+
+        let value = decode(&context, "Text", "<Text> a&lt;b&#x26;<bel/>c </Text>").expect("decode");
+        match value.form {
+            DecodedValueForm::Primitive(DecodedValueKind::CharacterString(_, str)) => {
+                assert_eq!(str, " a<b&\u{7}c ")
+            }
+            form => panic!("decoded {:?}", form),
+        }
+    }
+
+    #[test]
+    fn test_xer_decode_invalid() {
+        let mut context = Context::new();
+        compile_module(&mut context, "XerDecodeTest.asn", MODULE);
+
+        for (type_name, xml) in [
+            // root element that is not named after the type
+            ("Point", "<Pair><x>1</x><y>2</y></Pair>"),
+            // end tag that does not match the start tag
+            ("Point", "<Point><x>1</y><y>2</y></Point>"),
+            // missing component
+            ("Point", "<Point><x>1</x></Point>"),
+            // unknown component
+            ("Point", "<Point><x>1</x><y>2</y><z>3</z></Point>"),
+            // SEQUENCE components that are out of order
+            ("Point", "<Point><y>2</y><x>1</x></Point>"),
+            // SET component that is present more than once
+            ("Pair", "<Pair><a><true/></a><b>1</b><a><true/></a></Pair>"),
+            // unknown CHOICE alternative
+            ("Message", "<Message><other/></Message>"),
+            // trailing data after the root element
+            ("Text", "<Text>a</Text><Text>b</Text>"),
+        ] {
+            assert!(
+                decode(&context, type_name, xml).is_err(),
+                "XER {} {} is not rejected",
+                type_name,
+                xml
+            );
+        }
+    }
+
+    #[test]
+    fn test_xer_decode_positions() {
+        let mut context = Context::new();
+        compile_module(&mut context, "XerDecodeTest.asn", MODULE);
+
+        let value =
+            decode(&context, "Message", "<Message><data>1234</data></Message>").expect("decode");
+        assert_eq!((value.tag.pos.start, value.tag.pos.end), (0, 9));
+        assert_eq!((value.len.pos.start, value.len.pos.end), (26, 36));
+        assert_eq!(value.len.element, 4);
+        assert_eq!((value.value_pos.start, value.value_pos.end), (15, 19));
+    }
+
+    #[test]
+    fn test_xer_decode_object_class_field() {
+        let mut context = Context::new();
+        compile_module(&mut context, "XerDecodeTest.asn", MODULE);
+
+        let err = decode(
+            &context,
+            "Identified",
+            "<Identified><id>5</id></Identified>",
+        )
+        .expect_err("ObjectClassField component is decoded");
+        assert!(matches!(err, DecodeError::Parser(_)), "{}", err);
+    }
+}
@@ -0,0 +1,1135 @@
+use std::fmt::{Display, Write};
+
+use base64::Engine;
+
+use num::BigInt;
+
+use crate::{
+    compiler::{
+        parser::{AstElement, Error, ErrorKind, Loc, Result},
+        Context,
+    },
+    encoding::{ber, encode_contained_value, per, SetOrdering, TransferSyntax, XmlEncodingKind},
+    types::*,
+    values::*,
+};
+
+use super::XerEncoder;
+
+/// The names of the empty elements that replace the control characters in character strings, indexed by the value of the character.
+/// HT, LF and CR are `None`, because they are written as themselves.
+pub(crate) const CONTROL_CHARACTER_NAMES: [Option<&str>; 32] = [
+    Some("nul"),
+    Some("soh"),
+    Some("stx"),
+    Some("etx"),
+    Some("eot"),
+    Some("enq"),
+    Some("ack"),
+    Some("bel"),
+    Some("bs"),
+    None,
+    None,
+    Some("vt"),
+    Some("ff"),
+    None,
+    Some("so"),
+    Some("si"),
+    Some("dle"),
+    Some("dc1"),
+    Some("dc2"),
+    Some("dc3"),
+    Some("dc4"),
+    Some("nak"),
+    Some("syn"),
+    Some("etb"),
+    Some("can"),
+    Some("em"),
+    Some("sub"),
+    Some("esc"),
+    Some("is4"),
+    Some("is3"),
+    Some("is2"),
+    Some("is1"),
+];
+
+/// Escapes the characters of the string that cannot appear in XML character data.
+pub(crate) fn escape_character_data(str: &str) -> String {
+    let mut escaped = String::with_capacity(str.len());
+    for ch in str.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            ch if (ch as u32) < 0x20 => match CONTROL_CHARACTER_NAMES[ch as usize] {
+                Some(name) => {
+                    escaped.push('<');
+                    escaped.push_str(name);
+                    escaped.push_str("/>");
+                }
+                None => escaped.push(ch),
+            },
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Escapes the characters of the string that cannot appear in an XML attribute value.
+/// This is also used for the items of EXTENDED-XER lists, which cannot contain elements.
+pub(crate) fn escape_attribute_value(str: &str) -> String {
+    let mut escaped = String::with_capacity(str.len());
+    for ch in str.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            ch if (ch as u32) < 0x20 => write!(escaped, "&#x{:X};", ch as u32).unwrap(),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Returns the name of the element of a built-in type, which is its name with its spaces replaced by underscores.
+pub(crate) fn get_builtin_type_name(ty: &BuiltinType) -> String {
+    match ty {
+        BuiltinType::StructureOf(of) => format!("{}_OF", of.ty),
+        BuiltinType::RelativeOid => "RELATIVE_OID".to_string(),
+        other => other.to_string().replace(' ', "_"),
+    }
+}
+
+/// Returns the name of the element of a value of the type, which is the name of its type reference,
+/// or the name of its built-in type if it is not a type reference.
+pub(crate) fn get_type_name(context: &Context, tagged_type: &TaggedType) -> Result<String> {
+    Ok(match &tagged_type.ty {
+        UntaggedType::Reference(typeref) => typeref.element.name.clone(),
+        _ => get_builtin_type_name(&tagged_type.resolve(context)?.ty),
+    })
+}
+
+/// Returns whether the elements of a SEQUENCE OF or SET OF value of the type are written without enclosing elements,
+/// which is the case for types whose values are already elements.
+/// In EXTENDED-XER, the encoding instructions of the type can change its values into character data.
+pub(crate) fn is_value_list_type(kind: XmlEncodingKind, ty: &ResolvedType) -> bool {
+    let is_element = matches!(
+        ty.ty,
+        BuiltinType::Boolean | BuiltinType::Enumerated(_) | BuiltinType::Choice(_)
+    );
+    is_element
+        && !get_instructions(kind, &ty.xer_instructions)
+            .iter()
+            .any(|instruction| {
+                matches!(
+                    instruction,
+                    XerEncodingInstruction::Text
+                        | XerEncodingInstruction::UseNumber
+                        | XerEncodingInstruction::UseType
+                        | XerEncodingInstruction::UseUnion
+                )
+            })
+}
+
+/// Returns the encoding instructions that apply to the encoding.
+/// Only EXTENDED-XER uses encoding instructions, so there are none for the other encodings.
+pub(crate) fn get_instructions(
+    kind: XmlEncodingKind,
+    instructions: &[XerEncodingInstruction],
+) -> &[XerEncodingInstruction] {
+    match kind {
+        XmlEncodingKind::Extended => instructions,
+        XmlEncodingKind::Basic | XmlEncodingKind::Canonical => &[],
+    }
+}
+
+/// The name of an element or attribute, after the `NAME` and `NAMESPACE` encoding instructions are applied.
+pub(crate) struct QualifiedName<'a> {
+    pub name: String,
+    pub namespace: Option<&'a XerNamespace>,
+}
+
+impl<'a> QualifiedName<'a> {
+    pub fn new(name: &str, instructions: &'a [XerEncodingInstruction]) -> QualifiedName<'a> {
+        let mut qualified = QualifiedName {
+            name: name.to_string(),
+            namespace: None,
+        };
+        if let Some(modifier) = instructions
+            .iter()
+            .find_map(|instruction| match instruction {
+                XerEncodingInstruction::Name(modifier) => Some(modifier),
+                _ => None,
+            })
+        {
+            qualified.name = modifier.apply(name);
+        }
+        if let Some(namespace) = instructions
+            .iter()
+            .find_map(|instruction| match instruction {
+                XerEncodingInstruction::Namespace(namespace) => Some(namespace),
+                _ => None,
+            })
+        {
+            qualified.namespace = namespace.as_ref();
+        }
+        qualified
+    }
+
+    /// Returns the name with the prefix of its namespace, if it has one.
+    fn prefixed(&self) -> String {
+        match self
+            .namespace
+            .and_then(|namespace| namespace.prefix.as_ref())
+        {
+            Some(prefix) => format!("{}:{}", prefix, self.name),
+            None => self.name.clone(),
+        }
+    }
+
+    /// Returns the attribute that declares the namespace of the name, if it has one.
+    fn namespace_declaration(&self) -> String {
+        match self.namespace {
+            Some(XerNamespace {
+                uri,
+                prefix: Some(prefix),
+            }) => format!(" xmlns:{}=\"{}\"", prefix, escape_attribute_value(uri)),
+            Some(XerNamespace { uri, prefix: None }) => {
+                format!(" xmlns=\"{}\"", escape_attribute_value(uri))
+            }
+            None => String::new(),
+        }
+    }
+}
+
+/// The namespace of the `xsi:type` and `xsi:nil` attributes.
+pub(crate) const XSI_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+/// The largest magnitude of the decimal exponent of a REAL value that is expanded into decimal digits.
+/// Binary exponents are always expanded, so this limits the size of the representation of any REAL value.
+const MAX_EXPANDED_EXPONENT: i64 = 10000;
+
+fn exponent_too_large_error(exponent: impl Display) -> Error {
+    Error {
+        kind: ErrorKind::Ast(format!(
+            "the exponent {} of the REAL value is too large to write the value in decimal",
+            exponent
+        )),
+        loc: Loc::default(),
+    }
+}
+
+/// Returns the decimal representation of the REAL value that is written by [`format_real`], without an exponent.
+/// This is the representation of the `DECIMAL` encoding instruction.
+pub(crate) fn format_decimal(real: &str) -> Result<String> {
+    let (mantissa, exponent) = match real.split_once('E') {
+        Some((mantissa, exponent)) => match exponent.parse::<i64>() {
+            Ok(parsed) if parsed.unsigned_abs() <= MAX_EXPANDED_EXPONENT as u64 => {
+                (mantissa, parsed)
+            }
+            _ => return Err(exponent_too_large_error(exponent)),
+        },
+        None => return Ok(real.to_string()),
+    };
+    let (sign, digits) = match mantissa.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", mantissa),
+    };
+    if exponent >= 0 {
+        return Ok(format!(
+            "{}{}{}",
+            sign,
+            digits,
+            "0".repeat(exponent as usize)
+        ));
+    }
+    let point = digits.len() as i64 + exponent;
+    Ok(if point > 0 {
+        let (int, frac) = digits.split_at(point as usize);
+        format!("{}{}.{}", sign, int, frac)
+    } else {
+        format!("{}0.{}{}", sign, "0".repeat((-point) as usize), digits)
+    })
+}
+
+/// Returns the decimal representation of the REAL value `mantissa * base ^ exponent`,
+/// which is written as the integer mantissa without trailing zeros, followed by its exponent if it is not 0.
+/// Values with a base other than 10 are converted to base 10, which fails if their exponent is too large.
+pub(crate) fn format_real(mantissa: BigInt, base: u32, exponent: BigInt) -> Result<String> {
+    let (mantissa, exponent) = match base {
+        10 => (mantissa, exponent),
+        _ => {
+            // `mantissa * 2 ^ exponent` is `mantissa * 5 ^ -exponent * 10 ^ exponent` for negative exponents
+            let bits_per_digit = base.trailing_zeros() as i64;
+            let exponent = match i64::try_from(&exponent) {
+                Ok(int)
+                    if int.unsigned_abs() <= (MAX_EXPANDED_EXPONENT / bits_per_digit) as u64 =>
+                {
+                    int * bits_per_digit
+                }
+                _ => return Err(exponent_too_large_error(exponent)),
+            };
+            if exponent >= 0 {
+                (mantissa << exponent as usize, BigInt::ZERO)
+            } else {
+                (
+                    mantissa * num::pow(BigInt::from(5), (-exponent) as usize),
+                    BigInt::from(exponent),
+                )
+            }
+        }
+    };
+
+    let real = RealLiteral { mantissa, exponent }.normalize();
+    Ok(if real.mantissa == BigInt::ZERO {
+        "0".to_string()
+    } else if real.exponent == BigInt::ZERO {
+        real.mantissa.to_string()
+    } else {
+        format!("{}E{}", real.mantissa, real.exponent)
+    })
+}
+
+fn get_integer(context: &Context, value: &AstElement<TypedValue>) -> Result<BigInt> {
+    match value.resolve(context)?.value {
+        BuiltinValue::Integer(num) => Ok(num),
+        other => Err(Error {
+            kind: ErrorKind::Ast(format!(
+                "expecting INTEGER value, but found {}",
+                other.tag_type(context)?
+            )),
+            loc: value.loc,
+        }),
+    }
+}
+
+fn xer_encode_real(encoder: &mut XerEncoder<'_>, value: &BuiltinValue) -> Result<String> {
+    let context = encoder.context;
+    Ok(match value {
+        BuiltinValue::Integer(num) => format_real(num.clone(), 10, BigInt::ZERO)?,
+        BuiltinValue::RealLiteral(lit) => {
+            format_real(lit.mantissa.clone(), 10, lit.exponent.clone())?
+        }
+        BuiltinValue::Structure(_, structure) => {
+            let special = structure
+                .components
+                .iter()
+                .find(|component| component.name.element == "artasn-special");
+            match special {
+                Some(special) => {
+                    let special = match special.value.resolve(context)?.value {
+                        BuiltinValue::Enumerated(item) => get_integer(context, &item)?,
+                        _ => unreachable!(),
+                    };
+                    let name = match u32::try_from(special) {
+                        Ok(0) => "PLUS-INFINITY",
+                        Ok(1) => "MINUS-INFINITY",
+                        Ok(2) => "NOT-A-NUMBER",
+                        _ => unreachable!(),
+                    };
+                    format!("<{}/>", name)
+                }
+                None => {
+                    let mantissa = get_integer(context, &structure.components[0].value)?;
+                    let base = get_integer(context, &structure.components[1].value)?;
+                    let exponent = get_integer(context, &structure.components[2].value)?;
+                    format_real(
+                        mantissa,
+                        base.try_into().expect("base is out of bounds"),
+                        exponent,
+                    )?
+                }
+            }
+        }
+        _ => unreachable!(),
+    })
+}
+
+/// Returns the identifier of the ENUMERATED item with the value.
+pub(crate) fn get_enumerated_identifier(
+    context: &Context,
+    enumerated: &EnumeratedType,
+    value: &AstElement<TypedValue>,
+) -> Result<String> {
+    let num = get_integer(context, value)?;
+    for item in &enumerated.items {
+        let item_num = match &item.value {
+            EnumerationItemValue::Implied(num) => BigInt::from(*num),
+            EnumerationItemValue::Specified(value) => get_integer(context, value)?,
+        };
+        if item_num == num {
+            return Ok(item.name.element.clone());
+        }
+    }
+    Err(Error {
+        kind: ErrorKind::Ast(format!("ENUMERATED item with value {} is not defined", num)),
+        loc: value.loc,
+    })
+}
+
+/// Returns the names of the bits of the BIT STRING value if every bit that is 1 has a name,
+/// or `None` if the value can only be written as a string of 0 and 1 characters.
+pub(crate) fn get_named_bits(
+    context: &Context,
+    named_bits: &[NamedNumber],
+    bs: &BitStringValue,
+) -> Result<Option<Vec<String>>> {
+    let mut names = Vec::new();
+    for bit in 0..bs.len() {
+        if bs.data[(bit / 8) as usize] & (0x80 >> (bit % 8)) == 0 {
+            continue;
+        }
+        let mut name = None;
+        for named_bit in named_bits {
+            if get_integer(context, &named_bit.value)? == BigInt::from(bit) {
+                name = Some(named_bit.name.element.clone());
+                break;
+            }
+        }
+        match name {
+            Some(name) => names.push(name),
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(names))
+}
+
+pub(crate) fn format_bit_string(bs: &BitStringValue, len: u64) -> String {
+    (0..len)
+        .map(|bit| {
+            if bs.data[(bit / 8) as usize] & (0x80 >> (bit % 8)) == 0 {
+                '0'
+            } else {
+                '1'
+            }
+        })
+        .collect()
+}
+
+fn xer_encode_bit_string(
+    encoder: &mut XerEncoder<'_>,
+    resolved_type: &ResolvedType,
+    bs: &BitStringValue,
+) -> Result<String> {
+    if let BuiltinType::BitString(BitStringType {
+        named_bits: Some(named_bits),
+    }) = &resolved_type.ty
+    {
+        if encoder.kind.is_canonical() {
+            // CXER writes the bits without their trailing 0 bits, rather than the names of the bits
+            return Ok(format_bit_string(bs, bs.len_without_trailing_zeros()));
+        }
+        if let Some(names) = get_named_bits(encoder.context, named_bits, bs)? {
+            return Ok(names
+                .into_iter()
+                .map(|name| format!("<{}/>", name))
+                .collect());
+        }
+    }
+    Ok(format_bit_string(bs, bs.len()))
+}
+
+fn format_oid(oid: &Oid) -> String {
+    oid.0
+        .iter()
+        .map(|node| node.to_string())
+        .collect::<Vec<String>>()
+        .join(".")
+}
+
+/// Returns the XML representation of a value of a type that is not constructed.
+/// The text form is used for EXTENDED-XER attributes and list items, which cannot contain elements.
+fn xer_encode_primitive(
+    encoder: &mut XerEncoder<'_>,
+    typed_value: &ResolvedValue,
+    text_form: bool,
+) -> Result<String> {
+    let context = encoder.context;
+    let instructions = get_instructions(encoder.kind, &typed_value.ty.xer_instructions);
+    let is_text = text_form || instructions.contains(&XerEncodingInstruction::Text);
+
+    if ber::is_real_type(&typed_value.ty.ty) {
+        let mut real = xer_encode_real(encoder, &typed_value.value)?;
+        if text_form {
+            let special = match real.as_str() {
+                "<PLUS-INFINITY/>" => Some("INF"),
+                "<MINUS-INFINITY/>" => Some("-INF"),
+                "<NOT-A-NUMBER/>" => Some("NaN"),
+                _ => None,
+            };
+            if let Some(special) = special {
+                real = special.to_string();
+            }
+        }
+        if instructions.contains(&XerEncodingInstruction::Decimal) {
+            return format_decimal(&real);
+        }
+        // CXER always writes the exponent of a value that is not 0 or a special value
+        if encoder.kind.is_canonical()
+            && real != "0"
+            && !real.starts_with('<')
+            && !real.contains('E')
+        {
+            real.push_str("E0");
+        }
+        return Ok(real);
+    }
+
+    Ok(match &typed_value.value {
+        BuiltinValue::Boolean(value) if is_text => value.to_string(),
+        BuiltinValue::Boolean(true) => "<true/>".to_string(),
+        BuiltinValue::Boolean(false) => "<false/>".to_string(),
+        BuiltinValue::Integer(num) => num.to_string(),
+        BuiltinValue::BitString(bs) if text_form => format_bit_string(bs, bs.len()),
+        BuiltinValue::BitString(bs) => xer_encode_bit_string(encoder, &typed_value.ty, bs)?,
+        BuiltinValue::OctetString(bytes)
+            if instructions.contains(&XerEncodingInstruction::Base64) =>
+        {
+            base64::engine::general_purpose::STANDARD.encode(bytes)
+        }
+        BuiltinValue::OctetString(bytes) => hex::encode_upper(bytes),
+        BuiltinValue::Null => String::new(),
+        BuiltinValue::ObjectIdentifier(oid) | BuiltinValue::RelativeOid(oid) => {
+            format_oid(&oid.resolve_oid(context)?)
+        }
+        BuiltinValue::Enumerated(item)
+            if instructions.contains(&XerEncodingInstruction::UseNumber) =>
+        {
+            get_integer(context, item)?.to_string()
+        }
+        BuiltinValue::Enumerated(item) => match &typed_value.ty.ty {
+            BuiltinType::Enumerated(enumerated) => {
+                let identifier = get_enumerated_identifier(context, enumerated, item)?;
+                if is_text {
+                    identifier
+                } else {
+                    format!("<{}/>", identifier)
+                }
+            }
+            _ => unreachable!(),
+        },
+        BuiltinValue::CharacterString(_, str) if text_form => escape_attribute_value(str),
+        BuiltinValue::CharacterString(_, str) => escape_character_data(str),
+        BuiltinValue::Time(time) => time.to_ber_string(),
+        BuiltinValue::UTCTime(utc) if encoder.kind.is_canonical() => utc.to_canonical_string(),
+        BuiltinValue::UTCTime(utc) => utc.to_ber_string(),
+        BuiltinValue::GeneralizedTime(gt) if encoder.kind.is_canonical() => {
+            match gt.to_canonical_string() {
+                Some(str) => str,
+                None => {
+                    return Err(Error {
+                        kind: ErrorKind::Ast(
+                            "GeneralizedTime value must have a time zone in CXER".to_string(),
+                        ),
+                        loc: Loc::default(),
+                    })
+                }
+            }
+        }
+        BuiltinValue::GeneralizedTime(gt) => gt.to_ber_string(),
+        BuiltinValue::Date(date) => date.to_value_string(),
+        BuiltinValue::TimeOfDay(time_of_day) => time_of_day.to_value_string(),
+        BuiltinValue::DateTime(date_time) => date_time.to_value_string(),
+        BuiltinValue::Duration(duration) => duration.to_value_string(),
+        BuiltinValue::Containing(containing) => {
+            // without ENCODED BY, the contained value is encoded with the same encoding rules,
+            // and its encoding is written like any other value of the BIT STRING or OCTET STRING type
+            let data = encode_contained_value(
+                context,
+                encoder.options,
+                &typed_value.ty,
+                containing,
+                &TransferSyntax::Xml(encoder.kind),
+            )?;
+            let value = match containing.container_type {
+                TagType::BitString => BuiltinValue::BitString(BitStringValue {
+                    data,
+                    unused_bits: 0,
+                }),
+                _ => BuiltinValue::OctetString(data),
+            };
+            return xer_encode_primitive(
+                encoder,
+                &ResolvedValue {
+                    ty: typed_value.ty.clone(),
+                    value,
+                },
+                text_form,
+            );
+        }
+        _ => unreachable!(),
+    })
+}
+
+/// Writes the whitespace that begins a line at the current depth.
+/// CXER does not have any whitespace between elements.
+fn write_line_start(encoder: &mut XerEncoder<'_>) {
+    if encoder.kind.is_canonical() {
+        return;
+    }
+    encoder.buf.push(b'\n');
+    for _ in 0..encoder.depth {
+        encoder.buf.extend_from_slice(b"  ");
+    }
+}
+
+fn write_str(encoder: &mut XerEncoder<'_>, str: &str) {
+    encoder.buf.extend_from_slice(str.as_bytes());
+}
+
+/// Returns whether the component is omitted from the encoding because its value is equal to its DEFAULT value.
+fn is_omitted_default_value(
+    encoder: &XerEncoder<'_>,
+    component: &StructureValueComponent,
+) -> Result<bool> {
+    if !encoder.kind.is_canonical() && encoder.options.encode_default_values {
+        return Ok(false);
+    }
+    if component.is_default {
+        return Ok(true);
+    }
+    match &component.default_value {
+        Some(default_value) => component.value.try_eq(encoder.context, default_value),
+        None => Ok(false),
+    }
+}
+
+/// Sorts the components of a SET value into the canonical order of the tags of their types (X.680 clause 8.6), as required by CXER.
+fn sort_set_components<'a>(
+    encoder: &XerEncoder<'_>,
+    structure_type: &Structure,
+    components: Vec<(&'a StructureValueComponent, ResolvedValue)>,
+) -> Result<Vec<(&'a StructureValueComponent, ResolvedValue)>> {
+    let mut tagged_components = Vec::with_capacity(components.len());
+    for (component, value) in components {
+        let component_type = &structure_type
+            .components
+            .iter()
+            .find(|component_type| component_type.name.element == component.name.element)
+            .expect("SET component is not defined")
+            .component_type;
+        let tag = per::get_canonical_tag(encoder.context, component_type)?;
+        tagged_components.push((tag, component, value));
+    }
+
+    match encoder.options.set_ordering {
+        SetOrdering::Sort => tagged_components.sort_by_key(|(tag, _, _)| *tag),
+        SetOrdering::Verify => {
+            if let Some(window) = tagged_components
+                .windows(2)
+                .find(|window| window[0].0 > window[1].0)
+            {
+                return Err(Error {
+                    kind: ErrorKind::Ast(format!(
+                        "SET component '{}' must be placed before component '{}' in canonical order",
+                        window[1].1.name.element, window[0].1.name.element,
+                    )),
+                    loc: window[1].1.name.loc,
+                });
+            }
+        }
+    }
+    Ok(tagged_components
+        .into_iter()
+        .map(|(_, component, value)| (component, value))
+        .collect())
+}
+
+/// Sorts the encodings of the elements of a SET OF value in ascending order, as required by CXER.
+fn sort_set_of(
+    encoder: &XerEncoder<'_>,
+    encodings: &mut [(&AstElement<TypedValue>, Vec<u8>)],
+) -> Result<()> {
+    match encoder.options.set_ordering {
+        SetOrdering::Sort => encodings.sort_by(|(_, a), (_, b)| a.cmp(b)),
+        SetOrdering::Verify => {
+            if let Some(window) = encodings
+                .windows(2)
+                .find(|window| window[0].1 > window[1].1)
+            {
+                return Err(Error {
+                    kind: ErrorKind::Ast(
+                        "SET OF element must be placed before the previous element in canonical order"
+                            .to_string(),
+                    ),
+                    loc: window[1].0.loc,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The kind of content that was written for an element.
+/// Content with any character data is written without whitespace between its items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum ContentKind {
+    Empty,
+    Elements,
+    Text,
+}
+
+/// Returns the error for an encoding instruction that the EXTENDED-XER encoder does not support.
+fn unsupported_instruction_error(instruction: &XerEncodingInstruction) -> Error {
+    Error {
+        kind: ErrorKind::Ast(format!(
+            "{} encoding instruction is not supported",
+            instruction.name()
+        )),
+        loc: Loc::default(),
+    }
+}
+
+/// Writes the text of the PI-OR-COMMENT encoding instructions at the position.
+fn write_pi_or_comments(
+    buf: &mut Vec<u8>,
+    instructions: &[XerEncodingInstruction],
+    position: XerPiOrCommentPosition,
+) {
+    for instruction in instructions {
+        if let XerEncodingInstruction::PiOrComment {
+            text,
+            position: instruction_position,
+        } = instruction
+        {
+            if *instruction_position == position {
+                buf.extend_from_slice(text.as_bytes());
+            }
+        }
+    }
+}
+
+/// Writes the value as an attribute of the element being written, as required by the `ATTRIBUTE` encoding instruction.
+fn write_attribute(
+    encoder: &mut XerEncoder<'_>,
+    attributes: &mut String,
+    name: &QualifiedName<'_>,
+    typed_value: &ResolvedValue,
+) -> Result<()> {
+    if is_constructed(typed_value) {
+        return Err(Error {
+            kind: ErrorKind::Ast(format!(
+                "{} value cannot be encoded as the attribute '{}'",
+                typed_value.ty.ty, name.name
+            )),
+            loc: Loc::default(),
+        });
+    }
+    let value = xer_encode_primitive(encoder, typed_value, true)?;
+    write!(
+        attributes,
+        "{} {}=\"{}\"",
+        name.namespace_declaration(),
+        name.prefixed(),
+        value
+    )
+    .unwrap();
+    Ok(())
+}
+
+/// Writes the strings of the value as attributes of the element being written,
+/// as required by the `ANY-ATTRIBUTES` encoding instruction.
+fn write_any_attributes(
+    encoder: &XerEncoder<'_>,
+    attributes: &mut String,
+    typed_value: &ResolvedValue,
+) -> Result<()> {
+    if let BuiltinValue::StructureOf(_, elements) = &typed_value.value {
+        for element in elements {
+            if let BuiltinValue::CharacterString(_, str) = element.resolve(encoder.context)?.value {
+                attributes.push(' ');
+                attributes.push_str(str.trim());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Writes the content of the value without an enclosing element, as required by the `UNTAGGED` encoding instruction.
+fn xer_encode_untagged(
+    encoder: &mut XerEncoder<'_>,
+    typed_value: &ResolvedValue,
+    attributes: &mut String,
+) -> Result<ContentKind> {
+    if is_constructed(typed_value) {
+        return xer_encode_children(encoder, typed_value, attributes);
+    }
+    let content = xer_encode_primitive(encoder, typed_value, false)?;
+    if content.is_empty() {
+        return Ok(ContentKind::Empty);
+    }
+    write_str(encoder, &content);
+    Ok(ContentKind::Text)
+}
+
+/// Writes the contents of a constructed value, with each child element on its own line.
+/// Components that are encoded as attributes are written to `attributes`, rather than to the contents.
+fn xer_encode_children(
+    encoder: &mut XerEncoder<'_>,
+    typed_value: &ResolvedValue,
+    attributes: &mut String,
+) -> Result<ContentKind> {
+    let context = encoder.context;
+    let kind = encoder.kind;
+    let type_instructions = get_instructions(kind, &typed_value.ty.xer_instructions);
+
+    let mut content = ContentKind::Empty;
+    match (&typed_value.value, &typed_value.ty.ty) {
+        (BuiltinValue::Structure(_, structure), BuiltinType::Structure(structure_type)) => {
+            // the last component of a USE-NIL type is the content of the element,
+            // and the element is nil when the component is absent
+            let nil_component = match type_instructions.contains(&XerEncodingInstruction::UseNil) {
+                true => structure_type
+                    .components
+                    .last()
+                    .filter(|component| component.optional)
+                    .map(|component| component.name.element.as_str()),
+                false => None,
+            };
+            if let Some(nil_component) = nil_component {
+                if !structure
+                    .components
+                    .iter()
+                    .any(|component| component.name.element == nil_component)
+                {
+                    write!(
+                        attributes,
+                        " xmlns:xsi=\"{}\" xsi:nil=\"true\"",
+                        XSI_NAMESPACE
+                    )
+                    .unwrap();
+                }
+            }
+
+            let mut components = Vec::with_capacity(structure.components.len());
+            for component in &structure.components {
+                if is_omitted_default_value(encoder, component)? {
+                    continue;
+                }
+                components.push((component, component.value.resolve(context)?));
+            }
+            if structure_type.ty == TagType::Set && kind.is_canonical() {
+                components = sort_set_components(encoder, structure_type, components)?;
+            }
+
+            for (component, value) in components {
+                let component_type = &structure_type
+                    .components
+                    .iter()
+                    .find(|component_type| component_type.name.element == component.name.element)
+                    .expect("structure component is not defined")
+                    .component_type;
+                let name = QualifiedName::new(
+                    &component.name.element,
+                    get_instructions(kind, &component_type.xer_instructions),
+                );
+                let instructions = get_instructions(kind, &value.ty.xer_instructions);
+                if instructions.contains(&XerEncodingInstruction::Attribute) {
+                    write_attribute(encoder, attributes, &name, &value)?;
+                } else if instructions.contains(&XerEncodingInstruction::AnyAttributes) {
+                    write_any_attributes(encoder, attributes, &value)?;
+                } else if nil_component == Some(component.name.element.as_str())
+                    || instructions.contains(&XerEncodingInstruction::Untagged)
+                {
+                    content = content.max(xer_encode_untagged(encoder, &value, attributes)?);
+                } else {
+                    write_line_start(encoder);
+                    xer_encode_element(encoder, &name, &value)?;
+                    content = content.max(ContentKind::Elements);
+                }
+            }
+        }
+        (BuiltinValue::StructureOf(_, elements), BuiltinType::StructureOf(of)) => {
+            if type_instructions.contains(&XerEncodingInstruction::List) {
+                let mut items = Vec::with_capacity(elements.len());
+                for element in elements {
+                    let value = element.resolve(context)?;
+                    items.push(xer_encode_primitive(encoder, &value, true)?);
+                }
+                if !items.is_empty() {
+                    write_str(encoder, &items.join(" "));
+                    content = ContentKind::Text;
+                }
+                return Ok(content);
+            }
+
+            let element_type = of.component_type.resolve(context)?;
+            let element_name = get_type_name(context, &of.component_type)?;
+            let element_name = QualifiedName::new(
+                &element_name,
+                get_instructions(kind, &of.component_type.xer_instructions),
+            );
+            let is_untagged = get_instructions(kind, &element_type.xer_instructions)
+                .contains(&XerEncodingInstruction::Untagged);
+            let mut encodings = Vec::with_capacity(elements.len());
+            for element in elements {
+                let value = element.resolve(context)?;
+                let mut buf = Vec::new();
+                let mut element_encoder = XerEncoder {
+                    context,
+                    kind,
+                    options: encoder.options,
+                    buf: &mut buf,
+                    depth: encoder.depth,
+                };
+                if is_untagged {
+                    content = content.max(xer_encode_untagged(
+                        &mut element_encoder,
+                        &value,
+                        attributes,
+                    )?);
+                } else {
+                    write_line_start(&mut element_encoder);
+                    if is_value_list_type(kind, &element_type) {
+                        // the value of each element is written without an enclosing element
+                        xer_encode_content(&mut element_encoder, &value)?;
+                    } else {
+                        xer_encode_element(&mut element_encoder, &element_name, &value)?;
+                    }
+                    content = content.max(ContentKind::Elements);
+                }
+                encodings.push((element, buf));
+            }
+            if of.ty == TagType::Set && kind.is_canonical() {
+                sort_set_of(encoder, &mut encodings)?;
+            }
+
+            for (_, encoding) in encodings {
+                encoder.buf.extend(encoding);
+            }
+        }
+        (BuiltinValue::Choice(choice), BuiltinType::Choice(choice_type)) => {
+            let value = choice.value.resolve(context)?;
+            if get_instructions(kind, &value.ty.xer_instructions)
+                .contains(&XerEncodingInstruction::Untagged)
+            {
+                return xer_encode_untagged(encoder, &value, attributes);
+            }
+            let name = get_alternative_name(kind, choice_type, &choice.alternative.element);
+            write_line_start(encoder);
+            xer_encode_element(encoder, &name, &value)?;
+            content = ContentKind::Elements;
+        }
+        _ => unreachable!(),
+    }
+    Ok(content)
+}
+
+/// Returns the name of the element of the CHOICE alternative.
+fn get_alternative_name<'a>(
+    kind: XmlEncodingKind,
+    choice_type: &'a Choice,
+    alternative: &str,
+) -> QualifiedName<'a> {
+    let alternative_type = &choice_type
+        .alternatives
+        .iter()
+        .find(|alternative_type| alternative_type.name.element == alternative)
+        .expect("CHOICE alternative is not defined")
+        .alternative_type;
+    QualifiedName::new(
+        alternative,
+        get_instructions(kind, &alternative_type.xer_instructions),
+    )
+}
+
+/// Returns whether the value is written as child elements, rather than as character data.
+fn is_constructed(typed_value: &ResolvedValue) -> bool {
+    match &typed_value.value {
+        BuiltinValue::Structure(..) => !ber::is_real_type(&typed_value.ty.ty),
+        BuiltinValue::StructureOf(..) | BuiltinValue::Choice(_) => true,
+        _ => false,
+    }
+}
+
+/// Writes the contents of the element of the value.
+/// The contents of a CHOICE value is the element of its alternative.
+fn xer_encode_content(encoder: &mut XerEncoder<'_>, typed_value: &ResolvedValue) -> Result<()> {
+    if let (BuiltinValue::Choice(choice), BuiltinType::Choice(choice_type)) =
+        (&typed_value.value, &typed_value.ty.ty)
+    {
+        let value = choice.value.resolve(encoder.context)?;
+        let name = get_alternative_name(encoder.kind, choice_type, &choice.alternative.element);
+        return xer_encode_element(encoder, &name, &value);
+    }
+    let content = xer_encode_primitive(encoder, typed_value, false)?;
+    write_str(encoder, &content);
+    Ok(())
+}
+
+/// Writes the value as an element with the given name.
+/// An element without any content is written as an empty-element tag.
+fn xer_encode_element(
+    encoder: &mut XerEncoder<'_>,
+    name: &QualifiedName<'_>,
+    typed_value: &ResolvedValue,
+) -> Result<()> {
+    xer_encode_element_with_attributes(encoder, name, typed_value, String::new())
+}
+
+fn xer_encode_element_with_attributes(
+    encoder: &mut XerEncoder<'_>,
+    name: &QualifiedName<'_>,
+    typed_value: &ResolvedValue,
+    mut attributes: String,
+) -> Result<()> {
+    let instructions = get_instructions(encoder.kind, &typed_value.ty.xer_instructions);
+    if let Some(instruction) = instructions.iter().find(|instruction| {
+        matches!(
+            instruction,
+            XerEncodingInstruction::EmbedValues
+                | XerEncodingInstruction::UseOrder
+                | XerEncodingInstruction::UseQName
+        )
+    }) {
+        return Err(unsupported_instruction_error(instruction));
+    }
+
+    if instructions.contains(&XerEncodingInstruction::AnyElement) {
+        // the value is the XML of the element itself
+        if let BuiltinValue::CharacterString(_, str) = &typed_value.value {
+            write_str(encoder, str);
+            return Ok(());
+        }
+    }
+
+    if let (BuiltinValue::Choice(choice), BuiltinType::Choice(choice_type)) =
+        (&typed_value.value, &typed_value.ty.ty)
+    {
+        let use_type = instructions.contains(&XerEncodingInstruction::UseType);
+        if use_type || instructions.contains(&XerEncodingInstruction::UseUnion) {
+            // the element of the CHOICE value has the content of its alternative,
+            // and USE-TYPE identifies every alternative but the first with the xsi:type attribute
+            let value = choice.value.resolve(encoder.context)?;
+            let alternative_name =
+                get_alternative_name(encoder.kind, choice_type, &choice.alternative.element);
+            let is_first_alternative = choice_type
+                .alternatives
+                .first()
+                .is_some_and(|alternative| alternative.name.element == choice.alternative.element);
+            if use_type && !is_first_alternative {
+                write!(
+                    attributes,
+                    " xmlns:xsi=\"{}\" xsi:type=\"{}\"",
+                    XSI_NAMESPACE, alternative_name.name
+                )
+                .unwrap();
+            }
+            return xer_encode_element_with_attributes(encoder, name, &value, attributes);
+        }
+    }
+
+    write_pi_or_comments(encoder.buf, instructions, XerPiOrCommentPosition::BeforeTag);
+    let name_attributes = name.namespace_declaration();
+    attributes.insert_str(0, &name_attributes);
+    let name = name.prefixed();
+    if is_constructed(typed_value) {
+        let mut children = Vec::new();
+        let mut children_encoder = XerEncoder {
+            context: encoder.context,
+            kind: encoder.kind,
+            options: encoder.options,
+            buf: &mut children,
+            depth: encoder.depth + 1,
+        };
+        let content = xer_encode_children(&mut children_encoder, typed_value, &mut attributes)?;
+
+        if content == ContentKind::Empty {
+            write_str(encoder, &format!("<{}{}/>", name, attributes));
+        } else {
+            write_str(encoder, &format!("<{}{}>", name, attributes));
+            write_pi_or_comments(
+                encoder.buf,
+                instructions,
+                XerPiOrCommentPosition::BeforeValue,
+            );
+            encoder.buf.extend(children);
+            write_pi_or_comments(
+                encoder.buf,
+                instructions,
+                XerPiOrCommentPosition::AfterValue,
+            );
+            if content == ContentKind::Elements {
+                write_line_start(encoder);
+            }
+            write_str(encoder, &format!("</{}>", name));
+        }
+    } else {
+        let content = xer_encode_primitive(encoder, typed_value, false)?;
+        let is_default_for_empty = instructions.iter().any(|instruction| {
+            matches!(instruction, XerEncodingInstruction::DefaultForEmpty(default)
+                if content == *default || content == format!("<{}/>", default))
+        });
+        if content.is_empty() || is_default_for_empty {
+            write_str(encoder, &format!("<{}{}/>", name, attributes));
+        } else {
+            write_str(encoder, &format!("<{}{}>", name, attributes));
+            write_pi_or_comments(
+                encoder.buf,
+                instructions,
+                XerPiOrCommentPosition::BeforeValue,
+            );
+            write_str(encoder, &content);
+            write_pi_or_comments(
+                encoder.buf,
+                instructions,
+                XerPiOrCommentPosition::AfterValue,
+            );
+            write_str(encoder, &format!("</{}>", name));
+        }
+    }
+    write_pi_or_comments(encoder.buf, instructions, XerPiOrCommentPosition::AfterTag);
+    Ok(())
+}
+
+/// Writes the value as an XML document, whose root element is named after the type of the value.
+pub fn xer_encode_value(encoder: &mut XerEncoder<'_>, typed_value: &ResolvedValue) -> Result<()> {
+    let name = match &encoder.options.type_name {
+        Some(type_name) => type_name.clone(),
+        None => get_builtin_type_name(&typed_value.ty.ty),
+    };
+    let name = QualifiedName::new(
+        &name,
+        get_instructions(encoder.kind, &typed_value.ty.xer_instructions),
+    );
+    xer_encode_element(encoder, &name, typed_value)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::compiler::test::json_test;
+
+    json_test!(
+        test_xer_simple,
+        "../../../test-data/encode/xer/XerSimpleTest"
+    );
+    json_test!(
+        test_xer_structure,
+        "../../../test-data/encode/xer/XerStructureTest"
+    );
+    json_test!(test_cxer, "../../../test-data/encode/xer/CxerTest");
+    json_test!(test_exer, "../../../test-data/encode/xer/ExerTest");
+
+    #[test]
+    fn test_xer_format_real_exponent_limit() {
+        use num::BigInt;
+
+        use super::{format_decimal, format_real};
+
+        assert_eq!(
+            format_real(BigInt::from(1), 2, BigInt::from(10)).unwrap(),
+            "1024"
+        );
+        assert_eq!(
+            format_real(BigInt::from(1), 2, BigInt::from(-3)).unwrap(),
+            "125E-3"
+        );
+        assert!(format_real(BigInt::from(1), 2, BigInt::from(0x7FFF_FFFF)).is_err());
+        assert!(format_real(BigInt::from(1), 2, BigInt::from(i64::MIN)).is_err());
+        assert!(format_real(BigInt::from(1), 2, BigInt::from(u64::MAX)).is_err());
+        assert_eq!(format_decimal("125E-3").unwrap(), "0.125");
+        assert!(format_decimal("1E2147483647").is_err());
+        assert!(format_decimal("1E99999999999999999999").is_err());
+    }
+}
@@ -0,0 +1,26 @@
+mod encode;
+pub use encode::*;
+
+mod decode;
+pub use decode::*;
+
+use crate::{
+    compiler::Context,
+    encoding::{EncodeOptions, XmlEncodingKind},
+};
+
+pub(crate) struct XerEncoder<'a> {
+    pub context: &'a Context,
+    pub kind: XmlEncodingKind,
+    pub options: &'a EncodeOptions,
+    pub buf: &'a mut Vec<u8>,
+    /// The number of elements that enclose the element being written.
+    pub depth: usize,
+}
+
+pub(crate) struct XerDecoder<'a> {
+    pub context: &'a Context,
+    pub kind: XmlEncodingKind,
+    pub buf: &'a [u8],
+    pub pos: usize,
+}
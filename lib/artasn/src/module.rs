@@ -138,6 +138,9 @@ pub struct ModuleHeader {
     pub ident: ModuleIdentifier,
     pub tag_default: TagDefault,
     pub extensibility_implied: bool,
+    /// The encoding reference default of the module (e.g. `XER` in `DEFINITIONS XER INSTRUCTIONS`),
+    /// which applies to encoding prefixes that do not specify an encoding reference.
+    pub encoding_reference: Option<String>,
     pub exports: Exports,
     pub imports: Vec<ImportsFromModule>,
     pub declarations: Vec<String>,
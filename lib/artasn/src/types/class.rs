@@ -42,7 +42,7 @@ impl InformationObjectClass {
 
 #[derive(Debug, Clone)]
 pub enum ObjectClassField {
-    Value(ObjectClassFieldValue),
+    Value(Box<ObjectClassFieldValue>),
     ValueSet(ObjectClassFieldValueSet),
     OpenType(ObjectClassFieldType),
     Object(ObjectClassFieldObject),
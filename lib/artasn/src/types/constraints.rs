@@ -37,6 +37,43 @@ macro_rules! resolve_integer {
     }};
 }
 
+macro_rules! resolve_string {
+    ( $context:expr, $constant:expr ) => {{
+        let context = $context;
+        let constant = $constant.resolve(context)?;
+        match constant.value {
+            BuiltinValue::CharacterString(_, str) => str,
+            other => {
+                return Err(Error {
+                    kind: ErrorKind::Ast(format!(
+                        "expecting character string in permitted alphabet constraint, but found {}",
+                        other.tag_type(context)?
+                    )),
+                    loc: $constant.loc,
+                })
+            }
+        }
+    }};
+}
+
+macro_rules! resolve_char {
+    ( $context:expr, $constant:expr ) => {{
+        let str = resolve_string!($context, $constant);
+        let mut chars = str.chars();
+        match (chars.next(), chars.next()) {
+            (Some(ch), None) => ch as u32,
+            _ => {
+                return Err(Error {
+                    kind: ErrorKind::Ast(
+                        "expecting a single character in permitted alphabet range".to_string(),
+                    ),
+                    loc: $constant.loc,
+                })
+            }
+        }
+    }};
+}
+
 #[derive(Debug, Clone)]
 pub struct ResolvedConstraint {
     pub specs: Vec<ConstraintSpec>,
@@ -232,9 +269,10 @@ impl ResolvedConstraint {
                 if let ConstraintSpecItem::Value(value_spec) = item {
                     has_matching_constraint = true;
                     if let ValueConstraint::SingleValue(single_value) = &value_spec.element {
-                        if value
-                            .try_eq(context, &value_spec.as_ref().map(|_| single_value.clone()))?
-                        {
+                        if value.try_eq(
+                            context,
+                            &value_spec.as_ref().map(|_| (**single_value).clone()),
+                        )? {
                             return Ok(Some(true));
                         }
                     }
@@ -367,6 +405,122 @@ impl ResolvedConstraint {
                 .any(|item| matches!(item, ConstraintSpecItem::Value(_)))
         })
     }
+
+    /// Returns the permitted alphabet of the root of the constraint.
+    /// If there are no permitted alphabet constraints in the root, `None` is returned.
+    pub fn permitted_alphabet(&self) -> Option<PermittedAlphabet> {
+        let mut alphabet: Option<PermittedAlphabet> = None;
+        for spec in self.specs.iter().filter(|spec| !spec.is_extension) {
+            for item in &spec.items {
+                if let ConstraintSpecItem::PermittedAlphabet(item) = item {
+                    alphabet = Some(match alphabet {
+                        Some(alphabet) => alphabet.union(item),
+                        None => item.clone(),
+                    });
+                }
+            }
+        }
+        alphabet
+    }
+}
+
+/// The set of characters permitted by a `FROM` constraint.
+/// Characters are stored as sorted, non-overlapping, and non-adjacent inclusive ranges of their code points.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermittedAlphabet {
+    ranges: Vec<(u32, u32)>,
+    /// `true` if the `FROM` constraint has an extensiblity marker.
+    pub is_extensible: bool,
+}
+
+impl PermittedAlphabet {
+    pub fn from_ranges(mut ranges: Vec<(u32, u32)>) -> PermittedAlphabet {
+        ranges.retain(|(lower, upper)| lower <= upper);
+        ranges.sort();
+
+        let mut merged: Vec<(u32, u32)> = Vec::with_capacity(ranges.len());
+        for (lower, upper) in ranges {
+            match merged.last_mut() {
+                Some((_, last_upper)) if lower <= last_upper.saturating_add(1) => {
+                    *last_upper = (*last_upper).max(upper);
+                }
+                _ => merged.push((lower, upper)),
+            }
+        }
+
+        PermittedAlphabet {
+            ranges: merged,
+            is_extensible: false,
+        }
+    }
+
+    pub fn union(&self, other: &PermittedAlphabet) -> PermittedAlphabet {
+        let mut alphabet = PermittedAlphabet::from_ranges(
+            self.ranges.iter().chain(&other.ranges).copied().collect(),
+        );
+        alphabet.is_extensible = self.is_extensible || other.is_extensible;
+        alphabet
+    }
+
+    pub fn intersection(&self, other: &PermittedAlphabet) -> PermittedAlphabet {
+        let mut ranges = Vec::new();
+        for (lhs_lower, lhs_upper) in &self.ranges {
+            for (rhs_lower, rhs_upper) in &other.ranges {
+                ranges.push((*lhs_lower.max(rhs_lower), *lhs_upper.min(rhs_upper)));
+            }
+        }
+        let mut alphabet = PermittedAlphabet::from_ranges(ranges);
+        alphabet.is_extensible = self.is_extensible || other.is_extensible;
+        alphabet
+    }
+
+    /// Returns the number of characters in the alphabet.
+    pub fn len(&self) -> u64 {
+        self.ranges
+            .iter()
+            .map(|(lower, upper)| (upper - lower) as u64 + 1)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn contains(&self, ch: u32) -> bool {
+        self.index_of(ch).is_some()
+    }
+
+    /// Returns the largest code point in the alphabet.
+    pub fn max(&self) -> Option<u32> {
+        self.ranges.last().map(|(_, upper)| *upper)
+    }
+
+    /// Returns the position of a code point in the alphabet, sorted by code point.
+    pub fn index_of(&self, ch: u32) -> Option<u64> {
+        let mut index = 0;
+        for (lower, upper) in &self.ranges {
+            if ch < *lower {
+                return None;
+            }
+            if ch <= *upper {
+                return Some(index + (ch - lower) as u64);
+            }
+            index += (upper - lower) as u64 + 1;
+        }
+        None
+    }
+
+    /// Returns the code point at a position in the alphabet, sorted by code point.
+    pub fn nth(&self, mut index: u64) -> Option<u32> {
+        for (lower, upper) in &self.ranges {
+            let len = (upper - lower) as u64 + 1;
+            if index < len {
+                return Some(lower + index as u32);
+            }
+            index -= len;
+        }
+        None
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -379,8 +533,9 @@ pub struct ConstraintSpec {
 pub enum ConstraintSpecItem {
     Value(AstElement<ValueConstraint>),
     Size(ResolvedConstraint),
+    PermittedAlphabet(PermittedAlphabet),
     InnerType(InnerTypeConstraints),
-    Contents(ContentsConstraint),
+    Contents(Box<ContentsConstraint>),
     Table(TableConstraint),
     UserDefined,
 }
@@ -389,6 +544,7 @@ pub enum ConstraintSpecItem {
 enum ConstraintSpecItemKind {
     Value,
     Size,
+    PermittedAlphabet,
     InnerType,
     Other,
 }
@@ -399,6 +555,7 @@ fn collect_spec_item_kinds(items: &[ConstraintSpecItem]) -> HashSet<ConstraintSp
         kinds.insert(match item {
             ConstraintSpecItem::Value(_) => ConstraintSpecItemKind::Value,
             ConstraintSpecItem::Size(_) => ConstraintSpecItemKind::Size,
+            ConstraintSpecItem::PermittedAlphabet(_) => ConstraintSpecItemKind::PermittedAlphabet,
             ConstraintSpecItem::InnerType(_) => ConstraintSpecItemKind::InnerType,
             _ => ConstraintSpecItemKind::Other,
         });
@@ -418,6 +575,7 @@ impl ConstraintSpecItem {
         match self {
             Self::Value(_) => "value",
             Self::Size(_) => "size",
+            Self::PermittedAlphabet(_) => "permitted alphabet",
             Self::InnerType(_) => "inner type",
             Self::Contents(_) => "contents",
             Self::Table(_) => "reference table",
@@ -428,7 +586,7 @@ impl ConstraintSpecItem {
 
 #[derive(Debug, Clone)]
 pub enum ValueConstraint {
-    SingleValue(ResolvedValue),
+    SingleValue(Box<ResolvedValue>),
     Range(ResolvedValueRange),
 }
 
@@ -474,6 +632,21 @@ impl ConstraintTree {
                     &[ConstraintSpecItemKind::Value, ConstraintSpecItemKind::Size],
                 ) {
                     eval_value_and_size_set_binary_expr(&lhs, op, &rhs, constrained_type)?
+                } else if spec_item_kinds_contains_only(
+                    &lhs_kinds,
+                    &[
+                        ConstraintSpecItemKind::Size,
+                        ConstraintSpecItemKind::PermittedAlphabet,
+                    ],
+                ) && spec_item_kinds_contains_only(
+                    &rhs_kinds,
+                    &[
+                        ConstraintSpecItemKind::Size,
+                        ConstraintSpecItemKind::PermittedAlphabet,
+                    ],
+                ) && is_permitted_alphabet_binary_expr_supported(&lhs, op.element, &rhs)
+                {
+                    eval_permitted_alphabet_binary_expr(lhs, op.element, rhs)
                 } else if spec_item_kinds_contains_only(
                     &lhs_kinds,
                     &[ConstraintSpecItemKind::InnerType],
@@ -528,7 +701,7 @@ impl ConstraintTree {
                 SubtypeElement::SingleValue(single_value) => {
                     let value = single_value.resolve(context)?;
                     vec![ConstraintSpecItem::Value(AstElement::new(
-                        ValueConstraint::SingleValue(value),
+                        ValueConstraint::SingleValue(Box::new(value)),
                         single_value.loc,
                     ))]
                 }
@@ -565,11 +738,16 @@ impl ConstraintTree {
                         size.resolve(context, &BuiltinType::universal(TagType::Integer))?;
                     vec![ConstraintSpecItem::Size(size_constraint)]
                 }
+                SubtypeElement::PermittedAlphabet(alphabet) => {
+                    vec![ConstraintSpecItem::PermittedAlphabet(
+                        alphabet.resolve_permitted_alphabet(context)?,
+                    )]
+                }
                 SubtypeElement::InnerType(inner_type) => {
                     vec![ConstraintSpecItem::InnerType(inner_type.clone())]
                 }
                 SubtypeElement::Contents(contents) => {
-                    vec![ConstraintSpecItem::Contents(contents.clone())]
+                    vec![ConstraintSpecItem::Contents(Box::new(contents.clone()))]
                 }
                 SubtypeElement::Table(table) => vec![ConstraintSpecItem::Table(table.clone())],
                 SubtypeElement::UserDefined => vec![ConstraintSpecItem::UserDefined],
@@ -578,6 +756,53 @@ impl ConstraintTree {
     }
 }
 
+impl ConstraintTree {
+    fn resolve_alphabet_ranges(&self, context: &Context) -> Result<PermittedAlphabet> {
+        Ok(match self {
+            Self::BinaryExpr { lhs, op, rhs } => {
+                let lhs = lhs.resolve_alphabet_ranges(context)?;
+                let rhs = rhs.resolve_alphabet_ranges(context)?;
+                match op.element {
+                    ConstraintTreeOperator::Union => lhs.union(&rhs),
+                    ConstraintTreeOperator::Intersection => lhs.intersection(&rhs),
+                }
+            }
+            Self::Element(element) => match &element.element {
+                SubtypeElement::SingleValue(single_value) => PermittedAlphabet::from_ranges(
+                    resolve_string!(context, single_value)
+                        .chars()
+                        .map(|ch| (ch as u32, ch as u32))
+                        .collect(),
+                ),
+                SubtypeElement::ValueRange(range) => {
+                    let lower = match &range.lower {
+                        RangeLowerBound::Min => 0,
+                        RangeLowerBound::Eq(value) => resolve_char!(context, value),
+                        RangeLowerBound::Gt(value) => resolve_char!(context, value) + 1,
+                    };
+                    let upper = match &range.upper {
+                        RangeUpperBound::Max => u32::MAX,
+                        RangeUpperBound::Eq(value) => resolve_char!(context, value),
+                        RangeUpperBound::Lt(value) => {
+                            resolve_char!(context, value).saturating_sub(1)
+                        }
+                    };
+                    PermittedAlphabet::from_ranges(vec![(lower, upper)])
+                }
+                _ => {
+                    return Err(Error {
+                        kind: ErrorKind::Ast(
+                            "only single value and value range constraints are allowed in permitted alphabet constraints"
+                                .to_string(),
+                        ),
+                        loc: element.loc,
+                    })
+                }
+            },
+        })
+    }
+}
+
 fn get_value_constraint_bounds(item: &ValueConstraint) -> ResolvedValueRange {
     match item {
         ValueConstraint::Range(range) => range.clone(),
@@ -603,6 +828,52 @@ fn iter_exactly_zero_or_one<Item, I: Iterator<Item = Item>>(mut iter: I) -> Opti
     }
 }
 
+/// Returns `true` if a binary expression of size and permitted alphabet constraints can be evaluated.
+/// Permitted alphabets can be combined with each other by union or intersection,
+/// and a single size constraint can be intersected with permitted alphabets.
+fn is_permitted_alphabet_binary_expr_supported(
+    lhs: &[ConstraintSpecItem],
+    op: ConstraintTreeOperator,
+    rhs: &[ConstraintSpecItem],
+) -> bool {
+    let size_count = lhs
+        .iter()
+        .chain(rhs)
+        .filter(|item| matches!(item, ConstraintSpecItem::Size(_)))
+        .count();
+    match op {
+        ConstraintTreeOperator::Union => size_count == 0,
+        ConstraintTreeOperator::Intersection => size_count <= 1,
+    }
+}
+
+fn eval_permitted_alphabet_binary_expr(
+    lhs: Vec<ConstraintSpecItem>,
+    op: ConstraintTreeOperator,
+    rhs: Vec<ConstraintSpecItem>,
+) -> Vec<ConstraintSpecItem> {
+    let mut items = Vec::with_capacity(2);
+    let mut alphabet: Option<PermittedAlphabet> = None;
+    for item in lhs.into_iter().chain(rhs) {
+        match item {
+            ConstraintSpecItem::PermittedAlphabet(item) => {
+                alphabet = Some(match alphabet {
+                    Some(alphabet) => match op {
+                        ConstraintTreeOperator::Union => alphabet.union(&item),
+                        ConstraintTreeOperator::Intersection => alphabet.intersection(&item),
+                    },
+                    None => item,
+                });
+            }
+            item => items.push(item),
+        }
+    }
+    if let Some(alphabet) = alphabet {
+        items.push(ConstraintSpecItem::PermittedAlphabet(alphabet));
+    }
+    items
+}
+
 fn eval_value_and_size_set_binary_expr(
     lhs: &[ConstraintSpecItem],
     op: &AstElement<ConstraintTreeOperator>,
@@ -846,6 +1117,20 @@ impl Constraint {
         })
     }
 
+    /// Resolves the characters permitted by the constraint of a `FROM` constraint.
+    /// Only the root of the constraint is included.
+    pub fn resolve_permitted_alphabet(&self, context: &Context) -> Result<PermittedAlphabet> {
+        let mut ranges = Vec::new();
+        for element_set in &self.element_sets {
+            if !element_set.is_extension {
+                ranges.extend(element_set.tree.resolve_alphabet_ranges(context)?.ranges);
+            }
+        }
+        let mut alphabet = PermittedAlphabet::from_ranges(ranges);
+        alphabet.is_extensible = self.is_extensible;
+        Ok(alphabet)
+    }
+
     pub fn get_contents_constraint(&self) -> Option<&ContentsConstraint> {
         if self.element_sets.len() == 1 {
             let set = &self.element_sets[0];
@@ -885,6 +1170,7 @@ pub enum SubtypeElement {
     ContainedSubtype(TaggedType),
     ValueRange(ValueRange),
     Size(Constraint),
+    PermittedAlphabet(Constraint),
     InnerType(InnerTypeConstraints),
     Contents(ContentsConstraint),
     Table(TableConstraint),
@@ -1211,7 +1497,7 @@ mod test {
             Compiler, Context,
         },
         module::ModuleIdentifier,
-        types::{Bound, ConstraintBounds},
+        types::{Bound, ConstraintBounds, PermittedAlphabet},
     };
 
     use super::ResolvedConstraint;
@@ -1519,4 +1805,47 @@ mod test {
             })
         );
     }
+
+    #[test]
+    pub fn test_permitted_alphabet() {
+        let mut context = Context::new();
+        let alphabet = parse_constraint(
+            &mut context,
+            "S ::= IA5String (FROM(\"a\"..\"c\" | \"x\" | \"b\"))",
+        )
+        .permitted_alphabet()
+        .unwrap();
+        assert_eq!(
+            alphabet,
+            PermittedAlphabet::from_ranges(vec![(0x61, 0x63), (0x78, 0x78)])
+        );
+        assert_eq!(alphabet.len(), 4);
+        assert_eq!(alphabet.index_of('x' as u32), Some(3));
+        assert_eq!(alphabet.nth(2), Some('c' as u32));
+        assert_eq!(alphabet.index_of('d' as u32), None);
+
+        let constraint = parse_constraint(
+            &mut context,
+            "S ::= IA5String (SIZE(1..4) ^ FROM(\"0\"..\"9\") ^ FROM(\"5\"..<\"Z\"))",
+        );
+        assert_eq!(
+            constraint.permitted_alphabet(),
+            Some(PermittedAlphabet::from_ranges(vec![(0x35, 0x39)]))
+        );
+        assert_eq!(
+            constraint.size_bounds().unwrap(),
+            Some(ConstraintBounds {
+                lower_bound: Bound::Integer(1.into()),
+                upper_bound: Some(Bound::Integer(4.into())),
+                extended_upper_bound: Bound::Integer(4.into()),
+            })
+        );
+
+        assert!(
+            parse_constraint(&mut context, "S ::= IA5String (FROM(\"a\"..\"z\", ...))")
+                .permitted_alphabet()
+                .unwrap()
+                .is_extensible
+        );
+    }
 }
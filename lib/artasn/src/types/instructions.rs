@@ -0,0 +1,170 @@
+use std::fmt::Display;
+
+/// An XER encoding instruction from X.693 clause 18 onwards.
+///
+/// An encoding instruction applies to a type either through an encoding prefix (e.g. `[XER:ATTRIBUTE] INTEGER`),
+/// or through the `ENCODING-CONTROL XER` section at the end of a module.
+/// Encoding instructions only affect EXTENDED-XER encodings, and are ignored by all other encoding rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum XerEncodingInstruction {
+    AnyAttributes,
+    AnyElement,
+    Attribute,
+    Base64,
+    Decimal,
+    /// The content of the element that is encoded as an empty element, in EXTENDED-XER form.
+    DefaultForEmpty(String),
+    Element,
+    EmbedValues,
+    List,
+    Name(XerNameModifier),
+    /// The namespace of the element, or `None` if the element has no namespace.
+    Namespace(Option<XerNamespace>),
+    PiOrComment {
+        text: String,
+        position: XerPiOrCommentPosition,
+    },
+    Text,
+    Untagged,
+    UseNil,
+    UseNumber,
+    UseOrder,
+    UseQName,
+    UseType,
+    UseUnion,
+    Whitespace(XerWhitespace),
+}
+
+impl XerEncodingInstruction {
+    /// Returns the instruction keyword, e.g. `USE-TYPE`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::AnyAttributes => "ANY-ATTRIBUTES",
+            Self::AnyElement => "ANY-ELEMENT",
+            Self::Attribute => "ATTRIBUTE",
+            Self::Base64 => "BASE64",
+            Self::Decimal => "DECIMAL",
+            Self::DefaultForEmpty(_) => "DEFAULT-FOR-EMPTY",
+            Self::Element => "ELEMENT",
+            Self::EmbedValues => "EMBED-VALUES",
+            Self::List => "LIST",
+            Self::Name(_) => "NAME",
+            Self::Namespace(_) => "NAMESPACE",
+            Self::PiOrComment { .. } => "PI-OR-COMMENT",
+            Self::Text => "TEXT",
+            Self::Untagged => "UNTAGGED",
+            Self::UseNil => "USE-NIL",
+            Self::UseNumber => "USE-NUMBER",
+            Self::UseOrder => "USE-ORDER",
+            Self::UseQName => "USE-QNAME",
+            Self::UseType => "USE-TYPE",
+            Self::UseUnion => "USE-UNION",
+            Self::Whitespace(_) => "WHITESPACE",
+        }
+    }
+}
+
+impl Display for XerEncodingInstruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())?;
+        match self {
+            Self::DefaultForEmpty(content) => write!(f, " AS \"{}\"", content),
+            Self::Name(modifier) => write!(f, " AS {}", modifier),
+            Self::Namespace(Some(namespace)) => {
+                write!(f, " AS \"{}\"", namespace.uri)?;
+                if let Some(prefix) = &namespace.prefix {
+                    write!(f, " PREFIX \"{}\"", prefix)?;
+                }
+                Ok(())
+            }
+            Self::PiOrComment { text, position } => write!(f, " AS \"{}\" {}", text, position),
+            Self::Whitespace(whitespace) => write!(f, " {}", whitespace),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The new name of an element specified with the `NAME` encoding instruction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum XerNameModifier {
+    Name(String),
+    Capitalized,
+    Uncapitalized,
+    Uppercased,
+    Lowercased,
+}
+
+impl XerNameModifier {
+    pub fn apply(&self, name: &str) -> String {
+        match self {
+            Self::Name(name) => name.clone(),
+            Self::Capitalized | Self::Uncapitalized => {
+                let mut chars = name.chars();
+                match chars.next() {
+                    Some(first) => {
+                        let first = match self {
+                            Self::Capitalized => first.to_ascii_uppercase(),
+                            _ => first.to_ascii_lowercase(),
+                        };
+                        std::iter::once(first).chain(chars).collect()
+                    }
+                    None => String::new(),
+                }
+            }
+            Self::Uppercased => name.to_ascii_uppercase(),
+            Self::Lowercased => name.to_ascii_lowercase(),
+        }
+    }
+}
+
+impl Display for XerNameModifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Name(name) => write!(f, "\"{}\"", name),
+            Self::Capitalized => f.write_str("CAPITALIZED"),
+            Self::Uncapitalized => f.write_str("UNCAPITALIZED"),
+            Self::Uppercased => f.write_str("UPPERCASED"),
+            Self::Lowercased => f.write_str("LOWERCASED"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XerNamespace {
+    pub uri: String,
+    pub prefix: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XerPiOrCommentPosition {
+    BeforeTag,
+    BeforeValue,
+    AfterValue,
+    AfterTag,
+}
+
+impl Display for XerPiOrCommentPosition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::BeforeTag => "BEFORE-TAG",
+            Self::BeforeValue => "BEFORE-VALUE",
+            Self::AfterValue => "AFTER-VALUE",
+            Self::AfterTag => "AFTER-TAG",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XerWhitespace {
+    Replace,
+    Collapse,
+}
+
+impl Display for XerWhitespace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Replace => "REPLACE",
+            Self::Collapse => "COLLAPSE",
+        })
+    }
+}
@@ -14,6 +14,9 @@ pub use constraints::*;
 mod class;
 pub use class::*;
 
+mod instructions;
+pub use instructions::*;
+
 use crate::{
     compiler::{
         parser::{AstElement, Error, ErrorKind, Result},
@@ -23,7 +26,7 @@ use crate::{
     values::{BuiltinValue, TypedValue, ValueResolve},
 };
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Class {
     Universal,
     Application,
@@ -157,6 +160,8 @@ pub struct ResolvedType {
     pub tag: Option<Tag>,
     pub ty: BuiltinType,
     pub constraint: Option<Constraint>,
+    /// The XER encoding instructions of the type and all types that it references, outermost first.
+    pub xer_instructions: Vec<XerEncodingInstruction>,
 }
 
 impl ResolvedType {
@@ -165,6 +170,7 @@ impl ResolvedType {
             tag: Some(Tag::universal(tag_type)),
             ty: BuiltinType::universal(tag_type),
             constraint: None,
+            xer_instructions: Vec::new(),
         }
     }
 
@@ -201,6 +207,8 @@ pub struct TaggedType {
     pub tag: Option<Tag>,
     pub ty: UntaggedType,
     pub constraint: Option<Constraint>,
+    /// The XER encoding instructions applied directly to the type, outermost first.
+    pub xer_instructions: Vec<XerEncodingInstruction>,
 }
 
 impl TaggedType {
@@ -209,12 +217,14 @@ impl TaggedType {
             tag: Some(Tag::universal(tag_type)),
             ty: UntaggedType::BuiltinType(BuiltinType::universal(tag_type)),
             constraint: None,
+            xer_instructions: Vec::new(),
         }
     }
 
     pub fn resolve(&self, context: &Context) -> Result<ResolvedType> {
         let mut tagged_ty = self;
         let mut tag = self.tag.as_ref();
+        let mut xer_instructions = self.xer_instructions.clone();
         loop {
             match &tagged_ty.ty {
                 UntaggedType::BuiltinType(ty) => {
@@ -234,6 +244,7 @@ impl TaggedType {
                         //   I2 ::= I1 (8..<MAX)
                         // the only valid values for I2 are 8 and 9
                         constraint: tagged_ty.constraint.clone(),
+                        xer_instructions,
                     });
                 }
                 UntaggedType::Reference(name) => {
@@ -247,6 +258,7 @@ impl TaggedType {
 
                     tagged_ty = &decl.ty;
                     tag = tag.or(tagged_ty.tag.as_ref());
+                    xer_instructions.extend(tagged_ty.xer_instructions.iter().cloned());
                 }
                 UntaggedType::ObjectClassField(ocf) => {
                     let class_ref =
@@ -259,6 +271,7 @@ impl TaggedType {
                                 ObjectClassFieldValueType::TaggedType(tagged_type) => {
                                     tagged_ty = tagged_type;
                                     tag = tag.or(tagged_ty.tag.as_ref());
+                                    xer_instructions.extend(tagged_ty.xer_instructions.iter().cloned());
                                 }
                                 _ => return Err(Error {
                                     kind: ErrorKind::Ast(format!(
@@ -532,6 +545,10 @@ impl BuiltinType {
                         ConstraintCheckMode::Size,
                         &BigInt::from(value.len() as i64),
                     )?,
+                    BuiltinValue::CharacterString(_, value) => constraint.includes_integer(
+                        ConstraintCheckMode::Size,
+                        &BigInt::from(value.chars().count() as i64),
+                    )?,
                     _ => None,
                 }
             }
@@ -564,6 +581,24 @@ impl BuiltinType {
             _ => (),
         }
 
+        if let (Some(constraint), BuiltinValue::CharacterString(_, value)) =
+            (&constraint, &typed_value.value)
+        {
+            if let Some(alphabet) = constraint.permitted_alphabet() {
+                // characters outside of an extensible permitted alphabet are extension additions
+                if !alphabet.is_extensible && !value.chars().all(|ch| alphabet.contains(ch as u32))
+                {
+                    return Err(Error {
+                        kind: ErrorKind::Ast(
+                            "value contains characters outside the permitted alphabet of type"
+                                .to_string(),
+                        ),
+                        loc: valref.loc,
+                    });
+                }
+            }
+        }
+
         if let (Self::Structure(seq), builtin_value @ BuiltinValue::Structure(_, value)) =
             (self, &typed_value.value)
         {
@@ -621,7 +656,15 @@ impl BuiltinType {
                             )?;
                         }
                     }
-                } else if seq_component.default_value.is_none() && !seq_component.optional {
+                } else if seq_component.default_value.is_none()
+                    && !seq_component.optional
+                    && !is_absent_extension_addition(&seq.components, seq_component, |name| {
+                        value
+                            .components
+                            .iter()
+                            .any(|val_component| val_component.name.element == name)
+                    })
+                {
                     return Err(Error {
                         kind: ErrorKind::Ast(format!(
                             "SEQUENCE missing required component '{}'",
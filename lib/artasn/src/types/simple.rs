@@ -16,7 +16,12 @@ pub struct BitStringType {
     pub named_bits: Option<Vec<NamedNumber>>,
 }
 
-pub type EnumeratedType = Vec<EnumerationItem>;
+#[derive(Debug, Clone)]
+pub struct EnumeratedType {
+    pub items: Vec<EnumerationItem>,
+    /// Whether the ENUMERATED has an extension marker.
+    pub extensible: bool,
+}
 
 #[derive(Debug, Clone)]
 pub enum EnumerationItemValue {
@@ -28,4 +33,6 @@ pub enum EnumerationItemValue {
 pub struct EnumerationItem {
     pub name: AstElement<String>,
     pub value: EnumerationItemValue,
+    /// Whether the item is an extension addition, i.e. it is defined after the extension marker.
+    pub is_extension: bool,
 }
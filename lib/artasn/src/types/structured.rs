@@ -18,6 +18,17 @@ pub struct StructureComponent {
     pub component_type: Box<TaggedType>,
     pub optional: bool,
     pub default_value: Option<LazyParsedDefaultValue>,
+    /// The extension addition that the component belongs to, or `None` if it is in the extension root.
+    pub extension_addition: Option<ExtensionAddition>,
+}
+
+/// An extension addition of a structure, which is either a single component or an extension addition group (`[[ ]]`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtensionAddition {
+    /// The index of the extension addition, in the order that the extension additions are defined.
+    /// All components of an extension addition group share the same index.
+    pub index: usize,
+    pub is_group: bool,
 }
 
 impl ComponentLike for StructureComponent {
@@ -52,11 +63,28 @@ pub enum ObjectClassFieldReferenceKind {
     ValueLike,
 }
 
+/// Returns whether `component` is an extension addition that is absent from a value, because none of the components
+/// of its extension addition are present according to `is_present`.
+/// An absent extension addition may be omitted from a value, even if its components are not OPTIONAL.
+pub fn is_absent_extension_addition(
+    components: &[StructureComponent],
+    component: &StructureComponent,
+    is_present: impl Fn(&str) -> bool,
+) -> bool {
+    component.extension_addition.is_some_and(|addition| {
+        !components.iter().any(|other| {
+            other.extension_addition == Some(addition) && is_present(&other.name.element)
+        })
+    })
+}
+
 #[derive(Debug, Clone)]
 pub struct Structure {
     // Always either Sequence or Set
     pub ty: TagType,
     pub components: Vec<StructureComponent>,
+    /// Whether the structure has an extension marker.
+    pub extensible: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -70,6 +98,8 @@ pub struct StructureOf {
 pub struct ChoiceAlternative {
     pub name: AstElement<String>,
     pub alternative_type: Box<TaggedType>,
+    /// Whether the alternative is an extension addition, i.e. it is defined after the extension marker.
+    pub is_extension: bool,
 }
 
 impl ComponentLike for ChoiceAlternative {
@@ -85,4 +115,6 @@ impl ComponentLike for ChoiceAlternative {
 #[derive(Debug, Clone)]
 pub struct Choice {
     pub alternatives: Vec<ChoiceAlternative>,
+    /// Whether the CHOICE has an extension marker.
+    pub extensible: bool,
 }
@@ -19,6 +19,7 @@ use crate::{
         parser::{AstElement, Error, ErrorKind, Result},
         Context,
     },
+    encoding::is_real_type,
     module::QualifiedIdentifier,
     types::*,
 };
@@ -138,10 +139,20 @@ impl TryEq for AstElement<ResolvedValue> {
     fn try_eq(&self, context: &Context, rhs: &Self) -> Result<bool> {
         let lhs = &self.element;
         let rhs = &rhs.element;
+        if is_real_type(&lhs.ty.ty) || is_real_type(&rhs.ty.ty) {
+            if let (Some(lhs), Some(rhs)) = (
+                normalize_real(context, &lhs.value)?,
+                normalize_real(context, &rhs.value)?,
+            ) {
+                return Ok(lhs == rhs);
+            }
+        }
         Ok(match (&lhs.value, &rhs.value) {
             (BuiltinValue::Boolean(lhs), BuiltinValue::Boolean(rhs)) => lhs == rhs,
             (BuiltinValue::Integer(lhs), BuiltinValue::Integer(rhs)) => lhs == rhs,
-            (BuiltinValue::BitString(lhs), BuiltinValue::BitString(rhs)) => lhs == rhs,
+            (BuiltinValue::BitString(lhs_bs), BuiltinValue::BitString(rhs_bs)) => {
+                bit_string_eq(&lhs.ty, lhs_bs, rhs_bs)
+            }
             (BuiltinValue::OctetString(lhs), BuiltinValue::OctetString(rhs)) => lhs == rhs,
             (BuiltinValue::Null, BuiltinValue::Null) => true,
             (BuiltinValue::ObjectIdentifier(lhs), BuiltinValue::ObjectIdentifier(rhs)) => {
@@ -150,27 +161,177 @@ impl TryEq for AstElement<ResolvedValue> {
             (BuiltinValue::Enumerated(lhs), BuiltinValue::Enumerated(rhs)) => {
                 lhs.try_eq(context, rhs)?
             }
+            (BuiltinValue::RelativeOid(lhs), BuiltinValue::RelativeOid(rhs)) => {
+                lhs.resolve_oid(context)? == rhs.resolve_oid(context)?
+            }
+            (BuiltinValue::RealLiteral(lhs), BuiltinValue::RealLiteral(rhs)) => {
+                lhs.normalize() == rhs.normalize()
+            }
+            (BuiltinValue::Structure(lhs_tag, lhs), BuiltinValue::Structure(rhs_tag, rhs)) => {
+                lhs_tag == rhs_tag
+                    && lhs.components.len() == rhs.components.len()
+                    && try_eq_all(
+                        lhs.components
+                            .iter()
+                            .zip(&rhs.components)
+                            .map(|(lhs, rhs)| {
+                                Ok(lhs.name.element == rhs.name.element
+                                    && lhs.value.try_eq(context, &rhs.value)?)
+                            }),
+                    )?
+            }
+            (BuiltinValue::StructureOf(lhs_tag, lhs), BuiltinValue::StructureOf(rhs_tag, rhs)) => {
+                lhs_tag == rhs_tag
+                    && lhs.len() == rhs.len()
+                    && match lhs_tag {
+                        // the elements of a SET OF value are not ordered
+                        TagType::Set => try_eq_unordered(context, lhs, rhs)?,
+                        _ => try_eq_all(
+                            lhs.iter()
+                                .zip(rhs)
+                                .map(|(lhs, rhs)| lhs.try_eq(context, rhs)),
+                        )?,
+                    }
+            }
             (BuiltinValue::Choice(lhs), BuiltinValue::Choice(rhs)) => {
-                lhs.value.try_eq(context, &rhs.value)?
+                lhs.alternative.element == rhs.alternative.element
+                    && lhs.value.try_eq(context, &rhs.value)?
             }
             (
                 BuiltinValue::CharacterString(lhs_tag, lhs),
                 BuiltinValue::CharacterString(rhs_tag, rhs),
             ) => lhs_tag == rhs_tag && lhs == rhs,
-            _ => {
-                return Err(Error {
-                    kind: ErrorKind::Ast(format!(
-                        "cannot compare values of types {} and {}",
-                        lhs.value.tag_type(context)?,
-                        rhs.value.tag_type(context)?
-                    )),
-                    loc: self.loc,
-                })
+            (BuiltinValue::Time(lhs), BuiltinValue::Time(rhs)) => {
+                lhs.to_ber_string() == rhs.to_ber_string()
+            }
+            (BuiltinValue::UTCTime(lhs), BuiltinValue::UTCTime(rhs)) => {
+                lhs.to_ber_string() == rhs.to_ber_string()
+            }
+            (BuiltinValue::GeneralizedTime(lhs), BuiltinValue::GeneralizedTime(rhs)) => {
+                lhs.to_ber_string() == rhs.to_ber_string()
             }
+            (BuiltinValue::Date(lhs), BuiltinValue::Date(rhs)) => {
+                lhs.to_ber_string() == rhs.to_ber_string()
+            }
+            (BuiltinValue::TimeOfDay(lhs), BuiltinValue::TimeOfDay(rhs)) => {
+                lhs.to_ber_string() == rhs.to_ber_string()
+            }
+            (BuiltinValue::DateTime(lhs), BuiltinValue::DateTime(rhs)) => {
+                lhs.to_ber_string() == rhs.to_ber_string()
+            }
+            (BuiltinValue::Duration(lhs), BuiltinValue::Duration(rhs)) => {
+                lhs.to_ber_string() == rhs.to_ber_string()
+            }
+            (BuiltinValue::Containing(lhs), BuiltinValue::Containing(rhs)) => {
+                lhs.container_type == rhs.container_type && lhs.value.try_eq(context, &rhs.value)?
+            }
+            // values of different types are never equal
+            _ => false,
         })
     }
 }
 
+/// Returns a finite REAL value, which is an INTEGER, a REAL literal or a `{ mantissa, base, exponent }` value,
+/// as a mantissa that is not divisible by 2 or 5 and the exponents of 2 and 5,
+/// such that equal values have equal representations regardless of their form and base.
+/// Returns `None` if the value is not a finite REAL value.
+fn normalize_real(
+    context: &Context,
+    value: &BuiltinValue,
+) -> Result<Option<(BigInt, BigInt, BigInt)>> {
+    let (mut mantissa, base, exponent) = match value {
+        BuiltinValue::Integer(int) => (int.clone(), 10, BigInt::ZERO),
+        BuiltinValue::RealLiteral(lit) => (lit.mantissa.clone(), 10, lit.exponent.clone()),
+        BuiltinValue::Structure(_, structure) => {
+            let mut components = [None, None, None];
+            for component in &structure.components {
+                let index = match component.name.element.as_str() {
+                    "mantissa" => 0,
+                    "base" => 1,
+                    "exponent" => 2,
+                    // PLUS-INFINITY, MINUS-INFINITY and NOT-A-NUMBER
+                    _ => return Ok(None),
+                };
+                components[index] = match component.value.resolve(context)?.value {
+                    BuiltinValue::Integer(int) => Some(int),
+                    _ => return Ok(None),
+                };
+            }
+            match components {
+                [Some(mantissa), Some(base), Some(exponent)] if base == BigInt::from(2) => {
+                    (mantissa, 2, exponent)
+                }
+                [Some(mantissa), Some(_), Some(exponent)] => (mantissa, 10, exponent),
+                _ => return Ok(None),
+            }
+        }
+        _ => return Ok(None),
+    };
+    if mantissa == BigInt::ZERO {
+        return Ok(Some((mantissa, BigInt::ZERO, BigInt::ZERO)));
+    }
+
+    // m * 10^e is m * 2^e * 5^e
+    let (mut exponent_2, mut exponent_5) = match base {
+        2 => (exponent, BigInt::ZERO),
+        _ => (exponent.clone(), exponent),
+    };
+    while &mantissa % 2 == BigInt::ZERO {
+        mantissa /= 2;
+        exponent_2 += 1;
+    }
+    while &mantissa % 5 == BigInt::ZERO {
+        mantissa /= 5;
+        exponent_5 += 1;
+    }
+    Ok(Some((mantissa, exponent_2, exponent_5)))
+}
+
+/// Returns whether the BIT STRING values are equal.
+/// Trailing 0 bits are not significant in values of a type with named bits (X.680 clause 22.7).
+fn bit_string_eq(ty: &ResolvedType, lhs: &BitStringValue, rhs: &BitStringValue) -> bool {
+    let (lhs_len, rhs_len) = match ty.ty {
+        BuiltinType::BitString(BitStringType {
+            named_bits: Some(_),
+        }) => (
+            lhs.len_without_trailing_zeros(),
+            rhs.len_without_trailing_zeros(),
+        ),
+        _ => (lhs.len(), rhs.len()),
+    };
+    let bit = |bs: &BitStringValue, i: u64| bs.data[(i / 8) as usize] & (0x80 >> (i % 8));
+    lhs_len == rhs_len && (0..lhs_len).all(|i| bit(lhs, i) == bit(rhs, i))
+}
+
+/// Returns whether every element of `lhs` is equal to a distinct element of `rhs`, in any order.
+fn try_eq_unordered(
+    context: &Context,
+    lhs: &[AstElement<TypedValue>],
+    rhs: &[AstElement<TypedValue>],
+) -> Result<bool> {
+    let mut is_matched = vec![false; rhs.len()];
+    'lhs: for lhs in lhs {
+        for (index, rhs) in rhs.iter().enumerate() {
+            if !is_matched[index] && lhs.try_eq(context, rhs)? {
+                is_matched[index] = true;
+                continue 'lhs;
+            }
+        }
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+/// Returns whether every comparison is true, stopping at the first false comparison or error.
+fn try_eq_all(comparisons: impl Iterator<Item = Result<bool>>) -> Result<bool> {
+    for comparison in comparisons {
+        if !comparison? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
 #[derive(Debug, Clone)]
 pub enum ValueReference {
     BuiltinValue(BuiltinValue),
@@ -209,6 +370,7 @@ impl ValueResolve for AstElement<TypedValue> {
                             tag: tag.cloned(),
                             ty: resolved_type.ty,
                             constraint: resolved_type.constraint,
+                            xer_instructions: resolved_type.xer_instructions,
                         },
                         value: value.clone(),
                     });
@@ -335,6 +497,14 @@ mod test {
         "../../test-data/encode/CharacterStringTest"
     );
     json_test!(test_encode_real, "../../test-data/encode/RealTest");
+    json_test!(
+        test_encode_default_values,
+        "../../test-data/encode/DefaultValueTest"
+    );
+    json_test!(
+        test_encode_set_ordering,
+        "../../test-data/encode/SetOrderingTest"
+    );
     json_test!(
         test_encode_contents_constraint,
         "../../test-data/encode/ContentsConstraintTest"
@@ -23,6 +23,32 @@ pub struct BitStringValue {
     pub unused_bits: u8,
 }
 
+impl BitStringValue {
+    /// Returns the number of bits in the value.
+    pub fn len(&self) -> u64 {
+        self.data.len() as u64 * 8 - self.unused_bits as u64
+    }
+
+    /// Returns whether the value contains no bits.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of bits in the value, excluding its trailing 0 bits.
+    pub fn len_without_trailing_zeros(&self) -> u64 {
+        let mut len = self.len();
+        while len > 0 {
+            let bit_index = len - 1;
+            let byte = self.data[(bit_index / 8) as usize];
+            if (byte >> (7 - bit_index % 8)) & 1 == 1 {
+                break;
+            }
+            len -= 1;
+        }
+        len
+    }
+}
+
 impl Display for BitStringValue {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut bit_str = String::with_capacity(self.data.len() * 8);
@@ -51,7 +77,10 @@ pub trait ComponentValueLike {
 pub struct StructureValueComponent {
     pub name: AstElement<String>,
     pub value: AstElement<TypedValue>,
+    /// Whether the value was omitted from the structure value and filled in with the DEFAULT value.
     pub is_default: bool,
+    /// The DEFAULT value of the component, if its type declares one.
+    pub default_value: Option<AstElement<TypedValue>>,
 }
 
 impl ComponentValueLike for StructureValueComponent {
@@ -247,6 +276,26 @@ pub struct RealLiteral {
     pub exponent: BigInt,
 }
 
+impl RealLiteral {
+    /// Moves the trailing decimal zeros of the mantissa into the exponent,
+    /// such that equal values have equal representations.
+    pub fn normalize(&self) -> RealLiteral {
+        let mut mantissa = self.mantissa.clone();
+        let mut exponent = self.exponent.clone();
+        if mantissa == BigInt::ZERO {
+            return RealLiteral {
+                mantissa,
+                exponent: BigInt::ZERO,
+            };
+        }
+        while &mantissa % 10 == BigInt::ZERO {
+            mantissa /= 10;
+            exponent += 1;
+        }
+        RealLiteral { mantissa, exponent }
+    }
+}
+
 impl Display for RealLiteral {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let exponent: i64 = self.exponent.clone().try_into().unwrap();
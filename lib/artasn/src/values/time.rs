@@ -52,6 +52,62 @@ pub enum TimeZone {
     },
 }
 
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Converts the local date and time in the time zone to UTC, returning the year, month, day, hour and minute.
+fn to_utc(
+    (year, month, day, hour, minute): (u16, u8, u8, u8, u8),
+    tz: &TimeZone,
+) -> (u16, u8, u8, u8, u8) {
+    let offset = match tz {
+        TimeZone::Z => 0,
+        TimeZone::Offset { sign, hour, minute } => {
+            let offset = *hour as i32 * 60 + *minute as i32;
+            match sign {
+                TimeZoneSign::Plus => offset,
+                TimeZoneSign::Minus => -offset,
+            }
+        }
+    };
+
+    let minutes = hour as i32 * 60 + minute as i32 - offset;
+    let (mut year, mut month, mut day) = (year, month, day);
+    if minutes < 0 {
+        if day > 1 {
+            day -= 1;
+        } else if month > 1 {
+            month -= 1;
+            day = days_in_month(year, month);
+        } else {
+            year -= 1;
+            month = 12;
+            day = 31;
+        }
+    } else if minutes >= 24 * 60 {
+        if day < days_in_month(year, month) {
+            day += 1;
+        } else if month < 12 {
+            month += 1;
+            day = 1;
+        } else {
+            year += 1;
+            month = 1;
+            day = 1;
+        }
+    }
+    let minutes = minutes.rem_euclid(24 * 60);
+    (year, month, day, (minutes / 60) as u8, (minutes % 60) as u8)
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct UTCTime {
     pub year: u8,
@@ -162,6 +218,32 @@ impl UTCTime {
         }
         str
     }
+
+    /// Returns the canonical form of the value (X.690 clause 11.8),
+    /// which is in UTC and always includes the seconds.
+    pub fn to_canonical_string(&self) -> String {
+        // the century of a two-digit year is only needed to find leap years
+        let century = if self.year < 50 { 2000 } else { 1900 };
+        let (year, month, day, hour, minute) = to_utc(
+            (
+                century + self.year as u16,
+                self.month,
+                self.day,
+                self.hour,
+                self.minute,
+            ),
+            &self.tz,
+        );
+        format!(
+            "{:02}{:02}{:02}{:02}{:02}{:02}Z",
+            year % 100,
+            month,
+            day,
+            hour,
+            minute,
+            self.second.unwrap_or(0)
+        )
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -327,6 +409,40 @@ impl GeneralizedTime {
         }
         str
     }
+
+    /// Returns the canonical form of the value (X.690 clause 11.7),
+    /// which is in UTC, always includes the minutes and seconds, and has no trailing zeros in its fractional seconds.
+    /// Returns `None` if the value is a local time, which cannot be converted to UTC.
+    pub fn to_canonical_string(&self) -> Option<String> {
+        let (year, month, day, hour, minute) = to_utc(
+            (
+                self.year,
+                self.month,
+                self.day,
+                self.hour,
+                self.minute.unwrap_or(0),
+            ),
+            self.tz.as_ref()?,
+        );
+        let mut str = format!(
+            "{:04}{:02}{:02}{:02}{:02}{:02}",
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            self.second.unwrap_or(0)
+        );
+        if let Some(millisecond) = &self.millisecond {
+            let fraction = millisecond.to_string();
+            let fraction = fraction.trim_end_matches('0');
+            if !fraction.is_empty() {
+                str.write_fmt(format_args!(".{}", fraction)).unwrap();
+            }
+        }
+        str.push('Z');
+        Some(str)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -374,6 +490,11 @@ impl Date {
         str.write_fmt(format_args!("{:02}", self.day)).unwrap();
         str
     }
+
+    /// Returns the date in the form YYYY-MM-DD of its value notation, which the XML, JSON and GSER encodings use.
+    pub fn to_value_string(&self) -> String {
+        format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -429,6 +550,11 @@ impl TimeOfDay {
         str.write_fmt(format_args!("{:02}", self.second)).unwrap();
         str
     }
+
+    /// Returns the time in the form hh:mm:ss of its value notation.
+    pub fn to_value_string(&self) -> String {
+        format!("{:02}:{:02}:{:02}", self.hour, self.minute, self.second)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -468,6 +594,15 @@ impl DateTime {
         str.write_str(&self.time_of_day.to_ber_string()).unwrap();
         str
     }
+
+    /// Returns the date and time in the form YYYY-MM-DDThh:mm:ss of its value notation.
+    pub fn to_value_string(&self) -> String {
+        format!(
+            "{}T{}",
+            self.date.to_value_string(),
+            self.time_of_day.to_value_string()
+        )
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -557,13 +692,18 @@ impl Duration {
         // the 'P' prefix is not included in the encoded format
         self.source.as_str()[1..].to_string()
     }
+
+    /// Returns the duration with the 'P' prefix of its value notation.
+    pub fn to_value_string(&self) -> String {
+        self.source.clone()
+    }
 }
 
 #[cfg(test)]
 mod test {
     use crate::{
         compiler::parser::{AstElement, Loc},
-        values::{Date, DateTime, TimeOfDay, TimeZone, TimeZoneSign, UTCTime},
+        values::{Date, DateTime, GeneralizedTime, TimeOfDay, TimeZone, TimeZoneSign, UTCTime},
     };
 
     #[test]
@@ -711,4 +851,25 @@ mod test {
             }
         );
     }
+
+    #[test]
+    fn test_canonical_string() {
+        for (str, canonical) in [
+            ("8804152030Z", "880415203000Z"),
+            ("8804152030-0600", "880416023000Z"),
+            ("0003010030+0100", "000229233000Z"),
+        ] {
+            let utc = UTCTime::parse(&AstElement::new(str.as_bytes(), Loc::at(0))).unwrap();
+            assert_eq!(utc.to_canonical_string(), canonical);
+        }
+
+        for (str, canonical) in [
+            ("1988041520Z", Some("19880415200000Z")),
+            ("19881231233000.50-0100", Some("19890101003000.5Z")),
+            ("20240102030405.0Z", Some("20240102030405Z")),
+        ] {
+            let gt = GeneralizedTime::parse(&AstElement::new(str.as_bytes(), Loc::at(0))).unwrap();
+            assert_eq!(gt.to_canonical_string().as_deref(), canonical);
+        }
+    }
 }
@@ -0,0 +1,135 @@
+use std::io::{self, Read};
+
+use artasn::encoding::{DecodedValue, DecodedValueForm};
+use base64::Engine;
+use clap::ValueEnum;
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum InputFormat {
+    /// The encoded data as is
+    Raw,
+    /// Hexadecimal digits, which may be separated by whitespace
+    Hex,
+    /// Base64 text, which may be separated by whitespace
+    Base64,
+    /// One or more PEM blocks, such as "-----BEGIN CERTIFICATE-----"
+    Pem,
+}
+
+fn decode_base64(text: &str) -> Result<Vec<u8>, String> {
+    let text = text
+        .chars()
+        .filter(|ch| !ch.is_ascii_whitespace())
+        .collect::<String>();
+    base64::engine::general_purpose::STANDARD
+        .decode(text)
+        .map_err(|err| format!("invalid base64: {}", err))
+}
+
+/// Returns the concatenated contents of the PEM blocks in the text (RFC 7468).
+fn decode_pem(text: &str) -> Result<Vec<u8>, String> {
+    let mut data = Vec::new();
+    let mut block: Option<(&str, String)> = None;
+    for line in text.lines() {
+        let line = line.trim();
+        if let Some(label) = line
+            .strip_prefix("-----BEGIN ")
+            .and_then(|line| line.strip_suffix("-----"))
+        {
+            if let Some((label, _)) = block {
+                return Err(format!("PEM block '{}' is missing its END line", label));
+            }
+            block = Some((label, String::new()));
+        } else if let Some(label) = line
+            .strip_prefix("-----END ")
+            .and_then(|line| line.strip_suffix("-----"))
+        {
+            match block.take() {
+                Some((begin_label, contents)) if begin_label == label => {
+                    data.extend(decode_base64(&contents)?)
+                }
+                Some((begin_label, _)) => {
+                    return Err(format!(
+                        "PEM block '{}' ends with the label '{}'",
+                        begin_label, label
+                    ))
+                }
+                None => return Err(format!("PEM block '{}' is missing its BEGIN line", label)),
+            }
+        } else if let Some((_, contents)) = &mut block {
+            // RFC 1421 headers, such as "Proc-Type: 4,ENCRYPTED", are not part of the data
+            if !line.contains(':') {
+                contents.push_str(line);
+            }
+        }
+    }
+    match block {
+        Some((label, _)) => Err(format!("PEM block '{}' is missing its END line", label)),
+        None if data.is_empty() => Err("input does not contain a PEM block".to_string()),
+        None => Ok(data),
+    }
+}
+
+/// Reads the encoded data from the file, or from standard input if there is no file.
+pub fn read_input(path: Option<&str>, format: InputFormat) -> Result<Vec<u8>, String> {
+    let input = match path {
+        Some(path) => std::fs::read(path)
+            .map_err(|err| format!("failed to read input file at '{}': {}", path, err))?,
+        None => {
+            let mut input = Vec::new();
+            io::stdin()
+                .read_to_end(&mut input)
+                .map_err(|err| format!("failed to read standard input: {}", err))?;
+            input
+        }
+    };
+    if let InputFormat::Raw = format {
+        return Ok(input);
+    }
+
+    let text = String::from_utf8(input).map_err(|_| "input is not UTF-8 text".to_string())?;
+    match format {
+        InputFormat::Raw => unreachable!(),
+        InputFormat::Hex => hex::decode(
+            text.chars()
+                .filter(|ch| !ch.is_ascii_whitespace())
+                .collect::<String>(),
+        )
+        .map_err(|err| format!("invalid hex: {}", err)),
+        InputFormat::Base64 => decode_base64(&text),
+        InputFormat::Pem => decode_pem(&text),
+    }
+}
+
+/// Prints the decoded value and its elements, indented by their depth, such as:
+///
+/// ```text
+/// [UNIVERSAL 16] Module.Type (offset 0, length 6)
+///   id [CONTEXT-SPECIFIC 0] (offset 2, length 1) = 5
+/// ```
+pub fn print_decoded_value(value: &DecodedValue, depth: usize) {
+    let mut line = "  ".repeat(depth);
+    let metadata = value.metadata.as_ref();
+    if let Some(component_name) = metadata.and_then(|metadata| metadata.component_name.as_ref()) {
+        line.push_str(component_name);
+        line.push(' ');
+    }
+    line.push_str(&value.tag.element.to_string());
+    if let Some(type_ident) = metadata.and_then(|metadata| metadata.type_ident.as_ref()) {
+        line.push_str(&format!(" {}", type_ident));
+    }
+    line.push_str(&format!(
+        " (offset {}, length {})",
+        value.tag.pos.start, value.len.element
+    ));
+
+    match &value.form {
+        DecodedValueForm::Primitive(kind) => println!("{} = {}", line, kind),
+        DecodedValueForm::Constructed(elements) => {
+            println!("{}", line);
+            for element in elements {
+                print_decoded_value(element, depth + 1);
+            }
+        }
+    }
+}
@@ -2,12 +2,19 @@ use std::{fmt::Display, fs, time::Instant};
 
 use artasn::{
     compiler::{options::CompilerConfig, Compiler, Context},
-    encoding::{EncodeMode, TransferSyntax},
+    encoding::{
+        der_validate_value, dump_decoded_values, DecodeMode, EncodeMode, EncodeOptions,
+        SetOrdering, TransferSyntax,
+    },
     module::QualifiedIdentifier,
-    values::ValueResolve,
+    types::{ResolvedType, UntaggedType},
+    values::{ResolvedValue, ValueResolve},
 };
 use clap::{Parser, ValueEnum};
 
+mod decode;
+use decode::InputFormat;
+
 #[derive(Debug, Parser)]
 #[command(name = "artasn")]
 #[command(version = env!("CARGO_PKG_VERSION"))]
@@ -22,15 +29,47 @@ struct Cli {
     /// Encode an ASN.1 value definition in the format "ModuleName.valueName"
     #[arg(long, group = "group_encode")]
     encode: Option<String>,
-    /// The transfer syntax to encode the value into
+    /// Convert encoded data of an ASN.1 type in the format "ModuleName.TypeName" from one transfer syntax into another
+    #[arg(long, group = "group_encode", group = "group_input", requires = "from")]
+    convert: Option<String>,
+    /// The transfer syntax of the encoded data to convert
+    #[arg(long, short = 'f', requires = "convert")]
+    from: Option<TransferSyntaxName>,
+    /// Decode encoded data, as a value of an ASN.1 type in the format "ModuleName.TypeName" if one is given
+    #[arg(long, group = "group_encode", group = "group_input", num_args = 0..=1, value_name = "TYPE")]
+    decode: Option<Option<String>>,
+    /// Print the decoded data as an annotated listing of offsets, lengths, tags and values, like dumpasn1
+    #[arg(long, requires = "decode")]
+    dump: bool,
+    /// Decode BER data and report every violation of the DER restrictions on BER, instead of failing on the first one
+    #[arg(long, requires = "decode")]
+    validate_der: bool,
+    /// Path to the file containing the encoded data to convert or decode, instead of standard input
+    #[arg(long, short = 'i', requires = "group_input")]
+    input: Option<String>,
+    /// The format of the encoded data to convert or decode
+    #[arg(long, value_enum, default_value_t = InputFormat::Raw, requires = "group_input")]
+    input_format: InputFormat,
+    /// Path to the file to write the encoded data to instead of printing it
+    #[arg(long, short = 'o', requires = "group_encode")]
+    output: Option<String>,
+    /// The transfer syntax to encode the value into, or of the encoded data to decode
     #[clap(long, short = 't', default_value_t = TransferSyntaxName::DER, requires = "group_encode")]
     transfer_syntax: TransferSyntaxName,
+    /// Fail to encode instead of sorting SET components and SET OF elements that are not in canonical order
+    #[arg(long, requires = "group_encode")]
+    verify_set_order: bool,
+    /// Encode SEQUENCE and SET components that are equal to their DEFAULT value
+    /// (BER, PER, UPER, OER, XER, E-XER, JER and GSER; the canonical transfer syntaxes always omit them)
+    #[arg(long, requires = "group_encode")]
+    encode_default_values: bool,
     /// ASN.1 module files to compile
     #[arg(required = true, num_args = 1..)]
     files: Vec<String>,
 }
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
+#[allow(clippy::upper_case_acronyms)]
 enum TransferSyntaxName {
     #[value(name = "BER")]
     BER,
@@ -50,12 +89,16 @@ enum TransferSyntaxName {
     XER,
     #[value(name = "CXER")]
     CXER,
-    #[value(name = "E-CER")]
+    #[value(name = "E-XER")]
     EXER,
     #[value(name = "OER")]
     OER,
     #[value(name = "COER")]
     COER,
+    #[value(name = "JER")]
+    JER,
+    #[value(name = "GSER")]
+    GSER,
 }
 
 impl Display for TransferSyntaxName {
@@ -73,6 +116,8 @@ impl Display for TransferSyntaxName {
             Self::EXER => "E-XER",
             Self::OER => "OER",
             Self::COER => "COER",
+            Self::JER => "JER",
+            Self::GSER => "GSER",
         })
     }
 }
@@ -98,6 +143,143 @@ fn elapsed_to_string(start: &Instant) -> String {
     }
 }
 
+fn get_transfer_syntax(name: TransferSyntaxName) -> &'static TransferSyntax {
+    TransferSyntax::get_by_name(name.to_string().as_str())
+        .expect("invalid transfer syntax (this should be prevented by clap)")
+}
+
+/// Looks up the identifier in the format "ModuleName.name", where `kind` is either "value" or "type".
+fn parse_qualified_identifier(context: &Context, ident: &str, kind: &str) -> QualifiedIdentifier {
+    let placeholder = match kind {
+        "type" => "TypeName",
+        _ => "valueName",
+    };
+    let dot_count = ident.chars().filter(|ch| *ch == '.').count();
+    if dot_count == 0 {
+        exit_with_error(format_args!(
+            "{} '{}' is missing module name; use the format 'ModuleName.{}'",
+            kind, ident, ident
+        ));
+    } else if dot_count > 1 {
+        exit_with_error(format_args!(
+            "{} '{}' is malformed; use the format 'ModuleName.{}'",
+            kind, ident, placeholder
+        ))
+    }
+
+    let split = ident.split(".").collect::<Vec<&str>>();
+    let module_name = split[0].trim();
+    let name = split[1].trim();
+
+    if module_name.is_empty() {
+        exit_with_error(format_args!("module name cannot be empty"));
+    }
+    if name.is_empty() {
+        exit_with_error(format_args!("{} name cannot be empty", kind));
+    }
+
+    let module = match context.lookup_module_by_name(module_name) {
+        Some(module) => module.ident.clone(),
+        None => exit_with_error(format_args!("module '{}' could not be found", module_name)),
+    };
+    QualifiedIdentifier::new(module, name.to_string())
+}
+
+fn resolve_type(context: &Context, ident: &QualifiedIdentifier) -> ResolvedType {
+    let declared_type = match context.lookup_type(ident) {
+        Some(ty) => ty,
+        None => exit_with_error(format_args!(
+            "type '{}' could not be found in module '{}'",
+            ident.name, ident.module.name
+        )),
+    };
+    match declared_type.ty.resolve(context) {
+        Ok(resolved) => resolved,
+        Err(err) => exit_with_error(format_args!(
+            "failed to resolve type: {}",
+            err.kind.message()
+        )),
+    }
+}
+
+fn read_input(args: &Cli) -> Vec<u8> {
+    match decode::read_input(args.input.as_deref(), args.input_format) {
+        Ok(input) => input,
+        Err(err) => exit_with_error(format_args!("{}", err)),
+    }
+}
+
+fn encode_value(
+    args: &Cli,
+    context: &Context,
+    value: &ResolvedValue,
+    type_name: Option<String>,
+) -> Vec<u8> {
+    let transfer_syntax = args.transfer_syntax;
+    let ts = get_transfer_syntax(transfer_syntax);
+    let encoder = match ts.get_codec().encoder {
+        Some(encoder) => encoder,
+        None => exit_with_error(format_args!(
+            "encoding with the {} transfer syntax is not yet implemented",
+            transfer_syntax
+        )),
+    };
+
+    let options = EncodeOptions {
+        set_ordering: if args.verify_set_order {
+            SetOrdering::Verify
+        } else {
+            SetOrdering::Sort
+        },
+        encode_default_values: args.encode_default_values,
+        type_name,
+    };
+    let mut buf = Vec::with_capacity(64 * 1024);
+    match encoder(ts, EncodeMode::Normal, &options, &mut buf, context, value) {
+        Ok(()) => (),
+        Err(err) => exit_with_error(format_args!(
+            "failed to encode value: {}",
+            err.kind.message()
+        )),
+    }
+    buf
+}
+
+/// Returns whether the transfer syntax is text, like XML, JSON and GSER encodings, rather than binary.
+fn is_text_syntax(name: TransferSyntaxName) -> bool {
+    matches!(
+        get_transfer_syntax(name),
+        TransferSyntax::Xml(_) | TransferSyntax::Json(_) | TransferSyntax::String(_)
+    )
+}
+
+/// Writes the encoded data to the output file, or prints it if there is no output file.
+/// Binary encodings are written to files as raw bytes, but printed as hex.
+fn write_output(args: &Cli, start: &Instant, buf: Vec<u8>) {
+    if let Some(path) = &args.output {
+        if let Err(err) = fs::write(path, &buf) {
+            exit_with_error(format_args!(
+                "failed to write output file at '{}': {}",
+                path, err
+            ));
+        }
+        if !args.silent {
+            println!("encoded in {}", elapsed_to_string(start));
+        }
+        return;
+    }
+
+    let output = if is_text_syntax(args.transfer_syntax) {
+        String::from_utf8(buf).expect("encoding is not UTF-8")
+    } else {
+        hex::encode_upper(buf)
+    };
+    if !args.silent {
+        println!("encoded in {}\n", elapsed_to_string(start));
+    }
+    println!("{}", output);
+}
+
 fn main() {
     let args = Cli::parse();
 
@@ -158,75 +340,156 @@ fn main() {
     }
     let start = Instant::now();
 
-    if let Some(value) = args.encode {
-        let transfer_syntax = args.transfer_syntax;
-        let ts = TransferSyntax::get_by_name(transfer_syntax.to_string().as_str())
-            .expect("invalid transfer syntax (this should be prevented by clap)");
-        let encoder = match ts.get_codec().encoder {
-            Some(encoder) => encoder,
+    if let Some(value) = &args.encode {
+        let ident = parse_qualified_identifier(&context, value, "value");
+        let declared_value = match context.lookup_value(&ident) {
+            Some(value) => value,
             None => exit_with_error(format_args!(
-                "encoding with the {} transfer syntax is not yet implemented",
-                transfer_syntax
+                "value '{}' could not be found in module '{}'",
+                ident.name, ident.module.name
             )),
         };
 
-        let dot_count = value.chars().filter(|ch| *ch == '.').count();
-        if dot_count == 0 {
+        let value = match declared_value.value.resolve(&context) {
+            Ok(value) => value,
+            Err(err) => exit_with_error(format_args!(
+                "failed to resolve value: {}",
+                err.kind.message()
+            )),
+        };
+
+        let type_name = match &declared_value.ty.ty {
+            UntaggedType::Reference(typeref) => Some(typeref.element.name.clone()),
+            _ => None,
+        };
+        let buf = encode_value(&args, &context, &value, type_name);
+        write_output(&args, &start, buf);
+    }
+
+    if let Some(ty) = &args.convert {
+        let ident = parse_qualified_identifier(&context, ty, "type");
+        let resolved = resolve_type(&context, &ident);
+
+        let from = args
+            .from
+            .expect("missing input transfer syntax (this should be prevented by clap)");
+        let from_ts = get_transfer_syntax(from);
+        let decoder = match from_ts.get_codec().decoder {
+            Some(decoder) => decoder,
+            None => exit_with_error(format_args!(
+                "decoding with the {} transfer syntax is not yet implemented",
+                from
+            )),
+        };
+        if get_transfer_syntax(args.transfer_syntax)
+            .get_codec()
+            .encoder
+            .is_none()
+        {
             exit_with_error(format_args!(
-                "value '{}' is missing module name; use the format 'ModuleName.{}'",
-                value, value
+                "encoding with the {} transfer syntax is not yet implemented",
+                args.transfer_syntax
             ));
-        } else if dot_count > 1 {
-            exit_with_error(format_args!(
-                "value '{}' is malformed; use the format 'ModuleName.valueName'",
-                value
-            ))
         }
 
-        let split = value.split(".").collect::<Vec<&str>>();
-        let module_name = split[0].trim();
-        let value_name = split[1].trim();
-
-        if module_name.is_empty() {
-            exit_with_error(format_args!("module name cannot be empty"));
-        }
-        if value_name.is_empty() {
-            exit_with_error(format_args!("value name cannot be empty"));
-        }
+        let input = read_input(&args);
 
-        let module = match context.lookup_module_by_name(module_name) {
-            Some(module) => module.ident.clone(),
-            None => exit_with_error(format_args!("module '{}' could not be found", module_name)),
+        let mode = DecodeMode::SpecificType {
+            source_ident: Some(ident.clone()),
+            component_name: None,
+            resolved: Box::new(resolved),
         };
-        let declared_value =
-            match context.lookup_value(&QualifiedIdentifier::new(module, value_name.to_string())) {
-                Some(value) => value,
-                None => exit_with_error(format_args!(
-                    "value '{}' could not be found in module '{}'",
-                    value_name, module_name
-                )),
+        let decoded_values = match decoder(from_ts, &mode, &input, &context) {
+            Ok(values) => values,
+            Err(err) => exit_with_error(format_args!("failed to decode {}: {}", from, err)),
+        };
+
+        // the input may contain more than one value, such as consecutive BER TLVs
+        let mut buf = Vec::new();
+        for decoded_value in &decoded_values {
+            let value = match decoded_value.resolve_with_mode(&context, &mode) {
+                Ok(value) => value,
+                Err(err) => exit_with_error(format_args!("failed to convert value: {}", err)),
             };
+            if !buf.is_empty() && is_text_syntax(args.transfer_syntax) {
+                buf.push(b'\n');
+            }
+            buf.extend(encode_value(
+                &args,
+                &context,
+                &value,
+                Some(ident.name.clone()),
+            ));
+        }
+        write_output(&args, &start, buf);
+    }
 
-        let value = match declared_value.value.resolve(&context) {
-            Ok(value) => value,
-            Err(err) => exit_with_error(format_args!(
-                "failed to resolve value: {}",
-                err.kind.message()
+    if let Some(ty) = &args.decode {
+        let transfer_syntax = args.transfer_syntax;
+        let ts = get_transfer_syntax(transfer_syntax);
+        let decoder = match ts.get_codec().decoder {
+            Some(decoder) => decoder,
+            None => exit_with_error(format_args!(
+                "decoding with the {} transfer syntax is not yet implemented",
+                transfer_syntax
             )),
         };
 
-        let mut buf = Vec::with_capacity(64 * 1024);
-        match encoder(ts, EncodeMode::Normal, &mut buf, &context, &value) {
-            Ok(()) => (),
-            Err(err) => exit_with_error(format_args!(
-                "failed to encode value: {}",
-                err.kind.message()
-            )),
-        }
-        let hex = hex::encode_upper(buf);
+        let mode = match ty {
+            Some(ty) => {
+                let ident = parse_qualified_identifier(&context, ty, "type");
+                let resolved = resolve_type(&context, &ident);
+                DecodeMode::SpecificType {
+                    source_ident: Some(ident),
+                    component_name: None,
+                    resolved: Box::new(resolved),
+                }
+            }
+            None => DecodeMode::Contextless,
+        };
+
+        let input = read_input(&args);
+        let (decoded_values, violations) = if args.validate_der {
+            if !matches!(ts, TransferSyntax::Basic(_)) {
+                exit_with_error(format_args!(
+                    "cannot validate {} data as DER; use a BER, CER or DER transfer syntax",
+                    transfer_syntax
+                ));
+            }
+            match der_validate_value(&input, &context, &mode) {
+                Ok(result) => result,
+                Err(err) => exit_with_error(format_args!("failed to decode BER: {}", err)),
+            }
+        } else {
+            match decoder(ts, &mode, &input, &context) {
+                Ok(values) => (values, Vec::new()),
+                Err(err) => exit_with_error(format_args!(
+                    "failed to decode {}: {}",
+                    transfer_syntax, err
+                )),
+            }
+        };
         if !args.silent {
-            println!("encoded in {}\n", elapsed_to_string(&start));
+            println!("decoded in {}\n", elapsed_to_string(&start));
+        }
+        if args.dump {
+            print!("{}", dump_decoded_values(&decoded_values));
+        } else {
+            for decoded_value in &decoded_values {
+                decode::print_decoded_value(decoded_value, 0);
+            }
+        }
+
+        if args.validate_der {
+            if violations.is_empty() {
+                println!("\nthe data is valid DER");
+            } else {
+                println!("\nfound {} DER violation(s):", violations.len());
+                for violation in &violations {
+                    println!("  {}", violation);
+                }
+                std::process::exit(1);
+            }
         }
-        println!("{}", hex);
     }
 }
@@ -56,7 +56,7 @@ fn serialize_decoded_value_kind(kind: DecodedValueKind) -> JsValue {
             ("data", "INTEGER".into(), bigint.into())
         }
         DecodedValueKind::BitString(data) => {
-            let bit_str = data.to_string().into();
+            let bit_str = num::BigUint::from_bytes_be(&data.data).to_string().into();
             let bigint = BigInt::new(&bit_str).expect("BIT STRING -> bigint");
             ("data", "BIT STRING".into(), bigint.into())
         }
@@ -69,6 +69,9 @@ fn serialize_decoded_value_kind(kind: DecodedValueKind) -> JsValue {
         DecodedValueKind::ObjectIdentifier(data) => {
             ("data", "OBJECT IDENTIFIER".into(), data.to_string().into())
         }
+        DecodedValueKind::RelativeOid(data) => {
+            ("data", "RELATIVE-OID".into(), data.to_string().into())
+        }
         DecodedValueKind::Real(data) => ("data", "REAL".into(), data.to_string().into()),
         DecodedValueKind::Enumerated(variant) => ("data", "ENUMERATED".into(), variant.into()),
         DecodedValueKind::Time(time) => ("data", "TIME".into(), time.source.into()),
@@ -102,6 +105,11 @@ fn serialize_decoded_value_kind(kind: DecodedValueKind) -> JsValue {
             Reflect::set(&obj, &"tz".into(), &tz).unwrap();
             ("data", "UTCTime".into(), obj.into())
         }
+        DecodedValueKind::GeneralizedTime(gt) => (
+            "data",
+            "GeneralizedTime".into(),
+            gt.to_ber_string().into(),
+        ),
         DecodedValueKind::Date(date) => ("data", "DATE".into(), serialize_date(&date)),
         DecodedValueKind::TimeOfDay(time_of_day) => (
             "data",
@@ -244,7 +252,7 @@ pub fn compiler_decode_value(
             DecodeMode::SpecificType {
                 source_ident: Some(ident),
                 component_name: None,
-                resolved,
+                resolved: Box::new(resolved),
             }
         }
         other => panic!("{}", other),
@@ -309,6 +317,7 @@ pub unsafe fn compiler_encode_value(
                 match encoder(
                     ts,
                     EncodeMode::Normal,
+                    &EncodeOptions::default(),
                     &mut libweb.buffer,
                     &libweb.context,
                     &value,
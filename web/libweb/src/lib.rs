@@ -276,7 +276,7 @@ fn serialize_builtin_value(
                         };
                         match &resolved_type.ty {
                             BuiltinType::Enumerated(enumerated) => {
-                                for item in enumerated {
+                                for item in &enumerated.items {
                                     let item_num = match &item.value {
                                         EnumerationItemValue::Implied(implied) => *implied,
                                         EnumerationItemValue::Specified(specified) => {